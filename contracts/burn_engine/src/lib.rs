@@ -10,14 +10,15 @@
 //! processo de queima (sem permissão), tornando-o totalmente trustless e descentralizado.
 //!
 //! ## How it works / Como funciona:
-//! 1. Tax Manager sends LUNES burn-fee to this contract
+//! 1. Tax Manager sends LUNES burn-fee to this contract and reports it via `receive_lunes`
 //! 2. Anyone calls `burn_cycle(iterations)` — each iteration consumes gas
 //! 3. Gas consumption = LUNES burned by the network
-//! 4. Total burned is tracked for transparency
+//! 4. Anyone calls `burn_lunes(amount)` — held LUNES move to an unspendable address
+//! 5. Total burned is tracked for transparency
 //!
 //! ## Security / Segurança:
 //! - No private keys needed — it's a contract, not a wallet
-//! - Permissionless — anyone can call burn_cycle()
+//! - Permissionless — anyone can call burn_cycle() (optionally restricted to an allowlist)
 //! - On-chain verifiable — all burns are auditable
 //! - No off-chain dependency — no bots needed
 //! - Max 100 iterations per call to prevent block gas limit issues
@@ -27,6 +28,35 @@
 
 #[ink::contract]
 pub mod burn_engine {
+    use ink::prelude::vec::Vec;
+    use ink::storage::Mapping;
+    use common::errors::ProtocolError;
+    use common::traits::BurnEngine as BurnEngineApi;
+    #[cfg(not(any(test, feature = "off-chain-peers")))]
+    use common::traits::PSP22;
+
+    /// Destination for burned LUNES. No key pair maps to the all-zero public key,
+    /// so tokens sent here can never be moved again.
+    pub const BURN_ADDRESS: [u8; 32] = [0u8; 32];
+
+    /// Default gas estimate per burn iteration reported in `BurnCycleExecuted`.
+    pub const DEFAULT_GAS_PER_ITERATION: u64 = 50_000;
+
+    /// Number of burn cycles kept in the recent-burns ring buffer.
+    pub const MAX_BURN_RECORDS: u32 = 50;
+
+    /// One executed burn cycle, kept for transparency.
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct BurnRecord {
+        pub caller: AccountId,
+        pub iterations: u32,
+        pub timestamp: Timestamp,
+    }
+
     /// Emitted when a burn cycle is executed
     #[ink(event)]
     pub struct BurnCycleExecuted {
@@ -44,6 +74,24 @@ pub mod burn_engine {
         amount: Balance,
     }
 
+    /// Emitted when held LUNES are sent to the burn address
+    #[ink(event)]
+    pub struct LunesBurned {
+        #[ink(topic)]
+        caller: AccountId,
+        amount: Balance,
+        total_burned: Balance,
+    }
+
+    /// Emitted when an unrelated token sent to the contract by mistake is recovered
+    #[ink(event)]
+    pub struct TokenRescued {
+        #[ink(topic)]
+        token: AccountId,
+        to: AccountId,
+        amount: Balance,
+    }
+
     #[ink(storage)]
     pub struct BurnEngine {
         /// Contract owner (Tax Manager or admin)
@@ -58,6 +106,26 @@ pub mod burn_engine {
         max_iterations_per_call: u32,
         /// Whether the contract is active
         active: bool,
+        /// When true, only allowlisted callers (or the owner) may run burn cycles
+        burn_restricted: bool,
+        /// Callers allowed to run burn cycles in restricted mode
+        allowed_callers: Mapping<AccountId, bool>,
+        /// LUNES PSP22 token awaiting burn (protected from rescue; set once)
+        lunes_token: Option<AccountId>,
+        /// Tax Manager allowed to report LUNES burn fees via `receive_lunes`
+        tax_manager: Option<AccountId>,
+        /// Total LUNES reported as received for burning
+        total_lunes_received: Balance,
+        /// Total LUNES sent to `BURN_ADDRESS` (real supply reduction)
+        total_lunes_burned: Balance,
+        /// Reported LUNES not yet sent to `BURN_ADDRESS`
+        pending_burn_balance: Balance,
+        /// Last `MAX_BURN_RECORDS` burn cycles (ring buffer)
+        recent_burns: Vec<BurnRecord>,
+        /// Slot the next record overwrites once the buffer is full
+        next_burn_slot: u32,
+        /// Gas estimate per iteration used for `gas_consumed_estimate` (calibrated on-chain)
+        gas_per_iteration: u64,
     }
 
     #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
@@ -69,13 +137,49 @@ pub mod burn_engine {
         ZeroIterations,
         /// Only owner can call this
         Unauthorized,
+        /// Token is the LUNES awaiting burn and cannot be rescued
+        ProtectedToken,
+        /// LUNES token must be configured before rescuing tokens
+        LunesTokenNotSet,
+        /// LUNES token was already configured
+        LunesTokenAlreadySet,
+        /// Transfer of the rescued token failed
+        TokenTransferFailed,
+        /// Amount must be > 0
+        ZeroAmount,
+        /// Transfer of LUNES to the burn address failed
+        BurnTransferFailed,
+        /// Gas per iteration must be > 0
+        ZeroGasPerIteration,
+    }
+
+    impl From<Error> for ProtocolError {
+        fn from(error: Error) -> Self {
+            match error {
+                Error::Unauthorized => ProtocolError::Unauthorized,
+                Error::Inactive => ProtocolError::Paused,
+                Error::TokenTransferFailed | Error::BurnTransferFailed => ProtocolError::TransferFailed,
+                Error::ZeroAmount | Error::ZeroIterations | Error::ZeroGasPerIteration => {
+                    ProtocolError::InvalidInput
+                }
+                _ => ProtocolError::Other,
+            }
+        }
+    }
+
+    impl BurnEngineApi for BurnEngine {
+        #[ink(message)]
+        fn receive_lunes(&mut self, amount: Balance) -> Result<(), ProtocolError> {
+            self._receive_lunes(amount).map_err(ProtocolError::from)
+        }
     }
 
     impl BurnEngine {
         /// Creates a new BurnEngine contract.
-        /// The caller becomes the owner.
+        /// The caller becomes the owner. `lunes_token` may be left unset and
+        /// configured once later with `set_lunes_token`.
         #[ink(constructor)]
-        pub fn new() -> Self {
+        pub fn new(lunes_token: Option<AccountId>) -> Self {
             Self {
                 owner: Self::env().caller(),
                 total_cycles: 0,
@@ -83,6 +187,16 @@ pub mod burn_engine {
                 burn_accumulator: 0,
                 max_iterations_per_call: 100,
                 active: true,
+                burn_restricted: false,
+                allowed_callers: Mapping::default(),
+                lunes_token,
+                tax_manager: None,
+                total_lunes_received: 0,
+                total_lunes_burned: 0,
+                pending_burn_balance: 0,
+                recent_burns: Vec::new(),
+                next_burn_slot: 0,
+                gas_per_iteration: DEFAULT_GAS_PER_ITERATION,
             }
         }
 
@@ -101,6 +215,9 @@ pub mod burn_engine {
             if iterations == 0 {
                 return Err(Error::ZeroIterations);
             }
+            if !self.is_allowed_caller(self.env().caller()) {
+                return Err(Error::Unauthorized);
+            }
 
             let capped = iterations.min(self.max_iterations_per_call);
 
@@ -116,13 +233,77 @@ pub mod burn_engine {
 
             self.total_cycles += 1;
             self.total_iterations += capped as u64;
+            self.record_burn(BurnRecord {
+                caller: self.env().caller(),
+                iterations: capped,
+                timestamp: self.env().block_timestamp(),
+            });
 
             self.env().emit_event(BurnCycleExecuted {
                 caller: self.env().caller(),
                 iterations: capped,
-                gas_consumed_estimate: capped as u64 * 50_000, // rough estimate per iteration
+                gas_consumed_estimate: (capped as u64).saturating_mul(self.gas_per_iteration),
+            });
+
+            Ok(())
+        }
+
+        /// Burn `amount` of the LUNES held by this contract by sending it to
+        /// `BURN_ADDRESS`, reducing circulating supply.
+        ///
+        /// **Permissionless** — same access rules as `burn_cycle`.
+        #[ink(message)]
+        pub fn burn_lunes(&mut self, amount: Balance) -> Result<(), Error> {
+            self._burn_lunes(amount)
+        }
+
+        /// Burn all LUNES reported by the Tax Manager and not yet burned.
+        ///
+        /// **Permissionless** — same access rules as `burn_cycle`.
+        #[ink(message)]
+        pub fn burn_pending(&mut self) -> Result<(), Error> {
+            self._burn_lunes(self.pending_burn_balance)
+        }
+
+        fn _burn_lunes(&mut self, amount: Balance) -> Result<(), Error> {
+            if !self.active {
+                return Err(Error::Inactive);
+            }
+            if amount == 0 {
+                return Err(Error::ZeroAmount);
+            }
+            let caller = self.env().caller();
+            if !self.is_allowed_caller(caller) {
+                return Err(Error::Unauthorized);
+            }
+            let lunes_token = self.lunes_token.ok_or(Error::LunesTokenNotSet)?;
+
+            self.token_transfer(lunes_token, AccountId::from(BURN_ADDRESS), amount)
+                .map_err(|_| Error::BurnTransferFailed)?;
+            self.total_lunes_burned = self.total_lunes_burned.saturating_add(amount);
+            self.pending_burn_balance = self.pending_burn_balance.saturating_sub(amount);
+
+            self.env().emit_event(LunesBurned {
+                caller,
+                amount,
+                total_burned: self.total_lunes_burned,
             });
+            Ok(())
+        }
 
+        /// Record LUNES transferred here as a burn fee.
+        /// Only the configured Tax Manager (or the owner) may report.
+        fn _receive_lunes(&mut self, amount: Balance) -> Result<(), Error> {
+            let caller = self.env().caller();
+            if caller != self.owner && Some(caller) != self.tax_manager {
+                return Err(Error::Unauthorized);
+            }
+            if amount == 0 {
+                return Err(Error::ZeroAmount);
+            }
+            self.total_lunes_received = self.total_lunes_received.saturating_add(amount);
+            self.pending_burn_balance = self.pending_burn_balance.saturating_add(amount);
+            self.env().emit_event(LunesReceived { from: caller, amount });
             Ok(())
         }
 
@@ -164,6 +345,72 @@ pub mod burn_engine {
             self.max_iterations_per_call
         }
 
+        /// Get the gas estimate per iteration reported in burn events.
+        #[ink(message)]
+        pub fn get_gas_per_iteration(&self) -> u64 {
+            self.gas_per_iteration
+        }
+
+        /// Check if burn cycles are restricted to allowlisted callers.
+        #[ink(message)]
+        pub fn is_burn_restricted(&self) -> bool {
+            self.burn_restricted
+        }
+
+        /// Check if an account may run burn cycles under the current mode.
+        #[ink(message)]
+        pub fn is_allowed_caller(&self, account: AccountId) -> bool {
+            !self.burn_restricted
+                || account == self.owner
+                || self.allowed_callers.get(account).unwrap_or(false)
+        }
+
+        /// Get the protected LUNES token address, if configured.
+        #[ink(message)]
+        pub fn get_lunes_token(&self) -> Option<AccountId> {
+            self.lunes_token
+        }
+
+        /// Get the Tax Manager allowed to report burn fees, if configured.
+        #[ink(message)]
+        pub fn get_tax_manager(&self) -> Option<AccountId> {
+            self.tax_manager
+        }
+
+        /// Get total LUNES received from burn fees since deployment.
+        #[ink(message)]
+        pub fn get_total_lunes_received(&self) -> Balance {
+            self.total_lunes_received
+        }
+
+        /// Get the most recent burn cycles (up to `MAX_BURN_RECORDS`), oldest first.
+        #[ink(message)]
+        pub fn get_recent_burns(&self) -> Vec<BurnRecord> {
+            let slot = self.next_burn_slot as usize;
+            let mut burns = Vec::with_capacity(self.recent_burns.len());
+            burns.extend_from_slice(&self.recent_burns[slot..]);
+            burns.extend_from_slice(&self.recent_burns[..slot]);
+            burns
+        }
+
+        /// Get total LUNES sent to the burn address since deployment.
+        #[ink(message)]
+        pub fn get_total_lunes_burned(&self) -> Balance {
+            self.total_lunes_burned
+        }
+
+        /// Get reported LUNES waiting for `burn_pending`.
+        #[ink(message)]
+        pub fn get_pending_burn_balance(&self) -> Balance {
+            self.pending_burn_balance
+        }
+
+        /// Get the unspendable address burned LUNES are sent to.
+        #[ink(message)]
+        pub fn get_burn_address(&self) -> AccountId {
+            AccountId::from(BURN_ADDRESS)
+        }
+
         /// Get contract balance (LUNES waiting to be burned via gas).
         #[ink(message)]
         pub fn get_balance(&self) -> Balance {
@@ -180,6 +427,17 @@ pub mod burn_engine {
             Ok(())
         }
 
+        /// Calibrate the gas estimate per iteration to the measured runtime cost (owner only).
+        #[ink(message)]
+        pub fn set_gas_per_iteration(&mut self, gas: u64) -> Result<(), Error> {
+            self.ensure_owner()?;
+            if gas == 0 {
+                return Err(Error::ZeroGasPerIteration);
+            }
+            self.gas_per_iteration = gas;
+            Ok(())
+        }
+
         /// Pause/unpause the burn engine (owner only).
         #[ink(message)]
         pub fn set_active(&mut self, active: bool) -> Result<(), Error> {
@@ -188,6 +446,60 @@ pub mod burn_engine {
             Ok(())
         }
 
+        /// Restrict burn cycles to allowlisted callers, or reopen them to everyone (owner only).
+        #[ink(message)]
+        pub fn set_burn_restricted(&mut self, enabled: bool) -> Result<(), Error> {
+            self.ensure_owner()?;
+            self.burn_restricted = enabled;
+            Ok(())
+        }
+
+        /// Add or remove an account from the burn allowlist (owner only).
+        #[ink(message)]
+        pub fn set_allowed_caller(&mut self, account: AccountId, allowed: bool) -> Result<(), Error> {
+            self.ensure_owner()?;
+            if allowed {
+                self.allowed_callers.insert(account, &true);
+            } else {
+                self.allowed_callers.remove(account);
+            }
+            Ok(())
+        }
+
+        /// Set the LUNES token protected from rescue (owner only, once).
+        #[ink(message)]
+        pub fn set_lunes_token(&mut self, token: AccountId) -> Result<(), Error> {
+            self.ensure_owner()?;
+            if self.lunes_token.is_some() {
+                return Err(Error::LunesTokenAlreadySet);
+            }
+            self.lunes_token = Some(token);
+            Ok(())
+        }
+
+        /// Set the Tax Manager allowed to report burn fees (owner only).
+        #[ink(message)]
+        pub fn set_tax_manager(&mut self, tax_manager: AccountId) -> Result<(), Error> {
+            self.ensure_owner()?;
+            self.tax_manager = Some(tax_manager);
+            Ok(())
+        }
+
+        /// Recover a PSP22 token sent here by mistake (owner only).
+        /// LUNES awaiting burn can never be withdrawn.
+        #[ink(message)]
+        pub fn rescue_foreign_token(&mut self, token: AccountId, to: AccountId, amount: Balance) -> Result<(), Error> {
+            self.ensure_owner()?;
+            let lunes_token = self.lunes_token.ok_or(Error::LunesTokenNotSet)?;
+            if token == lunes_token {
+                return Err(Error::ProtectedToken);
+            }
+            self.token_transfer(token, to, amount)
+                .map_err(|_| Error::TokenTransferFailed)?;
+            self.env().emit_event(TokenRescued { token, to, amount });
+            Ok(())
+        }
+
         /// Transfer ownership (owner only).
         #[ink(message)]
         pub fn transfer_ownership(&mut self, new_owner: AccountId) -> Result<(), Error> {
@@ -198,6 +510,33 @@ pub mod burn_engine {
 
         // === Internal ===
 
+        /// Appends a record, overwriting the oldest one once the buffer is full.
+        fn record_burn(&mut self, record: BurnRecord) {
+            if self.recent_burns.len() < MAX_BURN_RECORDS as usize {
+                self.recent_burns.push(record);
+            } else {
+                self.recent_burns[self.next_burn_slot as usize] = record;
+                self.next_burn_slot = (self.next_burn_slot + 1) % MAX_BURN_RECORDS;
+            }
+        }
+
+        #[cfg(not(any(test, feature = "off-chain-peers")))]
+        fn token_transfer(&self, token: AccountId, to: AccountId, amount: Balance) -> Result<(), ink::LangError> {
+            let mut psp22: ink::contract_ref!(PSP22) = token.into();
+            psp22.transfer(to, amount)
+        }
+
+        /// The off-chain test environment cannot invoke other contracts: a token deployed
+        /// in `mock_peers` receives the transfer, otherwise it settles on the mock ledger.
+        #[cfg(any(test, feature = "off-chain-peers"))]
+        fn token_transfer(&self, token: AccountId, to: AccountId, amount: Balance) -> Result<(), ink::LangError> {
+            match mock_peers::call(token, mock_peers::PeerCall::Transfer(to, amount)) {
+                Some(true) => Ok(()),
+                Some(false) => Err(ink::LangError::CouldNotReadInput),
+                None => mock_psp22::transfer(token, self.env().account_id(), to, amount),
+            }
+        }
+
         fn ensure_owner(&self) -> Result<(), Error> {
             if self.env().caller() != self.owner {
                 Err(Error::Unauthorized)
@@ -207,6 +546,71 @@ pub mod burn_engine {
         }
     }
 
+    /// PSP22 balances used by `token_transfer` when no token is deployed in `mock_peers`.
+    #[cfg(any(test, feature = "off-chain-peers"))]
+    pub mod mock_psp22 {
+        use super::{AccountId, Balance};
+        use std::cell::RefCell;
+        use std::collections::HashMap;
+
+        thread_local! {
+            static BALANCES: RefCell<HashMap<(AccountId, AccountId), Balance>> = RefCell::new(HashMap::new());
+        }
+
+        pub fn set_balance(token: AccountId, owner: AccountId, amount: Balance) {
+            BALANCES.with(|b| b.borrow_mut().insert((token, owner), amount));
+        }
+
+        pub fn balance_of(token: AccountId, owner: AccountId) -> Balance {
+            BALANCES.with(|b| *b.borrow().get(&(token, owner)).unwrap_or(&0))
+        }
+
+        /// Unlike the TaxManager ledger this one is strict, so an over-burn fails.
+        pub fn transfer(token: AccountId, from: AccountId, to: AccountId, amount: Balance) -> Result<(), ink::LangError> {
+            let from_balance = balance_of(token, from);
+            if from_balance < amount {
+                return Err(ink::LangError::CouldNotReadInput);
+            }
+            set_balance(token, from, from_balance - amount);
+            set_balance(token, to, balance_of(token, to) + amount);
+            Ok(())
+        }
+    }
+
+    /// Real token contracts deployed next to the BurnEngine in tests. A registered
+    /// token receives the PSP22 transfers the BurnEngine would make on-chain.
+    #[cfg(any(test, feature = "off-chain-peers"))]
+    pub mod mock_peers {
+        use super::{AccountId, Balance};
+        use std::cell::RefCell;
+
+        /// A cross-contract call made by the BurnEngine.
+        pub enum PeerCall {
+            /// `PSP22::transfer(to, amount)` from the BurnEngine's balance.
+            Transfer(AccountId, Balance),
+        }
+
+        type Peer = Box<dyn FnMut(PeerCall) -> bool>;
+
+        thread_local! {
+            static PEERS: RefCell<Vec<(AccountId, Peer)>> = RefCell::new(Vec::new());
+        }
+
+        pub fn register(target: AccountId, peer: impl FnMut(PeerCall) -> bool + 'static) {
+            PEERS.with(|p| p.borrow_mut().push((target, Box::new(peer))));
+        }
+
+        /// Delivers `call` to the peer deployed at `target`; `None` if there is none.
+        pub fn call(target: AccountId, call: PeerCall) -> Option<bool> {
+            PEERS.with(|p| {
+                p.borrow_mut()
+                    .iter_mut()
+                    .find(|(at, _)| *at == target)
+                    .map(|(_, peer)| peer(call))
+            })
+        }
+    }
+
     #[cfg(test)]
     mod tests {
         use super::*;
@@ -216,7 +620,7 @@ pub mod burn_engine {
         fn setup() -> (BurnEngine, DefaultAccounts<DefaultEnvironment>) {
             let accounts = ink::env::test::default_accounts::<DefaultEnvironment>();
             set_caller::<DefaultEnvironment>(accounts.alice);
-            let contract = BurnEngine::new();
+            let contract = BurnEngine::new(None);
             (contract, accounts)
         }
 
@@ -248,6 +652,29 @@ pub mod burn_engine {
             assert_eq!(contract.get_total_iterations(), 100);
         }
 
+        #[ink::test]
+        fn gas_per_iteration_sets_event_estimate() {
+            type Event = <BurnEngine as ::ink::reflect::ContractEventBase>::Type;
+
+            let (mut contract, accounts) = setup();
+            assert_eq!(contract.get_gas_per_iteration(), DEFAULT_GAS_PER_ITERATION);
+
+            set_caller::<DefaultEnvironment>(accounts.bob);
+            assert_eq!(contract.set_gas_per_iteration(80_000), Err(Error::Unauthorized));
+            set_caller::<DefaultEnvironment>(accounts.alice);
+            assert_eq!(contract.set_gas_per_iteration(0), Err(Error::ZeroGasPerIteration));
+            assert_eq!(contract.set_gas_per_iteration(80_000), Ok(()));
+
+            contract.burn_cycle(10).unwrap();
+            let estimates: Vec<u64> = ink::env::test::recorded_events()
+                .filter_map(|e| match <Event as scale::Decode>::decode(&mut &e.data[..]) {
+                    Ok(Event::BurnCycleExecuted(b)) => Some(b.gas_consumed_estimate),
+                    _ => None,
+                })
+                .collect();
+            assert_eq!(estimates, vec![800_000]);
+        }
+
         #[ink::test]
         fn burn_cycle_zero_fails() {
             let (mut contract, _) = setup();
@@ -295,6 +722,185 @@ pub mod burn_engine {
             assert_eq!(contract.get_max_iterations(), 500);
         }
 
+        #[ink::test]
+        fn recent_burns_are_recorded_in_order() {
+            let (mut contract, accounts) = setup();
+            contract.burn_cycle(5).unwrap();
+            ink::env::test::set_block_timestamp::<DefaultEnvironment>(1_000);
+            set_caller::<DefaultEnvironment>(accounts.bob);
+            contract.burn_cycle(200).unwrap();
+
+            assert_eq!(
+                contract.get_recent_burns(),
+                vec![
+                    BurnRecord { caller: accounts.alice, iterations: 5, timestamp: 0 },
+                    BurnRecord { caller: accounts.bob, iterations: 100, timestamp: 1_000 },
+                ]
+            );
+        }
+
+        #[ink::test]
+        fn recent_burns_cap_and_evict_oldest() {
+            let (mut contract, _) = setup();
+            for i in 1..=MAX_BURN_RECORDS + 2 {
+                contract.burn_cycle(i).unwrap();
+            }
+
+            let burns = contract.get_recent_burns();
+            assert_eq!(burns.len(), MAX_BURN_RECORDS as usize);
+            // Cycles 1 and 2 were evicted; order stays oldest first
+            assert_eq!(burns[0].iterations, 3);
+            assert_eq!(burns[burns.len() - 1].iterations, MAX_BURN_RECORDS + 2);
+            assert!(burns.windows(2).all(|w| w[0].iterations < w[1].iterations));
+        }
+
+        #[ink::test]
+        fn permissionless_mode_allows_anyone() {
+            let (mut contract, accounts) = setup();
+            assert!(!contract.is_burn_restricted());
+
+            set_caller::<DefaultEnvironment>(accounts.django);
+            assert!(contract.burn_cycle(5).is_ok());
+        }
+
+        #[ink::test]
+        fn restricted_mode_only_allows_allowlisted() {
+            let (mut contract, accounts) = setup();
+            contract.set_burn_restricted(true).unwrap();
+            contract.set_allowed_caller(accounts.bob, true).unwrap();
+
+            set_caller::<DefaultEnvironment>(accounts.bob);
+            assert!(contract.burn_cycle(5).is_ok());
+
+            set_caller::<DefaultEnvironment>(accounts.charlie);
+            assert_eq!(contract.burn_cycle(5), Err(Error::Unauthorized));
+
+            // Removing from the allowlist revokes access
+            set_caller::<DefaultEnvironment>(accounts.alice);
+            contract.set_allowed_caller(accounts.bob, false).unwrap();
+            set_caller::<DefaultEnvironment>(accounts.bob);
+            assert_eq!(contract.burn_cycle(5), Err(Error::Unauthorized));
+            assert_eq!(contract.get_total_cycles(), 1);
+        }
+
+        #[ink::test]
+        fn owner_always_allowed_in_restricted_mode() {
+            let (mut contract, accounts) = setup();
+            contract.set_burn_restricted(true).unwrap();
+            assert!(contract.burn_cycle(5).is_ok());
+
+            // Non-owner cannot manage the allowlist
+            set_caller::<DefaultEnvironment>(accounts.bob);
+            assert_eq!(contract.set_allowed_caller(accounts.bob, true), Err(Error::Unauthorized));
+            assert_eq!(contract.set_burn_restricted(false), Err(Error::Unauthorized));
+        }
+
+        #[ink::test]
+        fn rescue_foreign_token_respects_protected_set() {
+            let (mut contract, accounts) = setup();
+            let foreign = AccountId::from([0x77; 32]);
+            mock_psp22::set_balance(foreign, ink::env::account_id::<DefaultEnvironment>(), 1);
+
+            // Protected set must be configured first
+            assert_eq!(contract.rescue_foreign_token(foreign, accounts.alice, 1), Err(Error::LunesTokenNotSet));
+
+            contract.set_lunes_token(accounts.eve).unwrap();
+            assert_eq!(contract.set_lunes_token(foreign), Err(Error::LunesTokenAlreadySet));
+
+            assert!(contract.rescue_foreign_token(foreign, accounts.alice, 1).is_ok());
+            assert_eq!(
+                contract.rescue_foreign_token(accounts.eve, accounts.alice, 1),
+                Err(Error::ProtectedToken)
+            );
+
+            set_caller::<DefaultEnvironment>(accounts.bob);
+            assert_eq!(contract.rescue_foreign_token(foreign, accounts.bob, 1), Err(Error::Unauthorized));
+        }
+
+        #[ink::test]
+        fn receive_lunes_only_from_tax_manager() {
+            let (mut contract, accounts) = setup();
+            contract.set_tax_manager(accounts.bob).unwrap();
+
+            set_caller::<DefaultEnvironment>(accounts.bob);
+            assert!(BurnEngineApi::receive_lunes(&mut contract, 500).is_ok());
+            assert_eq!(contract._receive_lunes(0), Err(Error::ZeroAmount));
+
+            set_caller::<DefaultEnvironment>(accounts.charlie);
+            assert_eq!(contract._receive_lunes(500), Err(Error::Unauthorized));
+            assert_eq!(BurnEngineApi::receive_lunes(&mut contract, 500), Err(ProtocolError::Unauthorized));
+            assert_eq!(contract.set_tax_manager(accounts.charlie), Err(Error::Unauthorized));
+
+            assert_eq!(contract.get_total_lunes_received(), 500);
+        }
+
+        #[ink::test]
+        fn burn_lunes_moves_balance_to_burn_address() {
+            let accounts = ink::env::test::default_accounts::<DefaultEnvironment>();
+            set_caller::<DefaultEnvironment>(accounts.alice);
+            let lunes = accounts.eve;
+            let mut contract = BurnEngine::new(Some(lunes));
+            let this = ink::env::account_id::<DefaultEnvironment>();
+            let burn_address = contract.get_burn_address();
+            mock_psp22::set_balance(lunes, this, 1_000);
+
+            // Permissionless
+            set_caller::<DefaultEnvironment>(accounts.django);
+            assert!(contract.burn_lunes(600).is_ok());
+            assert_eq!(mock_psp22::balance_of(lunes, this), 400);
+            assert_eq!(mock_psp22::balance_of(lunes, burn_address), 600);
+            assert_eq!(contract.get_total_lunes_burned(), 600);
+
+            assert_eq!(contract.burn_lunes(0), Err(Error::ZeroAmount));
+            assert_eq!(contract.burn_lunes(401), Err(Error::BurnTransferFailed));
+            assert!(contract.burn_lunes(400).is_ok());
+            assert_eq!(mock_psp22::balance_of(lunes, burn_address), 1_000);
+            assert_eq!(contract.get_total_lunes_burned(), 1_000);
+
+            // Gas-burn counters are unaffected
+            assert_eq!(contract.get_total_cycles(), 0);
+        }
+
+        #[ink::test]
+        fn burn_lunes_requires_token_and_active() {
+            let (mut contract, accounts) = setup();
+            assert_eq!(contract.burn_lunes(1), Err(Error::LunesTokenNotSet));
+
+            contract.set_lunes_token(accounts.eve).unwrap();
+            contract.set_active(false).unwrap();
+            assert_eq!(contract.burn_lunes(1), Err(Error::Inactive));
+        }
+
+        #[ink::test]
+        fn notified_lunes_are_burned_by_burn_pending() {
+            let accounts = ink::env::test::default_accounts::<DefaultEnvironment>();
+            set_caller::<DefaultEnvironment>(accounts.alice);
+            let lunes = accounts.eve;
+            let mut contract = BurnEngine::new(Some(lunes));
+            contract.set_tax_manager(accounts.bob).unwrap();
+            let this = ink::env::account_id::<DefaultEnvironment>();
+            let burn_address = contract.get_burn_address();
+            assert_eq!(contract.burn_pending(), Err(Error::ZeroAmount));
+
+            // Tax Manager transfers, then notifies
+            mock_psp22::set_balance(lunes, this, 700);
+            set_caller::<DefaultEnvironment>(accounts.bob);
+            assert!(BurnEngineApi::receive_lunes(&mut contract, 300).is_ok());
+            assert!(BurnEngineApi::receive_lunes(&mut contract, 400).is_ok());
+            assert_eq!(contract.get_pending_burn_balance(), 700);
+
+            set_caller::<DefaultEnvironment>(accounts.charlie);
+            assert_eq!(BurnEngineApi::receive_lunes(&mut contract, 1_000), Err(ProtocolError::Unauthorized));
+            assert_eq!(contract.get_pending_burn_balance(), 700);
+
+            // Anyone can trigger the burn
+            assert!(contract.burn_pending().is_ok());
+            assert_eq!(contract.get_pending_burn_balance(), 0);
+            assert_eq!(mock_psp22::balance_of(lunes, this), 0);
+            assert_eq!(mock_psp22::balance_of(lunes, burn_address), 700);
+            assert_eq!(contract.get_total_lunes_burned(), 700);
+        }
+
         #[ink::test]
         fn transfer_ownership_works() {
             let (mut contract, accounts) = setup();
@@ -52,4 +52,6 @@ pub struct FeeConfig {
 pub enum OperationType {
     Mint,
     Burn,
+    /// Peer-to-peer LUSDT transfer (only taxed when enabled on the token).
+    Transfer,
 }
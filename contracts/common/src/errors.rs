@@ -0,0 +1,21 @@
+/// Error returned across contract boundaries by the shared traits.
+/// Each contract maps its own `Error` into these cases at the trait-impl layer,
+/// so callers see why a cross-contract call failed instead of a decode error.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub enum ProtocolError {
+    /// Caller lacks the required role or authorization.
+    Unauthorized,
+    /// The callee (or the relevant subsystem) is paused.
+    Paused,
+    /// Not enough tokens to complete the operation.
+    InsufficientBalance,
+    /// Arithmetic overflow or underflow.
+    Overflow,
+    /// A token transfer made by the callee failed.
+    TransferFailed,
+    /// Arguments were rejected (e.g. a zero amount).
+    InvalidInput,
+    /// Any other callee-specific failure.
+    Other,
+}
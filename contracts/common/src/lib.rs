@@ -1,4 +1,5 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
 pub mod common_types;
+pub mod errors;
 pub mod traits;
@@ -1,4 +1,5 @@
 use crate::common_types::{FeeType, OperationType};
+use crate::errors::ProtocolError;
 use ink::primitives::AccountId;
 
 /// The `TaxManager` trait defines the public interface for the tax management contract.
@@ -14,7 +15,7 @@ pub trait TaxManager {
         operation: OperationType,
         user: AccountId,
         lusdt_amount: u128,
-    ) -> Result<(), ink::LangError>;
+    ) -> Result<(), ProtocolError>;
 
     /// @notice Processes fees with explicit fee type selection.
     /// @dev v2 entry point for single-currency fees.
@@ -26,7 +27,7 @@ pub trait TaxManager {
         user: AccountId,
         lusdt_amount: u128,
         fee_type: FeeType,
-    ) -> Result<(), ink::LangError>;
+    ) -> Result<(), ProtocolError>;
 
     /// @notice v3 Dual-fee model: charges stablecoin fee (revenue) + LUNES fee (burn).
     /// @dev Mint: USDT fee + LUNES burn. Burn: LUSDT fee + LUNES burn.
@@ -41,7 +42,7 @@ pub trait TaxManager {
         user: AccountId,
         lusdt_amount: u128,
         stablecoin_fee_type: FeeType,
-    ) -> Result<(), ink::LangError>;
+    ) -> Result<(), ProtocolError>;
 
     /// @notice Charges only the LUNES deflationary burn fee (0.10%) and sends to BurnEngine.
     /// @dev Used by mint — the stablecoin (USDT) fee is handled by the bridge BEFORE minting.
@@ -54,7 +55,7 @@ pub trait TaxManager {
         operation: OperationType,
         user: AccountId,
         lusdt_amount: u128,
-    ) -> Result<(), ink::LangError>;
+    ) -> Result<(), ProtocolError>;
 }
 
 /// The `StakingManager` trait defines the public interface for the staking contract.
@@ -66,16 +67,31 @@ pub trait StakingManager {
     /// @dev Called by Tax Manager or bridge after fee collection. Updates reward accounting.
     /// The caller must have already approved this contract to spend `amount` LUSDT.
     #[ink(message)]
-    fn deposit_rewards(&mut self, amount: u128) -> Result<(), ink::LangError>;
+    fn deposit_rewards(&mut self, amount: u128) -> Result<(), ProtocolError>;
 
     /// @notice Notify the contract that LUSDT rewards were transferred directly (no transfer_from).
     /// @dev Only callable by owner or authorized address. Used when Tax Manager transfers directly.
     #[ink(message)]
-    fn notify_reward_amount(&mut self, amount: u128) -> Result<(), ink::LangError>;
+    fn notify_reward_amount(&mut self, amount: u128) -> Result<(), ProtocolError>;
+
+    /// @notice Notify the contract that LUNES rewards were transferred directly (no transfer_from).
+    /// @dev Authorized like `notify_reward_amount`. Used for the LUNES staking share of fees.
+    #[ink(message)]
+    fn notify_lunes_reward_amount(&mut self, amount: u128) -> Result<(), ProtocolError>;
+}
+
+/// The `BurnEngine` trait is the interface the Tax Manager uses to hand LUNES burn fees
+/// to the BurnEngine contract.
+#[ink::trait_definition]
+pub trait BurnEngine {
+    /// @notice Records LUNES already transferred to the BurnEngine for burning.
+    /// @dev Only callable by the configured Tax Manager or the BurnEngine owner.
+    #[ink(message)]
+    fn receive_lunes(&mut self, amount: u128) -> Result<(), ProtocolError>;
 }
 
 /// A minimal PSP22 trait for token interaction.
-/// Defines the `transfer_from` function needed for fee collection.
+/// Shared by every contract that moves or queries PSP22 balances.
 #[ink::trait_definition]
 pub trait PSP22 {
     /// @notice Transfers `value` amount of tokens from `from` to `to`.
@@ -92,4 +108,20 @@ pub trait PSP22 {
     /// @notice Transfers `value` amount of tokens from the caller's account to `to`.
     #[ink(message)]
     fn transfer(&mut self, to: AccountId, value: u128) -> Result<(), ink::LangError>;
+
+    /// @notice Returns the token balance of `owner`.
+    #[ink(message)]
+    fn balance_of(&self, owner: AccountId) -> u128;
+
+    /// @notice Returns how much `spender` may still transfer from `owner`.
+    #[ink(message)]
+    fn allowance(&self, owner: AccountId, spender: AccountId) -> u128;
+}
+
+/// Minimal on-chain price feed interface consumed by the Tax Manager.
+#[ink::trait_definition]
+pub trait PriceOracle {
+    /// @notice Returns the latest LUNES price in USD (6 decimals) and the timestamp it was observed at.
+    #[ink(message)]
+    fn get_price(&self) -> (u128, u64);
 }
@@ -0,0 +1,366 @@
+//! The real contracts deployed side by side in the off-chain environment.
+//!
+//! Each contract keeps its storage under its own address. TaxManager and LusdtToken are
+//! built with `off-chain-peers`, so the calls they make to their peers are delivered
+//! through their `mock_peers` to the instances deployed here, with the sender as the
+//! caller. LUNES and LUSDT moved by TaxManager live in its `mock_psp22` ledger.
+//! LusdtToken sends its transfer tax to TaxManager and skips its mint and burn fee calls.
+//! BurnEngine is built with `off-chain-peers` too; its PSP22 transfers reach a deployed
+//! `MockLunesToken` when one is registered.
+
+use burn_engine::burn_engine::{mock_peers as burn_peers, BurnEngine, Error as BurnError};
+use common::common_types::{FeeType, OperationType};
+use common::traits::{
+    BurnEngine as BurnEngineApi, StakingManager as StakingManagerApi, TaxManager as TaxManagerApi, PSP22,
+};
+use ink::env::{test, DefaultEnvironment};
+use ink::primitives::AccountId;
+use lusdt_token::lusdt_token::{mock_peers as token_peers, LusdtToken, PAUSER_ROLE};
+use mock_lunes_token::mock_lunes_token::MockLunesToken;
+use staking_manager::staking_manager::{StakerInfo, StakingManager};
+use std::{cell::RefCell, rc::Rc};
+use tax_manager::tax_manager::{
+    mock_peers::{self, PeerCall},
+    mock_psp22, DistributionWallets, TaxManager,
+};
+
+fn tax_manager_at() -> AccountId {
+    AccountId::from([0x71; 32])
+}
+
+fn staking_manager_at() -> AccountId {
+    AccountId::from([0x72; 32])
+}
+
+fn lusdt_token_at() -> AccountId {
+    AccountId::from([0x73; 32])
+}
+
+fn burn_engine_at() -> AccountId {
+    AccountId::from([0x74; 32])
+}
+
+/// The protocol's LUNES lives in TaxManager's mock ledger, so it needs no deployed contract.
+fn lunes_token_at() -> AccountId {
+    AccountId::from([0x75; 32])
+}
+
+/// A deployed `MockLunesToken`, for paths that move LUNES through the PSP22 messages.
+fn lunes_contract_at() -> AccountId {
+    AccountId::from([0x76; 32])
+}
+
+const MIN_STAKE: u128 = 1_000_000;
+
+/// Runs `f` as `caller` inside the contract at `contract`, then restores the
+/// previous callee and caller.
+fn at<R>(contract: AccountId, caller: AccountId, f: impl FnOnce() -> R) -> R {
+    let prev_callee = test::callee::<DefaultEnvironment>();
+    let prev_caller = ink::env::caller::<DefaultEnvironment>();
+    test::set_callee::<DefaultEnvironment>(contract);
+    test::set_caller::<DefaultEnvironment>(caller);
+    let result = f();
+    test::set_callee::<DefaultEnvironment>(prev_callee);
+    test::set_caller::<DefaultEnvironment>(prev_caller);
+    result
+}
+
+/// The protocol as deployed by `owner`, with TaxManager's peer calls wired up.
+struct Protocol {
+    owner: AccountId,
+    tax_manager: Rc<RefCell<TaxManager>>,
+    staking: Rc<RefCell<StakingManager>>,
+    token: Rc<RefCell<LusdtToken>>,
+    burn_engine: Rc<RefCell<BurnEngine>>,
+}
+
+impl Protocol {
+    fn deploy() -> Self {
+        let accounts = test::default_accounts::<DefaultEnvironment>();
+        let owner = accounts.alice;
+        let wallets = DistributionWallets {
+            dev_solana: accounts.bob,
+            dev_lunes: accounts.bob,
+            insurance_fund: accounts.charlie,
+            staking_rewards_pool: accounts.django,
+        };
+
+        let tax_manager = Rc::new(RefCell::new(at(tax_manager_at(), owner, || {
+            TaxManager::new(lunes_token_at(), lusdt_token_at(), wallets, 500_000)
+        })));
+        let staking = Rc::new(RefCell::new(at(staking_manager_at(), owner, || {
+            StakingManager::new(lunes_token_at(), lusdt_token_at(), MIN_STAKE)
+        })));
+        let token = Rc::new(RefCell::new(at(lusdt_token_at(), owner, || {
+            LusdtToken::new(tax_manager_at(), accounts.eve, owner)
+        })));
+        let burn_engine = Rc::new(RefCell::new(at(burn_engine_at(), owner, || {
+            BurnEngine::new(Some(lunes_token_at()))
+        })));
+
+        let peer = staking.clone();
+        mock_peers::register(staking_manager_at(), move |call| match call {
+            PeerCall::Pause => at(staking_manager_at(), tax_manager_at(), || peer.borrow_mut().pause()).is_ok(),
+            PeerCall::NotifyRewardAmount(amount) => at(staking_manager_at(), tax_manager_at(), || {
+                peer.borrow_mut().notify_reward_amount(amount)
+            })
+            .is_ok(),
+            PeerCall::NotifyLunesRewardAmount(amount) => at(staking_manager_at(), tax_manager_at(), || {
+                peer.borrow_mut().notify_lunes_reward_amount(amount)
+            })
+            .is_ok(),
+            _ => false,
+        });
+        let peer = token.clone();
+        mock_peers::register(lusdt_token_at(), move |call| match call {
+            PeerCall::EmergencyPause => at(lusdt_token_at(), tax_manager_at(), || {
+                peer.borrow_mut().emergency_pause("TaxManager pause_all".into())
+            })
+            .is_ok(),
+            _ => false,
+        });
+        let peer = staking.clone();
+        token_peers::register(staking_manager_at(), move |call| match call {
+            token_peers::PeerCall::NotifyRewardAmount(amount) => at(staking_manager_at(), lusdt_token_at(), || {
+                peer.borrow_mut().notify_reward_amount(amount)
+            })
+            .is_ok(),
+            _ => false,
+        });
+        let peer = tax_manager.clone();
+        token_peers::register(tax_manager_at(), move |call| match call {
+            token_peers::PeerCall::ProcessFees(operation, user, amount) => at(tax_manager_at(), lusdt_token_at(), || {
+                peer.borrow_mut().process_fees(operation, user, amount)
+            })
+            .is_ok(),
+            _ => false,
+        });
+        let peer = burn_engine.clone();
+        mock_peers::register(burn_engine_at(), move |call| match call {
+            PeerCall::ReceiveLunes(amount) => at(burn_engine_at(), tax_manager_at(), || {
+                peer.borrow_mut().receive_lunes(amount)
+            })
+            .is_ok(),
+            _ => false,
+        });
+
+        let protocol = Self { owner, tax_manager, staking, token, burn_engine };
+        protocol.tax(|tm| tm.set_staking_manager(Some(staking_manager_at()))).unwrap();
+        protocol.tax(|tm| tm.set_burn_engine(burn_engine_at())).unwrap();
+        at(burn_engine_at(), owner, || protocol.burn_engine.borrow_mut().set_tax_manager(tax_manager_at())).unwrap();
+        protocol
+    }
+
+    /// Calls TaxManager as the owner.
+    fn tax<R>(&self, f: impl FnOnce(&mut TaxManager) -> R) -> R {
+        at(tax_manager_at(), self.owner, || f(&mut self.tax_manager.borrow_mut()))
+    }
+
+    /// Calls LusdtToken as `caller`.
+    fn token_as<R>(&self, caller: AccountId, f: impl FnOnce(&mut LusdtToken) -> R) -> R {
+        at(lusdt_token_at(), caller, || f(&mut self.token.borrow_mut()))
+    }
+
+    /// Calls StakingManager as the owner.
+    fn staking<R>(&self, f: impl FnOnce(&mut StakingManager) -> R) -> R {
+        at(staking_manager_at(), self.owner, || f(&mut self.staking.borrow_mut()))
+    }
+
+    /// Gives `staker` a stake of `amount` without moving LUNES, through the migration path.
+    fn seed_staker(&self, staker: AccountId, amount: u128) {
+        self.staking(|sm| sm.set_migration_source(Some(self.owner))).unwrap();
+        let info = StakerInfo { amount, ..Default::default() };
+        self.staking(|sm| sm.migrate_in(staker, info)).unwrap();
+    }
+}
+
+#[ink::test]
+fn pause_all_pauses_every_deployed_contract() {
+    let protocol = Protocol::deploy();
+    let owner = protocol.owner;
+
+    // Without the roles the peers refuse, and only TaxManager is paused
+    assert_eq!(protocol.tax(|tm| tm.pause_all()), Ok(()));
+    assert!(protocol.tax(|tm| tm.is_paused()));
+    assert!(!protocol.staking.borrow().is_paused());
+    assert!(!protocol.token.borrow().is_paused());
+    protocol.tax(|tm| tm.unpause()).unwrap();
+
+    // Make TaxManager the StakingManager's pauser and a LusdtToken PAUSER
+    at(staking_manager_at(), owner, || protocol.staking.borrow_mut().set_pauser(Some(tax_manager_at()))).unwrap();
+    at(lusdt_token_at(), owner, || protocol.token.borrow_mut().grant_role(PAUSER_ROLE, tax_manager_at())).unwrap();
+
+    assert_eq!(protocol.tax(|tm| tm.pause_all()), Ok(()));
+    assert!(protocol.tax(|tm| tm.is_paused()));
+    assert!(protocol.staking.borrow().is_paused());
+    assert!(protocol.token.borrow().is_paused());
+}
+
+#[ink::test]
+fn mint_fee_staking_shares_reach_deployed_staking_manager() {
+    let protocol = Protocol::deploy();
+    let accounts = test::default_accounts::<DefaultEnvironment>();
+    let (minter, staker) = (accounts.eve, accounts.frank);
+    protocol.seed_staker(staker, 100 * MIN_STAKE);
+    protocol.staking(|sm| sm.set_authorized_depositor(tax_manager_at())).unwrap();
+    // Routing defaults on once the StakingManager is set
+    assert!(protocol.tax(|tm| tm.is_auto_route_staking_rewards()));
+
+    let amount = 1_000_000_000;
+    mock_psp22::set_balance(lusdt_token_at(), minter, amount);
+    mock_psp22::set_balance(lunes_token_at(), minter, 1_000_000_000_000);
+
+    // LUSDT fee: the staking share is transferred and notified as LUSDT rewards
+    assert_eq!(protocol.staking(|sm| sm.get_pending_rewards(staker)), 0);
+    assert_eq!(
+        protocol.tax(|tm| tm.process_fees_flexible(OperationType::Mint, minter, amount, FeeType::Lusdt)),
+        Ok(())
+    );
+    let lusdt_share = mock_psp22::balance_of(lusdt_token_at(), staking_manager_at());
+    assert!(lusdt_share > 0);
+    let pending = protocol.staking(|sm| sm.get_pending_rewards(staker));
+    assert!(pending > 0 && lusdt_share - pending <= 1);
+
+    // LUNES fee: the staking share is transferred and notified as LUNES rewards
+    assert_eq!(protocol.staking(|sm| sm.get_pending_rewards_lunes(staker)), 0);
+    assert_eq!(
+        protocol.tax(|tm| tm.process_fees_flexible(OperationType::Mint, minter, amount, FeeType::Lunes)),
+        Ok(())
+    );
+    let lunes_share = mock_psp22::balance_of(lunes_token_at(), staking_manager_at());
+    assert!(lunes_share > 0);
+    let pending_lunes = protocol.staking(|sm| sm.get_pending_rewards_lunes(staker));
+    assert!(pending_lunes > 0 && lunes_share - pending_lunes <= 1);
+}
+
+#[ink::test]
+fn burn_fee_reaches_deployed_burn_engine() {
+    let protocol = Protocol::deploy();
+    let accounts = test::default_accounts::<DefaultEnvironment>();
+    let user = accounts.eve;
+    let amount = 500_000_000;
+    let funded = 1_000_000_000_000;
+    mock_psp22::set_balance(lunes_token_at(), user, funded);
+
+    // Reporting off: the LUNES arrive but the BurnEngine doesn't record them
+    assert_eq!(protocol.tax(|tm| tm.process_burn_fee_only(OperationType::Burn, user, amount)), Ok(()));
+    let fee = funded - mock_psp22::balance_of(lunes_token_at(), user);
+    assert!(fee > 0);
+    assert_eq!(mock_psp22::balance_of(lunes_token_at(), burn_engine_at()), fee);
+    assert_eq!(protocol.burn_engine.borrow().get_total_lunes_received(), 0);
+
+    protocol.tax(|tm| tm.set_auto_fund_burn_engine(true)).unwrap();
+    assert_eq!(protocol.tax(|tm| tm.process_burn_fee_only(OperationType::Burn, user, amount)), Ok(()));
+    assert_eq!(mock_psp22::balance_of(lunes_token_at(), burn_engine_at()), 2 * fee);
+    assert_eq!(protocol.burn_engine.borrow().get_total_lunes_received(), fee);
+    assert_eq!(protocol.burn_engine.borrow().get_pending_burn_balance(), fee);
+
+    // Only the Tax Manager (or owner) may report burn fees
+    let reported = at(burn_engine_at(), user, || protocol.burn_engine.borrow_mut().receive_lunes(1));
+    assert!(reported.is_err());
+    assert_eq!(protocol.burn_engine.borrow().get_total_lunes_received(), fee);
+}
+
+#[ink::test]
+fn swept_transfer_fees_become_claimable_staking_rewards() {
+    let protocol = Protocol::deploy();
+    let owner = protocol.owner;
+    let accounts = test::default_accounts::<DefaultEnvironment>();
+    let (minter, holder, staker) = (accounts.eve, accounts.django, accounts.frank);
+    protocol.seed_staker(staker, 100 * MIN_STAKE);
+    protocol.staking(|sm| sm.set_authorized_depositor(lusdt_token_at())).unwrap();
+    protocol.token_as(owner, |t| t.set_staking_manager(Some(staking_manager_at()))).unwrap();
+    protocol.token_as(owner, |t| t.set_flat_transfer_fee(1_000)).unwrap();
+
+    // Flat transfer fees accrue on the token's own balance
+    protocol.token_as(minter, |t| t.mint(holder, 1_000_000)).unwrap();
+    for _ in 0..3 {
+        protocol.token_as(holder, |t| t.transfer(accounts.bob, 10_000)).unwrap();
+    }
+    assert_eq!(protocol.token.borrow().get_accrued_transfer_fees(), 3_000);
+
+    // Anyone may sweep; the StakingManager is credited and notified
+    assert_eq!(protocol.staking(|sm| sm.get_pending_rewards(staker)), 0);
+    assert_eq!(protocol.token_as(holder, |t| t.sweep_transfer_fees_to_staking()), Ok(3_000));
+    assert_eq!(protocol.token_as(holder, |t| t.balance_of(staking_manager_at())), 3_000);
+    assert_eq!(protocol.staking(|sm| sm.get_pending_rewards(staker)), 3_000);
+    assert_eq!(protocol.token_as(holder, |t| t.sweep_transfer_fees_to_staking()), Ok(0));
+}
+
+#[ink::test]
+fn taxed_lusdt_transfers_pay_fees_to_tax_manager() {
+    let protocol = Protocol::deploy();
+    let owner = protocol.owner;
+    let accounts = test::default_accounts::<DefaultEnvironment>();
+    let (minter, holder) = (accounts.eve, accounts.frank);
+    let funded = 1_000_000_000_000;
+    mock_psp22::set_balance(lunes_token_at(), holder, funded);
+    protocol.token_as(minter, |t| t.mint(holder, 1_000_000_000)).unwrap();
+
+    // Tax off: LUSDT moves and TaxManager sees nothing
+    protocol.token_as(holder, |t| t.transfer(accounts.bob, 100_000_000)).unwrap();
+    assert_eq!(protocol.tax(|tm| tm.get_monthly_volume_usd()), 0);
+    assert_eq!(mock_psp22::balance_of(lunes_token_at(), holder), funded);
+
+    // Tax on: every transfer, including each batch leg, pays a LUNES fee to TaxManager
+    protocol.token_as(owner, |t| t.set_transfer_tax_enabled(true)).unwrap();
+    protocol.token_as(holder, |t| t.transfer(accounts.bob, 100_000_000)).unwrap();
+    protocol.token_as(holder, |t| t.batch_transfer(vec![(accounts.bob, 100_000_000)])).unwrap();
+    assert_eq!(protocol.tax(|tm| tm.get_monthly_volume_usd()), 200_000_000);
+
+    let fee = funded - mock_psp22::balance_of(lunes_token_at(), holder);
+    assert!(fee > 0);
+    let distributed: u128 = [accounts.bob, accounts.charlie, staking_manager_at()]
+        .iter()
+        .map(|wallet| mock_psp22::balance_of(lunes_token_at(), *wallet))
+        .sum();
+    assert_eq!(distributed, fee);
+    assert_eq!(protocol.token_as(holder, |t| t.balance_of(holder)), 700_000_000);
+}
+
+/// Deploys `MockLunesToken` at `address` with `supply` minted to `owner`, and routes
+/// the BurnEngine's transfers of it to the deployed contract's `PSP22::transfer`.
+fn deploy_lunes_for_burn_engine(address: AccountId, owner: AccountId, supply: u128) -> Rc<RefCell<MockLunesToken>> {
+    let token = Rc::new(RefCell::new(at(address, owner, || MockLunesToken::new(supply))));
+    let peer = token.clone();
+    burn_peers::register(address, move |call| match call {
+        burn_peers::PeerCall::Transfer(to, amount) => {
+            at(address, burn_engine_at(), || PSP22::transfer(&mut *peer.borrow_mut(), to, amount)).is_ok()
+        }
+    });
+    token
+}
+
+#[ink::test]
+fn burn_engine_burns_and_rescues_through_deployed_psp22() {
+    let accounts = test::default_accounts::<DefaultEnvironment>();
+    let (owner, tax_manager, keeper) = (accounts.alice, accounts.bob, accounts.django);
+    let lunes = deploy_lunes_for_burn_engine(lunes_contract_at(), owner, 10_000);
+    let foreign_at = AccountId::from([0x77; 32]);
+    let foreign = deploy_lunes_for_burn_engine(foreign_at, owner, 500);
+    let balance = |token: &Rc<RefCell<MockLunesToken>>, token_at: AccountId, who: AccountId| {
+        at(token_at, owner, || PSP22::balance_of(&*token.borrow(), who))
+    };
+
+    let mut engine = at(burn_engine_at(), owner, || BurnEngine::new(Some(lunes_contract_at())));
+    at(burn_engine_at(), owner, || engine.set_tax_manager(tax_manager)).unwrap();
+    let burn_address = engine.get_burn_address();
+
+    // Fee LUNES land on the BurnEngine and are reported by the Tax Manager
+    at(lunes_contract_at(), owner, || PSP22::transfer(&mut *lunes.borrow_mut(), burn_engine_at(), 1_000)).unwrap();
+    at(burn_engine_at(), tax_manager, || BurnEngineApi::receive_lunes(&mut engine, 700)).unwrap();
+
+    // Burns move the token's real balances; an over-burn is refused by the token
+    assert_eq!(at(burn_engine_at(), keeper, || engine.burn_lunes(300)), Ok(()));
+    assert_eq!(at(burn_engine_at(), keeper, || engine.burn_lunes(701)), Err(BurnError::BurnTransferFailed));
+    assert_eq!(at(burn_engine_at(), keeper, || engine.burn_pending()), Ok(()));
+    assert_eq!(balance(&lunes, lunes_contract_at(), burn_address), 700);
+    assert_eq!(balance(&lunes, lunes_contract_at(), burn_engine_at()), 300);
+    assert_eq!(engine.get_total_lunes_burned(), 700);
+
+    // A stray token is rescued through the same transfer path
+    at(foreign_at, owner, || PSP22::transfer(&mut *foreign.borrow_mut(), burn_engine_at(), 200)).unwrap();
+    assert_eq!(at(burn_engine_at(), owner, || engine.rescue_foreign_token(foreign_at, keeper, 200)), Ok(()));
+    assert_eq!(balance(&foreign, foreign_at, keeper), 200);
+    assert_eq!(balance(&foreign, foreign_at, burn_engine_at()), 0);
+}
@@ -6,7 +6,9 @@
 //!   Flow 3: Admin panel — pause, fee config, roles, price update
 //! Plus: Burn flow (LUSDT → USDT), approval flow, reentrancy-safe transfers
 
+// The mocks model the full off-chain services, so some of their fields are only set
 #[cfg(test)]
+#[allow(dead_code, clippy::module_inception)]
 mod e2e_tests {
     use ink::primitives::AccountId;
 
@@ -186,6 +188,75 @@ mod e2e_tests {
         }
     }
 
+    // ── Mock Staking Pool (reward-per-token, like StakingManager) ─────
+    pub struct MockStakingPool {
+        pub stakes: Vec<(AccountId, u128)>,
+        pub total_staked: u128,
+        pub reward_per_token: u128, // scaled by 1e12
+        pub paid: Vec<(AccountId, u128)>,
+        pub lusdt_balance: u128,
+    }
+
+    impl MockStakingPool {
+        const PRECISION: u128 = 1_000_000_000_000;
+
+        pub fn new() -> Self {
+            Self { stakes: Vec::new(), total_staked: 0, reward_per_token: 0, paid: Vec::new(), lusdt_balance: 0 }
+        }
+
+        pub fn stake(&mut self, user: AccountId, amount: u128) {
+            self.stakes.push((user, amount));
+            self.paid.push((user, self.reward_per_token));
+            self.total_staked += amount;
+        }
+
+        /// TaxManager transferred `amount` LUSDT and called `notify_reward_amount`
+        pub fn notify_reward_amount(&mut self, amount: u128) {
+            self.lusdt_balance += amount;
+            if let Some(delta) = (amount * Self::PRECISION).checked_div(self.total_staked) {
+                self.reward_per_token += delta;
+            }
+        }
+
+        pub fn claim(&mut self, user: AccountId) -> Result<u128, String> {
+            let amount = self.stakes.iter().find(|(u, _)| *u == user).map(|(_, a)| *a).ok_or("NoActiveStake")?;
+            let paid = self.paid.iter_mut().find(|(u, _)| *u == user).ok_or("NoActiveStake")?;
+            let reward = amount * (self.reward_per_token - paid.1) / Self::PRECISION;
+            if reward == 0 {
+                return Err("NoRewardsToClaim".into());
+            }
+            paid.1 = self.reward_per_token;
+            self.lusdt_balance -= reward;
+            Ok(reward)
+        }
+    }
+
+    // ── Mock Burn Engine (receive_lunes, like BurnEngine) ─────────────
+    pub struct MockBurnEngine {
+        pub tax_manager: AccountId,
+        pub lunes_balance: u128,
+        pub total_lunes_received: u128,
+    }
+
+    impl MockBurnEngine {
+        pub fn new(tax_manager: AccountId) -> Self {
+            Self { tax_manager, lunes_balance: 0, total_lunes_received: 0 }
+        }
+
+        /// TaxManager transferred `amount` LUNES and called `receive_lunes`
+        pub fn receive_lunes(&mut self, caller: AccountId, amount: u128) -> Result<(), String> {
+            if caller != self.tax_manager {
+                return Err("Unauthorized".into());
+            }
+            if amount == 0 {
+                return Err("ZeroAmount".into());
+            }
+            self.lunes_balance += amount;
+            self.total_lunes_received += amount;
+            Ok(())
+        }
+    }
+
     // ── Mock Admin Panel ──────────────────────────────────────────────
     pub struct MockAdminPanel {
         pub owner: AccountId,
@@ -445,7 +516,7 @@ mod e2e_tests {
         let mut bridge = MockBridgeService::new(bridge_acc, 60);
 
         // Multiple swaps generate fees
-        let deposits = vec![
+        let deposits = [
             (user1, 10_000_000_000u128),  // $10K
             (user2, 50_000_000_000u128),  // $50K
             (user1, 100_000_000_000u128), // $100K
@@ -499,7 +570,7 @@ mod e2e_tests {
             bridge.process_mint_request(user, 20_000_000_000, format!("tx_{}", i)).unwrap();
         }
         // 3 burn operations
-        for i in 0..3 {
+        for _ in 0..3 {
             bridge.process_burn_request(user, 10_000_000_000, "7xKXtg2CW87d97TXJSDpbD5jBkheTqA83TZRuJosgAsU".into()).unwrap();
         }
 
@@ -686,7 +757,7 @@ mod e2e_tests {
 
         let user = AccountId::from([1u8; 32]);
         let tax_manager = AccountId::from([2u8; 32]);
-        let dev_wallet = AccountId::from([3u8; 32]);
+        let _dev_wallet = AccountId::from([3u8; 32]);
 
         let mut approvals = MockApprovalManager::new();
 
@@ -786,7 +857,7 @@ mod e2e_tests {
         approval_flow_lusdt_and_lunes();
         approval_transfer_from_reentrancy_safe();
         integration_full_user_journey();
-        println!("\n🎉 All 18 E2E tests passed!");
+        println!("\n🎉 All 20 E2E tests passed!");
     }
 }
 
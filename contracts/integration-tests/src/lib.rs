@@ -1,13 +1,18 @@
 //! Integration Tests for LUSDT Contracts — v3 Dual-Fee Model
 //!
 //! This crate contains comprehensive E2E integration tests that verify
-//! all user flows: swap, staking, admin, burn, and approval.
+//! all user flows: swap, staking, admin, burn, and approval. `deployed_tests`
+//! runs the real contracts side by side, with TaxManager's peer calls delivered
+//! to them through its `off-chain-peers` mocks.
 
 pub mod e2e_tests;
+#[cfg(test)]
+mod deployed_tests;
 
 #[cfg(test)]
 mod tests {
     #[test]
+    #[allow(clippy::assertions_on_constants)]
     fn integration_tests_crate_loads() {
         assert!(true, "Integration test crate loaded successfully");
     }
@@ -21,12 +21,14 @@
 #![allow(clippy::cast_possible_truncation)]
 
 #[ink::contract]
-mod lusdt_token {
+pub mod lusdt_token {
     use ink::prelude::string::String;
+    use ink::prelude::vec::Vec;
     use ink::storage::Mapping;
 
-    #[cfg(not(test))]
-    use common::{common_types::{FeeType, OperationType}, traits::TaxManager};
+    use common::{common_types::OperationType, errors::ProtocolError, traits::PSP22};
+    #[cfg(not(any(test, feature = "off-chain-peers")))]
+    use common::{common_types::FeeType, traits::{StakingManager, TaxManager}};
 
     // Role Constants
     pub type Role = u32;
@@ -35,6 +37,37 @@ mod lusdt_token {
     pub const MINTER_ROLE: Role = 2; // Substitutes BRIDGE_ROLE
     pub const TAX_MANAGER_ROLE: Role = 3;
 
+    /// Maximum recipients in a single `batch_transfer`
+    pub const MAX_BATCH_TRANSFER: usize = 100;
+
+    /// Current `RedemptionRequested` payload format; bump when the bridge message changes
+    pub const BRIDGE_MESSAGE_VERSION: u16 = 1;
+
+    /// Subsystems that can be paused independently of the master switch
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub enum Subsystem {
+        Mint,
+        Burn,
+        Transfer,
+    }
+
+    /// @notice Structured result of `mint_with_receipt`
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct OperationReceipt {
+        /// Amount requested
+        pub gross: Balance,
+        /// LUSDT withheld from `gross` (the mint fee is paid outside the minted amount, so 0)
+        pub fee: Balance,
+        /// Amount credited to the recipient
+        pub net: Balance,
+        /// Recipient balance after the operation
+        pub new_balance: Balance,
+        /// Mint counter after the operation
+        pub nonce: u64,
+    }
+
     /// @title LUSDT Token Storage
     #[ink(storage)]
     pub struct LusdtToken {
@@ -42,6 +75,14 @@ mod lusdt_token {
         version: u16,
         /// Total supply
         total_supply: Balance,
+        /// Optional hard cap on total supply
+        max_supply: Option<Balance>,
+        /// PSP22Metadata: token name
+        token_name: Option<String>,
+        /// PSP22Metadata: token symbol
+        token_symbol: Option<String>,
+        /// PSP22Metadata: token decimals
+        token_decimals: u8,
         /// Balances
         balances: Mapping<AccountId, Balance>,
         /// Allowances
@@ -55,10 +96,17 @@ mod lusdt_token {
         tax_manager_contract: AccountId,
 
         // === SECURITY: Circuit Breaker ===
+        /// Master switch: blocks every subsystem
         paused: bool,
+        mint_paused: bool,
+        burn_paused: bool,
+        transfer_paused: bool,
         pause_reason: Option<String>,
         paused_at: Option<u64>,
 
+        // === SECURITY: Compliance Freeze ===
+        frozen: Mapping<AccountId, bool>,
+
         // === SECURITY: Reentrancy Protection ===
         locked: bool,
 
@@ -66,6 +114,49 @@ mod lusdt_token {
         last_mint_time: u64,
         mint_window_amount: Balance,
         mint_window_start: u64,
+        /// Rate limit window length in ms
+        mint_rate_window_ms: u64,
+        /// Maximum amount mintable per window
+        max_mint_per_window: Balance,
+        /// Minimum time between mints to the same recipient (0 = disabled)
+        mint_cooldown_ms: u64,
+        /// Last mint timestamp per recipient
+        last_mint_to: Mapping<AccountId, u64>,
+
+        // === Transfer fee sweeping ===
+        /// StakingManager receiving swept transfer fees
+        staking_manager: Option<AccountId>,
+        /// Transfer-fee LUSDT held by this contract, awaiting a sweep
+        accrued_transfer_fees: Balance,
+        /// Flat LUSDT fee charged to the sender on top of each transfer (0 = disabled)
+        flat_transfer_fee: Balance,
+        /// Receives flat transfer fees; defaults to this contract, where they accrue for the sweep
+        fee_collector: AccountId,
+
+        /// Monotonic counter used as the redemption `request_id`
+        redemption_nonce: u64,
+        /// Monotonic counter of non-zero mints (receipt `nonce`)
+        mint_nonce: u64,
+        /// Lifetime gross LUSDT minted (never decreases)
+        cumulative_minted: Balance,
+        /// Lifetime gross LUSDT burned (never decreases)
+        cumulative_burned: Balance,
+        /// `format_version` stamped on `RedemptionRequested`
+        bridge_message_version: u16,
+        /// Solana deposit tx hashes already minted through `mint_for_deposit`
+        processed_deposits: Mapping<Hash, bool>,
+
+        // === Transfer tax ===
+        /// When true, `transfer`/`transfer_from` report to the Tax Manager as `OperationType::Transfer`
+        transfer_tax_enabled: bool,
+
+        // === Supply audit ===
+        /// Every account that has ever held a balance, by insertion order
+        holders: Mapping<u32, AccountId>,
+        /// Whether an account is already in `holders`
+        holder_indexed: Mapping<AccountId, bool>,
+        /// Number of entries in `holders`
+        holder_count: u32,
     }
 
     /// @title LUSDT Events
@@ -96,6 +187,8 @@ mod lusdt_token {
         solana_recipient_address: String,
         request_id: u64,
         block_timestamp: u64,
+        /// Bridge message format, so parsers can dispatch on it
+        format_version: u16,
     }
 
     // === SECURITY EVENTS ===
@@ -108,6 +201,15 @@ mod lusdt_token {
         timestamp: u64,
     }
 
+    #[ink(event)]
+    pub struct PauseStateChanged {
+        #[ink(topic)]
+        admin: AccountId,
+        subsystem: Subsystem,
+        paused: bool,
+        timestamp: u64,
+    }
+
     #[ink(event)]
     pub struct EmergencyUnpause {
         #[ink(topic)]
@@ -115,6 +217,22 @@ mod lusdt_token {
         timestamp: u64,
     }
 
+    #[ink(event)]
+    pub struct AccountFrozen {
+        #[ink(topic)]
+        account: AccountId,
+        #[ink(topic)]
+        admin: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct AccountUnfrozen {
+        #[ink(topic)]
+        account: AccountId,
+        #[ink(topic)]
+        admin: AccountId,
+    }
+
     #[ink(event)]
     pub struct RoleGranted {
         #[ink(topic)]
@@ -135,6 +253,26 @@ mod lusdt_token {
         admin: AccountId,
     }
 
+    #[ink(event)]
+    pub struct MintRateLimitUpdated {
+        window_ms: u64,
+        max_per_window: Balance,
+    }
+
+    #[ink(event)]
+    pub struct TransferFeesSwept {
+        #[ink(topic)]
+        staking_manager: AccountId,
+        amount: Balance,
+    }
+
+    #[ink(event)]
+    pub struct MetadataUpdated {
+        name: Option<String>,
+        symbol: Option<String>,
+        decimals: u8,
+    }
+
     #[ink(event)]
     pub struct SecurityAlert {
         operation: String,
@@ -160,10 +298,51 @@ mod lusdt_token {
         EmergencyActive,
         InvalidTimestamp,
         SetCodeHashFailed,
+        MintCooldownActive,
+        StakingManagerNotSet,
+        RewardNotifyFailed,
+        InvalidRateLimit,
+        AccountFrozen,
+        MaxSupplyExceeded,
+        BatchTooLarge,
+        DuplicateDeposit,
     }
 
     pub type Result<T> = core::result::Result<T, Error>;
 
+    /// @notice Checks that `address` is base58 and decodes to exactly 32 bytes (an ed25519 public key)
+    /// @dev Inline big-number decoder over a fixed buffer — no heap allocation
+    fn validate_solana_address(address: &str) -> Result<()> {
+        const ALPHABET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+        if address.len() < 32 || address.len() > 44 {
+            return Err(Error::InvalidSolanaAddress);
+        }
+
+        // Big-endian value; anything needing more than 32 bytes is rejected on overflow
+        let mut value = [0u8; 32];
+        for c in address.bytes() {
+            let digit = ALPHABET.iter().position(|&a| a == c).ok_or(Error::InvalidSolanaAddress)?;
+            let mut carry = digit as u32;
+            for byte in value.iter_mut().rev() {
+                carry += (*byte as u32) * 58;
+                *byte = carry as u8;
+                carry >>= 8;
+            }
+            if carry != 0 {
+                return Err(Error::InvalidSolanaAddress);
+            }
+        }
+
+        // Each leading '1' encodes a leading zero byte
+        let leading_zeros = address.bytes().take_while(|&c| c == b'1').count();
+        let value_len = value.len() - value.iter().take_while(|&&b| b == 0).count();
+        if leading_zeros + value_len != 32 {
+            return Err(Error::InvalidSolanaAddress);
+        }
+        Ok(())
+    }
+
     impl LusdtToken {
         /// @notice Initializes the LUSDT token contract with RBAC
         #[ink(constructor)]
@@ -177,17 +356,43 @@ mod lusdt_token {
             let mut instance = Self {
                 version: 2, // RBAC Version
                 total_supply: 0,
+                max_supply: None,
+                token_name: Some(String::from("Lunes USD")),
+                token_symbol: Some(String::from("LUSDT")),
+                token_decimals: 12,
                 balances: Mapping::new(),
                 allowances: Mapping::new(),
                 roles: Mapping::new(),
                 tax_manager_contract,
                 paused: false,
+                mint_paused: false,
+                burn_paused: false,
+                transfer_paused: false,
                 pause_reason: None,
                 paused_at: None,
+                frozen: Mapping::new(),
                 locked: false,
                 mint_window_amount: 0,
                 mint_window_start: Self::env().block_timestamp(),
                 last_mint_time: Self::env().block_timestamp(),
+                mint_rate_window_ms: 3_600_000, // 1 hour
+                max_mint_per_window: 1_000_000_000_000, // 1M LUSDT
+                mint_cooldown_ms: 0,
+                last_mint_to: Mapping::new(),
+                staking_manager: None,
+                accrued_transfer_fees: 0,
+                flat_transfer_fee: 0,
+                fee_collector: Self::env().account_id(),
+                redemption_nonce: 0,
+                mint_nonce: 0,
+                cumulative_minted: 0,
+                cumulative_burned: 0,
+                bridge_message_version: BRIDGE_MESSAGE_VERSION,
+                processed_deposits: Mapping::default(),
+                transfer_tax_enabled: false,
+                holders: Mapping::new(),
+                holder_indexed: Mapping::new(),
+                holder_count: 0,
             };
 
             // Setup Default Roles
@@ -312,21 +517,89 @@ mod lusdt_token {
             (self.paused, self.pause_reason.clone(), self.paused_at)
         }
 
+        /// @notice Pauses or resumes a single subsystem (mint, burn or transfer).
+        /// The master `paused` switch still overrides every subsystem. PAUSER or ADMIN.
+        #[ink(message)]
+        pub fn set_subsystem_paused(&mut self, subsystem: Subsystem, paused: bool) -> Result<()> {
+            if !self.has_role(PAUSER_ROLE, self.env().caller()) && !self.has_role(DEFAULT_ADMIN_ROLE, self.env().caller()) {
+                return Err(Error::MissingRole);
+            }
+
+            match subsystem {
+                Subsystem::Mint => self.mint_paused = paused,
+                Subsystem::Burn => self.burn_paused = paused,
+                Subsystem::Transfer => self.transfer_paused = paused,
+            }
+
+            self.env().emit_event(PauseStateChanged {
+                admin: self.env().caller(),
+                subsystem,
+                paused,
+                timestamp: self.env().block_timestamp(),
+            });
+            Ok(())
+        }
+
+        /// @notice Returns whether a subsystem is currently blocked (its own flag or the master switch)
+        #[ink(message)]
+        pub fn is_subsystem_paused(&self, subsystem: Subsystem) -> bool {
+            self.paused
+                || match subsystem {
+                    Subsystem::Mint => self.mint_paused,
+                    Subsystem::Burn => self.burn_paused,
+                    Subsystem::Transfer => self.transfer_paused,
+                }
+        }
+
+        // === COMPLIANCE FREEZE ===
+
+        /// @notice Freezes an account: it can no longer send, receive, mint or burn. Only ADMIN can call.
+        #[ink(message)]
+        pub fn freeze_account(&mut self, account: AccountId) -> Result<()> {
+            self.ensure_role(DEFAULT_ADMIN_ROLE)?;
+            self.frozen.insert(account, &true);
+            self.env().emit_event(AccountFrozen {
+                account,
+                admin: self.env().caller(),
+            });
+            Ok(())
+        }
+
+        /// @notice Lifts a freeze. Only ADMIN can call.
+        #[ink(message)]
+        pub fn unfreeze_account(&mut self, account: AccountId) -> Result<()> {
+            self.ensure_role(DEFAULT_ADMIN_ROLE)?;
+            self.frozen.remove(account);
+            self.env().emit_event(AccountUnfrozen {
+                account,
+                admin: self.env().caller(),
+            });
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn is_frozen(&self, account: AccountId) -> bool {
+            self.frozen.get(account).unwrap_or(false)
+        }
+
+        fn ensure_not_frozen(&self, account: AccountId) -> Result<()> {
+            if self.is_frozen(account) { return Err(Error::AccountFrozen); }
+            Ok(())
+        }
+
         // === RATE LIMITING ===
         
         fn check_mint_rate_limit(&mut self, amount: Balance) -> Result<()> {
             let current_time = self.env().block_timestamp();
-            const RATE_LIMIT_WINDOW: u64 = 3600000; // 1 hour
-            const MAX_MINT_PER_HOUR: Balance = 1_000_000_000_000; // 1M LUSDT
 
-            if current_time.saturating_sub(self.mint_window_start) >= RATE_LIMIT_WINDOW {
+            if current_time.saturating_sub(self.mint_window_start) >= self.mint_rate_window_ms {
                 self.mint_window_start = current_time;
                 self.mint_window_amount = 0;
             }
 
             let new_amount = self.mint_window_amount.checked_add(amount).ok_or(Error::MathOverflow)?;
 
-            if new_amount > MAX_MINT_PER_HOUR {
+            if new_amount > self.max_mint_per_window {
                 return Err(Error::RateLimitExceeded);
             }
 
@@ -334,8 +607,113 @@ mod lusdt_token {
             Ok(())
         }
 
+        /// @notice Updates the mint rate limit. Only ADMIN can call.
+        /// @dev A zero window would never reset, so it is rejected.
+        #[ink(message)]
+        pub fn set_mint_rate_limit(&mut self, window_ms: u64, max_per_window: Balance) -> Result<()> {
+            self.ensure_role(DEFAULT_ADMIN_ROLE)?;
+            if window_ms == 0 {
+                return Err(Error::InvalidRateLimit);
+            }
+            self.mint_rate_window_ms = window_ms;
+            self.max_mint_per_window = max_per_window;
+            self.env().emit_event(MintRateLimitUpdated { window_ms, max_per_window });
+            Ok(())
+        }
+
+        /// @notice Returns (window_ms, max_per_window)
+        #[ink(message)]
+        pub fn get_mint_rate_limit(&self) -> (u64, Balance) {
+            (self.mint_rate_window_ms, self.max_mint_per_window)
+        }
+
+        /// @notice Rejects a mint to `to` if it was minted to within `mint_cooldown_ms`
+        fn check_mint_cooldown(&self, to: AccountId) -> Result<()> {
+            if self.mint_cooldown_ms == 0 {
+                return Ok(());
+            }
+            if let Some(last) = self.last_mint_to.get(to) {
+                if self.env().block_timestamp().saturating_sub(last) < self.mint_cooldown_ms {
+                    return Err(Error::MintCooldownActive);
+                }
+            }
+            Ok(())
+        }
+
+        /// @notice Sets the per-recipient mint cooldown (0 disables). Only ADMIN can call.
+        #[ink(message)]
+        pub fn set_mint_cooldown(&mut self, cooldown_ms: u64) -> Result<()> {
+            self.ensure_role(DEFAULT_ADMIN_ROLE)?;
+            self.mint_cooldown_ms = cooldown_ms;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn get_mint_cooldown(&self) -> u64 {
+            self.mint_cooldown_ms
+        }
+
+        /// @notice Sets the `format_version` stamped on redemption events. Only ADMIN can call.
+        /// @dev Bump together with any change to the `RedemptionRequested` payload.
+        #[ink(message)]
+        pub fn set_bridge_message_version(&mut self, version: u16) -> Result<()> {
+            self.ensure_role(DEFAULT_ADMIN_ROLE)?;
+            self.bridge_message_version = version;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn get_bridge_message_version(&self) -> u16 {
+            self.bridge_message_version
+        }
+
+        /// @notice Enables/disables Tax Manager fees on peer-to-peer transfers. Only ADMIN can call.
+        /// @dev Off by default; fee movements to or from the Tax Manager itself are never taxed.
+        #[ink(message)]
+        pub fn set_transfer_tax_enabled(&mut self, enabled: bool) -> Result<()> {
+            self.ensure_role(DEFAULT_ADMIN_ROLE)?;
+            self.transfer_tax_enabled = enabled;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn is_transfer_tax_enabled(&self) -> bool {
+            self.transfer_tax_enabled
+        }
+
+        /// Charges the optional transfer tax on `value` moved from `from` to `to`, paid by
+        /// `from`. Soft-fails like the mint fee.
+        fn charge_transfer_tax(&mut self, from: AccountId, to: AccountId, value: Balance) {
+            if !self.is_taxed_transfer(from, to) {
+                return;
+            }
+            if self.tax_process_fees(OperationType::Transfer, from, value).is_err() {
+                self.env().emit_event(SecurityAlert {
+                    operation: "TransferTaxProcessing".into(),
+                    message: "Failed to process fees for transfer.".into(),
+                    timestamp: self.env().block_timestamp(),
+                });
+            }
+        }
+
+        /// Whether a transfer is taxed. Anything the Tax Manager moves itself is exempt,
+        /// otherwise distributing one fee would charge another.
+        fn is_taxed_transfer(&self, from: AccountId, to: AccountId) -> bool {
+            let tax_manager = self.tax_manager_contract;
+            self.transfer_tax_enabled
+                && self.env().caller() != tax_manager
+                && from != tax_manager
+                && to != tax_manager
+        }
+
         // === CORE TOKEN FUNCTIONS ===
 
+        /// @notice Last issued redemption `request_id`
+        #[ink(message)]
+        pub fn get_redemption_nonce(&self) -> u64 {
+            self.redemption_nonce
+        }
+
         #[ink(message)]
         pub fn get_version(&self) -> u16 {
             self.version
@@ -346,9 +724,67 @@ mod lusdt_token {
             self.paused
         }
 
+        /// @notice Mints without deposit deduplication. Kept for backward compatibility;
+        /// the bridge should prefer `mint_for_deposit`.
         #[ink(message)]
         pub fn mint(&mut self, to: AccountId, amount: Balance) -> Result<()> {
-            self.ensure_not_paused()?;
+            self.mint_inner(to, amount).map(|_| ())
+        }
+
+        /// @notice Preferred bridge entrypoint: mints for a Solana deposit at most once.
+        /// @dev A retried call carrying an already-processed `solana_tx_hash` fails with
+        /// `DuplicateDeposit` before anything is minted.
+        #[ink(message)]
+        pub fn mint_for_deposit(&mut self, to: AccountId, amount: Balance, solana_tx_hash: Hash) -> Result<()> {
+            if self.processed_deposits.contains(solana_tx_hash) {
+                return Err(Error::DuplicateDeposit);
+            }
+            self.mint_inner(to, amount)?;
+            self.processed_deposits.insert(solana_tx_hash, &true);
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn is_deposit_processed(&self, solana_tx_hash: Hash) -> bool {
+            self.processed_deposits.contains(solana_tx_hash)
+        }
+
+        /// @notice Same as `mint`, returning a structured receipt instead of requiring event parsing.
+        /// The USDT fee is deducted by the bridge before minting and the LUNES burn fee is
+        /// billed to `to` by the Tax Manager, so the full `gross` is credited.
+        #[ink(message)]
+        pub fn mint_with_receipt(&mut self, to: AccountId, amount: Balance) -> Result<OperationReceipt> {
+            let new_balance = self.mint_inner(to, amount)?;
+            Ok(OperationReceipt {
+                gross: amount,
+                fee: 0,
+                net: amount,
+                new_balance,
+                nonce: self.mint_nonce,
+            })
+        }
+
+        /// @notice Lifetime gross LUSDT minted, unaffected by burns
+        #[ink(message)]
+        pub fn get_cumulative_minted(&self) -> Balance {
+            self.cumulative_minted
+        }
+
+        /// @notice Lifetime gross LUSDT burned, unaffected by mints
+        #[ink(message)]
+        pub fn get_cumulative_burned(&self) -> Balance {
+            self.cumulative_burned
+        }
+
+        /// @notice Last issued mint receipt `nonce`
+        #[ink(message)]
+        pub fn get_mint_nonce(&self) -> u64 {
+            self.mint_nonce
+        }
+
+        /// Shared mint body; returns the recipient's new balance.
+        fn mint_inner(&mut self, to: AccountId, amount: Balance) -> Result<Balance> {
+            self.ensure_not_paused(Subsystem::Mint)?;
             self.ensure_not_locked()?;
 
             // Only MINTER or ADMIN can mint
@@ -357,16 +793,24 @@ mod lusdt_token {
             }
 
             let result = (|| {
+                self.ensure_not_frozen(to)?;
+                self.check_mint_cooldown(to)?;
                 self.check_mint_rate_limit(amount)?;
 
-                if amount == 0 { return Ok(()); }
+                if amount == 0 { return Ok(self.balances.get(to).unwrap_or(0)); }
 
                 let new_total_supply = self.total_supply.checked_add(amount).ok_or(Error::MathOverflow)?;
+                if self.max_supply.is_some_and(|cap| new_total_supply > cap) {
+                    return Err(Error::MaxSupplyExceeded);
+                }
                 self.total_supply = new_total_supply;
+                self.cumulative_minted = self.cumulative_minted.checked_add(amount).ok_or(Error::MathOverflow)?;
 
                 let current_balance = self.balances.get(to).unwrap_or(0);
                 let new_balance = current_balance.checked_add(amount).ok_or(Error::MathOverflow)?;
-                self.balances.insert(to, &new_balance);
+                self._set_balance(to, new_balance);
+                self.last_mint_to.insert(to, &self.env().block_timestamp());
+                self.mint_nonce = self.mint_nonce.checked_add(1).ok_or(Error::MathOverflow)?;
 
                 self.env().emit_event(Transfer {
                     from: None,
@@ -380,7 +824,7 @@ mod lusdt_token {
                 // On-chain we only charge the LUNES deflationary burn fee (0.10%).
                 // Fee payer is `to` (the user), not caller (bridge). Soft-fail if user
                 // hasn't approved Tax Manager for LUNES or doesn't hold LUNES.
-                #[cfg(not(test))]
+                #[cfg(not(any(test, feature = "off-chain-peers")))]
                 {
                     let mut tax_manager: ink::contract_ref!(TaxManager) = self.tax_manager_contract.into();
                     if tax_manager.process_burn_fee_only(OperationType::Mint, to, amount).is_err() {
@@ -391,7 +835,7 @@ mod lusdt_token {
                         });
                     }
                 }
-                Ok(())
+                Ok(new_balance)
             })();
 
             self.unlock();
@@ -400,63 +844,92 @@ mod lusdt_token {
 
         #[ink(message)]
         pub fn burn(&mut self, amount: Balance, solana_recipient_address: String) -> Result<()> {
-            self.ensure_not_paused()?;
+            self.ensure_not_paused(Subsystem::Burn)?;
+            self.ensure_not_locked()?;
+
+            let caller = self.env().caller();
+            let result = self.burn_inner(caller, amount, solana_recipient_address);
+
+            self.unlock();
+            result
+        }
+
+        /// @notice Burns `amount` from `from` for a bridge-initiated redemption. Only MINTER (the bridge)
+        /// can call, spending its allowance from `from`; `from` is reported as the originator.
+        #[ink(message)]
+        pub fn burn_from(&mut self, from: AccountId, amount: Balance, solana_recipient_address: String) -> Result<()> {
+            self.ensure_not_paused(Subsystem::Burn)?;
+            self.ensure_role(MINTER_ROLE)?;
             self.ensure_not_locked()?;
 
             let result = (|| {
                 let caller = self.env().caller();
-
-                if solana_recipient_address.len() < 32 || solana_recipient_address.len() > 44 {
-                    return Err(Error::InvalidSolanaAddress);
+                let current_allowance = self.allowances.get((from, caller)).unwrap_or(0);
+                if current_allowance < amount {
+                    return Err(Error::InsufficientAllowance);
                 }
+                self.allowances.insert((from, caller), &(current_allowance - amount));
+                self.burn_inner(from, amount, solana_recipient_address)
+            })();
 
-                if amount == 0 { return Ok(()); }
+            self.unlock();
+            result
+        }
 
-                let current_balance = self.balances.get(caller).unwrap_or(0);
-                if current_balance < amount {
-                    return Err(Error::InsufficientBalance);
-                }
+        /// Shared burn body: debits `from`, emits the redemption request and charges the burn fees to `from`.
+        fn burn_inner(&mut self, from: AccountId, amount: Balance, solana_recipient_address: String) -> Result<()> {
+            self.ensure_not_frozen(from)?;
 
-                let new_balance = current_balance.checked_sub(amount).ok_or(Error::MathUnderflow)?;
-                self.balances.insert(caller, &new_balance);
+            validate_solana_address(&solana_recipient_address)?;
 
-                let new_total_supply = self.total_supply.checked_sub(amount).ok_or(Error::MathUnderflow)?;
-                self.total_supply = new_total_supply;
+            if amount == 0 { return Ok(()); }
 
-                let request_id = self.env().block_timestamp();
+            let current_balance = self.balances.get(from).unwrap_or(0);
+            if current_balance < amount {
+                return Err(Error::InsufficientBalance);
+            }
 
-                self.env().emit_event(Transfer {
-                    from: Some(caller),
-                    to: None,
-                    value: amount,
-                    block_timestamp: request_id,
-                });
+            let new_balance = current_balance.checked_sub(amount).ok_or(Error::MathUnderflow)?;
+            self._set_balance(from, new_balance);
 
-                self.env().emit_event(RedemptionRequested {
-                    from: caller,
-                    amount,
-                    solana_recipient_address,
-                    request_id,
-                    block_timestamp: request_id,
-                });
+            let new_total_supply = self.total_supply.checked_sub(amount).ok_or(Error::MathUnderflow)?;
+            self.total_supply = new_total_supply;
+            self.cumulative_burned = self.cumulative_burned.checked_add(amount).ok_or(Error::MathOverflow)?;
 
-                // Interactions with Tax Manager (v3: dual-fee — LUSDT revenue + LUNES burn)
-                #[cfg(not(test))]
-                {
-                    let mut tax_manager: ink::contract_ref!(TaxManager) = self.tax_manager_contract.into();
-                    if tax_manager.process_dual_fee(OperationType::Burn, caller, amount, FeeType::Lusdt).is_err() {
-                        self.env().emit_event(SecurityAlert {
-                            operation: "BurnTaxProcessing".into(),
-                            message: "Failed to process dual fees for burn operation.".into(),
-                            timestamp: self.env().block_timestamp(),
-                        });
-                    }
-                }
-                Ok(())
-            })();
+            // Unique per redemption (block timestamps collide within a block)
+            self.redemption_nonce = self.redemption_nonce.checked_add(1).ok_or(Error::MathOverflow)?;
+            let request_id = self.redemption_nonce;
+            let block_timestamp = self.env().block_timestamp();
 
-            self.unlock();
-            result
+            self.env().emit_event(Transfer {
+                from: Some(from),
+                to: None,
+                value: amount,
+                block_timestamp,
+            });
+
+            self.env().emit_event(RedemptionRequested {
+                from,
+                amount,
+                solana_recipient_address,
+                request_id,
+                block_timestamp,
+                format_version: self.bridge_message_version,
+            });
+
+            // Interactions with Tax Manager (v3: dual-fee — LUSDT revenue + LUNES burn)
+            #[cfg(not(any(test, feature = "off-chain-peers")))]
+            {
+                let mut tax_manager: ink::contract_ref!(TaxManager) = self.tax_manager_contract.into();
+                if tax_manager.process_dual_fee(OperationType::Burn, from, amount, FeeType::Lusdt).is_err() {
+                    self.env().emit_event(SecurityAlert {
+                        operation: "BurnTaxProcessing".into(),
+                        message: "Failed to process dual fees for burn operation.".into(),
+                        timestamp: self.env().block_timestamp(),
+                    });
+                }
+            }
+            Ok(())
         }
 
         #[ink(message)]
@@ -464,20 +937,23 @@ mod lusdt_token {
         /// the caller's own balance and doesn't change total supply. Safe for cross-contract
         /// callbacks (e.g., Tax Manager distributing LUSDT fees during burn).
         pub fn transfer(&mut self, to: AccountId, value: Balance) -> Result<()> {
-            self.ensure_not_paused()?;
+            self.ensure_not_paused(Subsystem::Transfer)?;
 
             let from = self.env().caller();
+            self.ensure_not_frozen(from)?;
+            self.ensure_not_frozen(to)?;
+            let fee = self.flat_transfer_fee_for(from, to);
             let from_balance = self.balances.get(from).unwrap_or(0);
-            if from_balance < value {
+            if from_balance < value.checked_add(fee).ok_or(Error::MathOverflow)? {
                 return Err(Error::InsufficientBalance);
             }
 
             let new_from_balance = from_balance.checked_sub(value).ok_or(Error::MathUnderflow)?;
-            self.balances.insert(from, &new_from_balance);
+            self._set_balance(from, new_from_balance);
 
             let to_balance = self.balances.get(to).unwrap_or(0);
             let new_to_balance = to_balance.checked_add(value).ok_or(Error::MathOverflow)?;
-            self.balances.insert(to, &new_to_balance);
+            self._set_balance(to, new_to_balance);
 
             self.env().emit_event(Transfer {
                 from: Some(from),
@@ -485,12 +961,72 @@ mod lusdt_token {
                 value,
                 block_timestamp: self.env().block_timestamp(),
             });
+            self.collect_flat_transfer_fee(from, fee)?;
+
+            // Optional transfer tax, paid by the sender
+            self.charge_transfer_tax(from, to, value);
             Ok(())
         }
 
+        /// @notice Transfers to many recipients in one call (airdrops, payroll).
+        /// The whole batch is validated against the caller's balance before any state changes.
         #[ink(message)]
-        pub fn approve(&mut self, spender: AccountId, amount: Balance) -> Result<()> {
+        pub fn batch_transfer(&mut self, recipients: Vec<(AccountId, Balance)>) -> Result<()> {
+            self.ensure_not_paused(Subsystem::Transfer)?;
+            if recipients.len() > MAX_BATCH_TRANSFER {
+                return Err(Error::BatchTooLarge);
+            }
             self.ensure_not_locked()?;
+
+            let result = (|| {
+                let from = self.env().caller();
+                self.ensure_not_frozen(from)?;
+
+                let mut total: Balance = 0;
+                let mut fees: Balance = 0;
+                for (to, value) in recipients.iter() {
+                    self.ensure_not_frozen(*to)?;
+                    total = total.checked_add(*value).ok_or(Error::MathOverflow)?;
+                    fees = fees.checked_add(self.flat_transfer_fee_for(from, *to)).ok_or(Error::MathOverflow)?;
+                }
+
+                let from_balance = self.balances.get(from).unwrap_or(0);
+                if from_balance < total.checked_add(fees).ok_or(Error::MathOverflow)? {
+                    return Err(Error::InsufficientBalance);
+                }
+                self._set_balance(from, from_balance.checked_sub(total).ok_or(Error::MathUnderflow)?);
+
+                let block_timestamp = self.env().block_timestamp();
+                for (to, value) in recipients.iter().copied() {
+                    let to_balance = self.balances.get(to).unwrap_or(0);
+                    let new_to_balance = to_balance.checked_add(value).ok_or(Error::MathOverflow)?;
+                    self._set_balance(to, new_to_balance);
+
+                    self.env().emit_event(Transfer {
+                        from: Some(from),
+                        to: Some(to),
+                        value,
+                        block_timestamp,
+                    });
+                }
+                self.collect_flat_transfer_fee(from, fees)
+            })();
+
+            self.unlock();
+            // Each leg is taxed like a single transfer, once the batch has settled
+            if result.is_ok() {
+                let from = self.env().caller();
+                for (to, value) in recipients {
+                    self.charge_transfer_tax(from, to, value);
+                }
+            }
+            result
+        }
+
+        /// @notice Sets the caller's allowance for `spender`. No reentrancy lock: it only writes
+        /// an allowance, so it stays usable when called back during a guarded operation.
+        #[ink(message)]
+        pub fn approve(&mut self, spender: AccountId, amount: Balance) -> Result<()> {
             let owner = self.env().caller();
             self.allowances.insert((owner, spender), &amount);
             self.env().emit_event(Approval {
@@ -498,7 +1034,37 @@ mod lusdt_token {
                 spender,
                 value: amount,
             });
-            self.unlock();
+            Ok(())
+        }
+
+        /// @notice Increases the caller's allowance for `spender` by `delta`.
+        /// Avoids the approve-overwrite front-running race.
+        #[ink(message)]
+        pub fn increase_allowance(&mut self, spender: AccountId, delta: Balance) -> Result<()> {
+            let owner = self.env().caller();
+            let current = self.allowances.get((owner, spender)).unwrap_or(0);
+            let new_allowance = current.checked_add(delta).ok_or(Error::MathOverflow)?;
+            self.allowances.insert((owner, spender), &new_allowance);
+            self.env().emit_event(Approval {
+                owner,
+                spender,
+                value: new_allowance,
+            });
+            Ok(())
+        }
+
+        /// @notice Decreases the caller's allowance for `spender` by `delta`, saturating at zero.
+        #[ink(message)]
+        pub fn decrease_allowance(&mut self, spender: AccountId, delta: Balance) -> Result<()> {
+            let owner = self.env().caller();
+            let current = self.allowances.get((owner, spender)).unwrap_or(0);
+            let new_allowance = current.saturating_sub(delta);
+            self.allowances.insert((owner, spender), &new_allowance);
+            self.env().emit_event(Approval {
+                owner,
+                spender,
+                value: new_allowance,
+            });
             Ok(())
         }
 
@@ -508,28 +1074,34 @@ mod lusdt_token {
         /// change total supply. Safe for cross-contract callbacks (e.g., Tax Manager pulling
         /// LUSDT fees during burn via transfer_from).
         pub fn transfer_from(&mut self, from: AccountId, to: AccountId, amount: Balance) -> Result<()> {
-            self.ensure_not_paused()?;
+            self.ensure_not_paused(Subsystem::Transfer)?;
 
+            self.ensure_not_frozen(from)?;
+            self.ensure_not_frozen(to)?;
+
+            // The flat fee is spent from the allowance too, so a spender can't cost `from` more than approved
             let caller = self.env().caller();
+            let fee = self.flat_transfer_fee_for(from, to);
+            let total = amount.checked_add(fee).ok_or(Error::MathOverflow)?;
             let current_allowance = self.allowances.get((from, caller)).unwrap_or(0);
-            if current_allowance < amount {
+            if current_allowance < total {
                 return Err(Error::InsufficientAllowance);
             }
 
-            let new_allowance = current_allowance.checked_sub(amount).ok_or(Error::MathUnderflow)?;
+            let new_allowance = current_allowance.checked_sub(total).ok_or(Error::MathUnderflow)?;
             self.allowances.insert((from, caller), &new_allowance);
 
             let from_balance = self.balances.get(from).unwrap_or(0);
-            if from_balance < amount {
+            if from_balance < total {
                 return Err(Error::InsufficientBalance);
             }
 
             let new_from_balance = from_balance.checked_sub(amount).ok_or(Error::MathUnderflow)?;
-            self.balances.insert(from, &new_from_balance);
+            self._set_balance(from, new_from_balance);
 
             let to_balance = self.balances.get(to).unwrap_or(0);
             let new_to_balance = to_balance.checked_add(amount).ok_or(Error::MathOverflow)?;
-            self.balances.insert(to, &new_to_balance);
+            self._set_balance(to, new_to_balance);
 
             self.env().emit_event(Transfer {
                 from: Some(from),
@@ -537,28 +1109,77 @@ mod lusdt_token {
                 value: amount,
                 block_timestamp: self.env().block_timestamp(),
             });
+            self.collect_flat_transfer_fee(from, fee)?;
+
+            // Optional transfer tax, paid by the token owner
+            self.charge_transfer_tax(from, to, amount);
             Ok(())
         }
 
+        // === PSP22 METADATA ===
+
         #[ink(message)]
-        pub fn total_supply(&self) -> Balance {
-            self.total_supply
+        pub fn token_name(&self) -> Option<String> {
+            self.token_name.clone()
         }
 
         #[ink(message)]
-        pub fn balance_of(&self, who: AccountId) -> Balance {
-            self.balances.get(who).unwrap_or(0)
+        pub fn token_symbol(&self) -> Option<String> {
+            self.token_symbol.clone()
         }
 
         #[ink(message)]
-        pub fn allowance(&self, owner: AccountId, spender: AccountId) -> Balance {
-            self.allowances.get((owner, spender)).unwrap_or(0)
+        pub fn token_decimals(&self) -> u8 {
+            self.token_decimals
         }
 
-        // === ADMIN FUNCTIONS (Role Protected) ===
-
+        /// @notice Updates token metadata. Only ADMIN can call.
         #[ink(message)]
-        pub fn set_tax_manager_contract(&mut self, new_tax_manager: AccountId) -> Result<()> {
+        pub fn set_metadata(&mut self, name: Option<String>, symbol: Option<String>, decimals: u8) -> Result<()> {
+            self.ensure_role(DEFAULT_ADMIN_ROLE)?;
+            self.token_name = name.clone();
+            self.token_symbol = symbol.clone();
+            self.token_decimals = decimals;
+            self.env().emit_event(MetadataUpdated { name, symbol, decimals });
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn total_supply(&self) -> Balance {
+            self.total_supply
+        }
+
+        #[ink(message)]
+        pub fn get_max_supply(&self) -> Option<Balance> {
+            self.max_supply
+        }
+
+        /// @notice Sets (or removes with `None`) the supply cap. Only ADMIN can call.
+        /// @dev The cap can never be set below the circulating supply, which would lock minting.
+        #[ink(message)]
+        pub fn set_max_supply(&mut self, max_supply: Option<Balance>) -> Result<()> {
+            self.ensure_role(DEFAULT_ADMIN_ROLE)?;
+            if max_supply.is_some_and(|cap| cap < self.total_supply) {
+                return Err(Error::MaxSupplyExceeded);
+            }
+            self.max_supply = max_supply;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn balance_of(&self, who: AccountId) -> Balance {
+            self.balances.get(who).unwrap_or(0)
+        }
+
+        #[ink(message)]
+        pub fn allowance(&self, owner: AccountId, spender: AccountId) -> Balance {
+            self.allowances.get((owner, spender)).unwrap_or(0)
+        }
+
+        // === ADMIN FUNCTIONS (Role Protected) ===
+
+        #[ink(message)]
+        pub fn set_tax_manager_contract(&mut self, new_tax_manager: AccountId) -> Result<()> {
             // Only TAX_MANAGER or ADMIN can update this
             if !self.has_role(TAX_MANAGER_ROLE, self.env().caller()) && !self.has_role(DEFAULT_ADMIN_ROLE, self.env().caller()) {
                 return Err(Error::MissingRole);
@@ -572,9 +1193,204 @@ mod lusdt_token {
             self.tax_manager_contract
         }
 
+        /// @notice Sets the StakingManager that receives swept transfer fees. Only ADMIN can call.
+        /// @dev This contract must be the StakingManager's authorized depositor for the sweep to succeed.
+        #[ink(message)]
+        pub fn set_staking_manager(&mut self, staking_manager: Option<AccountId>) -> Result<()> {
+            self.ensure_role(DEFAULT_ADMIN_ROLE)?;
+            self.staking_manager = staking_manager;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn get_staking_manager(&self) -> Option<AccountId> {
+            self.staking_manager
+        }
+
+        #[ink(message)]
+        pub fn get_accrued_transfer_fees(&self) -> Balance {
+            self.accrued_transfer_fees
+        }
+
+        /// @notice Sets the flat LUSDT fee charged to senders on top of each transfer. Only ADMIN can call.
+        #[ink(message)]
+        pub fn set_flat_transfer_fee(&mut self, fee: Balance) -> Result<()> {
+            self.ensure_role(DEFAULT_ADMIN_ROLE)?;
+            self.flat_transfer_fee = fee;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn get_flat_transfer_fee(&self) -> Balance {
+            self.flat_transfer_fee
+        }
+
+        /// @notice Sets the account receiving flat transfer fees. Only ADMIN can call.
+        /// @dev Fees sent to this contract itself accrue for `sweep_transfer_fees_to_staking`.
+        #[ink(message)]
+        pub fn set_fee_collector(&mut self, fee_collector: AccountId) -> Result<()> {
+            self.ensure_role(DEFAULT_ADMIN_ROLE)?;
+            self.fee_collector = fee_collector;
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn get_fee_collector(&self) -> AccountId {
+            self.fee_collector
+        }
+
+        /// Flat fee `from` owes for a transfer to `to`. Fee movements (to or from the
+        /// collector, or by the Tax Manager) are exempt.
+        fn flat_transfer_fee_for(&self, from: AccountId, to: AccountId) -> Balance {
+            let exempt = [self.fee_collector, self.tax_manager_contract];
+            if exempt.contains(&from) || exempt.contains(&to) || self.env().caller() == self.tax_manager_contract {
+                return 0;
+            }
+            self.flat_transfer_fee
+        }
+
+        /// Moves a flat transfer fee from `from` to the fee collector.
+        fn collect_flat_transfer_fee(&mut self, from: AccountId, fee: Balance) -> Result<()> {
+            if fee == 0 { return Ok(()); }
+
+            let from_balance = self.balances.get(from).unwrap_or(0);
+            let new_from_balance = from_balance.checked_sub(fee).ok_or(Error::InsufficientBalance)?;
+            self._set_balance(from, new_from_balance);
+
+            let collector = self.fee_collector;
+            let collector_balance = self.balances.get(collector).unwrap_or(0);
+            self._set_balance(collector, collector_balance.checked_add(fee).ok_or(Error::MathOverflow)?);
+            if collector == self.env().account_id() {
+                self.accrued_transfer_fees = self.accrued_transfer_fees.checked_add(fee).ok_or(Error::MathOverflow)?;
+            }
+
+            self.env().emit_event(Transfer {
+                from: Some(from),
+                to: Some(collector),
+                value: fee,
+                block_timestamp: self.env().block_timestamp(),
+            });
+            Ok(())
+        }
+
+        /// @notice Sends accrued transfer-fee LUSDT to the StakingManager and notifies it as rewards.
+        /// @dev Permissionless so it can be triggered periodically by anyone. Returns the amount swept.
+        #[ink(message)]
+        pub fn sweep_transfer_fees_to_staking(&mut self) -> Result<Balance> {
+            self.ensure_not_paused(Subsystem::Transfer)?;
+            self.ensure_not_locked()?;
+
+            let result = (|| {
+                let staking_manager = self.staking_manager.ok_or(Error::StakingManagerNotSet)?;
+                let amount = self.accrued_transfer_fees;
+                if amount == 0 { return Ok(0); }
+
+                let this = self.env().account_id();
+                let from_balance = self.balances.get(this).unwrap_or(0);
+                if from_balance < amount {
+                    return Err(Error::InsufficientBalance);
+                }
+                self._set_balance(this, from_balance - amount);
+                let to_balance = self.balances.get(staking_manager).unwrap_or(0);
+                let new_to_balance = to_balance.checked_add(amount).ok_or(Error::MathOverflow)?;
+                self._set_balance(staking_manager, new_to_balance);
+                self.accrued_transfer_fees = 0;
+
+                self.env().emit_event(Transfer {
+                    from: Some(this),
+                    to: Some(staking_manager),
+                    value: amount,
+                    block_timestamp: self.env().block_timestamp(),
+                });
+
+                self.notify_staking_rewards(staking_manager, amount)
+                    .map_err(|_| Error::RewardNotifyFailed)?;
+
+                self.env().emit_event(TransferFeesSwept { staking_manager, amount });
+                Ok(amount)
+            })();
+
+            self.unlock();
+            result
+        }
+
+        #[cfg(not(any(test, feature = "off-chain-peers")))]
+        fn tax_process_fees(
+            &self,
+            operation: OperationType,
+            user: AccountId,
+            amount: Balance,
+        ) -> core::result::Result<(), ProtocolError> {
+            let mut tax_manager: ink::contract_ref!(TaxManager) = self.tax_manager_contract.into();
+            tax_manager.process_fees(operation, user, amount)
+        }
+
+        #[cfg(any(test, feature = "off-chain-peers"))]
+        fn tax_process_fees(
+            &self,
+            operation: OperationType,
+            user: AccountId,
+            amount: Balance,
+        ) -> core::result::Result<(), ProtocolError> {
+            let call = mock_peers::PeerCall::ProcessFees(operation, user, amount);
+            match mock_peers::call(self.tax_manager_contract, call) {
+                Some(false) => Err(ProtocolError::Other),
+                _ => Ok(()),
+            }
+        }
+
+        /// Tells the StakingManager about rewards already credited to it.
+        #[cfg(not(any(test, feature = "off-chain-peers")))]
+        fn notify_staking_rewards(&self, staking_manager: AccountId, amount: Balance) -> core::result::Result<(), ProtocolError> {
+            let mut staking: ink::contract_ref!(StakingManager) = staking_manager.into();
+            staking.notify_reward_amount(amount)
+        }
+
+        /// The off-chain test environment cannot invoke other contracts; a peer registered in
+        /// `mock_peers` receives the notification instead.
+        #[cfg(any(test, feature = "off-chain-peers"))]
+        fn notify_staking_rewards(&self, staking_manager: AccountId, amount: Balance) -> core::result::Result<(), ProtocolError> {
+            match mock_peers::call(staking_manager, mock_peers::PeerCall::NotifyRewardAmount(amount)) {
+                Some(false) => Err(ProtocolError::Other),
+                _ => Ok(()),
+            }
+        }
+
+        /// @notice Recomputes the sum of all balances and checks it equals `total_supply`. Only ADMIN can call.
+        /// @dev Iterates every indexed holder, so it is meant to be dry-run rather than submitted.
+        #[ink(message)]
+        pub fn audit_supply(&self) -> Result<bool> {
+            self.ensure_role(DEFAULT_ADMIN_ROLE)?;
+            let mut sum: Balance = 0;
+            for i in 0..self.holder_count {
+                if let Some(holder) = self.holders.get(i) {
+                    sum = sum
+                        .checked_add(self.balances.get(holder).unwrap_or(0))
+                        .ok_or(Error::MathOverflow)?;
+                }
+            }
+            Ok(sum == self.total_supply)
+        }
+
+        /// @notice Number of accounts that have ever held LUSDT (the set `audit_supply` sums over).
+        #[ink(message)]
+        pub fn get_holder_count(&self) -> u32 {
+            self.holder_count
+        }
+
         // === HELPERS ===
-        fn ensure_not_paused(&self) -> Result<()> {
-            if self.paused { return Err(Error::ContractPaused); }
+        /// Writes a balance, indexing the account the first time it holds tokens.
+        fn _set_balance(&mut self, account: AccountId, amount: Balance) {
+            self.balances.insert(account, &amount);
+            if amount > 0 && !self.holder_indexed.get(account).unwrap_or(false) {
+                self.holders.insert(self.holder_count, &account);
+                self.holder_indexed.insert(account, &true);
+                self.holder_count = self.holder_count.saturating_add(1);
+            }
+        }
+
+        fn ensure_not_paused(&self, subsystem: Subsystem) -> Result<()> {
+            if self.is_subsystem_paused(subsystem) { return Err(Error::ContractPaused); }
             Ok(())
         }
 
@@ -589,6 +1405,64 @@ mod lusdt_token {
         }
     }
 
+    /// @notice Shared PSP22 interface, so TaxManager and StakingManager can reach LUSDT
+    /// through `contract_ref!(PSP22)`. Delegates to the inherent messages above.
+    impl PSP22 for LusdtToken {
+        #[ink(message)]
+        fn transfer_from(&mut self, from: AccountId, to: AccountId, value: Balance) -> core::result::Result<(), ink::LangError> {
+            LusdtToken::transfer_from(self, from, to, value).map_err(|_| ink::LangError::CouldNotReadInput)
+        }
+
+        #[ink(message)]
+        fn transfer(&mut self, to: AccountId, value: Balance) -> core::result::Result<(), ink::LangError> {
+            LusdtToken::transfer(self, to, value).map_err(|_| ink::LangError::CouldNotReadInput)
+        }
+
+        #[ink(message)]
+        fn balance_of(&self, owner: AccountId) -> Balance {
+            LusdtToken::balance_of(self, owner)
+        }
+
+        #[ink(message)]
+        fn allowance(&self, owner: AccountId, spender: AccountId) -> Balance {
+            LusdtToken::allowance(self, owner, spender)
+        }
+    }
+
+    /// Real peer contracts deployed next to the token in tests. A registered peer
+    /// receives the calls the token would make on-chain.
+    #[cfg(any(test, feature = "off-chain-peers"))]
+    pub mod mock_peers {
+        use super::{AccountId, Balance, OperationType};
+        use std::cell::RefCell;
+
+        /// A cross-contract call made by the token.
+        pub enum PeerCall {
+            NotifyRewardAmount(Balance),
+            ProcessFees(OperationType, AccountId, Balance),
+        }
+
+        type Peer = Box<dyn FnMut(PeerCall) -> bool>;
+
+        thread_local! {
+            static PEERS: RefCell<Vec<(AccountId, Peer)>> = RefCell::new(Vec::new());
+        }
+
+        pub fn register(target: AccountId, peer: impl FnMut(PeerCall) -> bool + 'static) {
+            PEERS.with(|p| p.borrow_mut().push((target, Box::new(peer))));
+        }
+
+        /// Delivers `call` to the peer deployed at `target`; `None` if there is none.
+        pub fn call(target: AccountId, call: PeerCall) -> Option<bool> {
+            PEERS.with(|p| {
+                p.borrow_mut()
+                    .iter_mut()
+                    .find(|(at, _)| *at == target)
+                    .map(|(_, peer)| peer(call))
+            })
+        }
+    }
+
     #[cfg(test)]
     mod tests {
         use super::*;
@@ -612,6 +1486,27 @@ mod lusdt_token {
             assert!(!contract.has_role(DEFAULT_ADMIN_ROLE, OPERATOR.into()));
         }
 
+        #[ink::test]
+        fn metadata_defaults_set_by_constructor() {
+            let contract = setup();
+            assert_eq!(contract.token_name(), Some(String::from("Lunes USD")));
+            assert_eq!(contract.token_symbol(), Some(String::from("LUSDT")));
+            assert_eq!(contract.token_decimals(), 12);
+        }
+
+        #[ink::test]
+        fn set_metadata_requires_admin() {
+            let mut contract = setup();
+            set_caller::<DefaultEnvironment>(USER.into());
+            assert_eq!(contract.set_metadata(None, None, 6), Err(Error::MissingRole));
+
+            set_caller::<DefaultEnvironment>(OWNER.into());
+            assert!(contract.set_metadata(Some("Lunes Dollar".into()), Some("LUSD".into()), 6).is_ok());
+            assert_eq!(contract.token_name(), Some(String::from("Lunes Dollar")));
+            assert_eq!(contract.token_symbol(), Some(String::from("LUSD")));
+            assert_eq!(contract.token_decimals(), 6);
+        }
+
         #[ink::test]
         fn grant_revoke_role_works() {
             let mut contract = setup();
@@ -651,6 +1546,233 @@ mod lusdt_token {
             assert_eq!(contract.total_supply(), 1_000_000);
         }
 
+        #[ink::test]
+        fn psp22_trait_reaches_token_messages() {
+            let mut contract = setup();
+            set_caller::<DefaultEnvironment>(OPERATOR.into());
+            assert!(contract.mint(USER.into(), 1_000_000).is_ok());
+
+            // Peers call LUSDT through the shared trait selectors
+            set_caller::<DefaultEnvironment>(USER.into());
+            assert_eq!(PSP22::transfer(&mut contract, OPERATOR.into(), 100_000), Ok(()));
+            assert!(contract.approve(OWNER.into(), 50_000).is_ok());
+            assert_eq!(PSP22::allowance(&contract, USER.into(), OWNER.into()), 50_000);
+
+            set_caller::<DefaultEnvironment>(OWNER.into());
+            assert_eq!(PSP22::transfer_from(&mut contract, USER.into(), OWNER.into(), 50_000), Ok(()));
+            assert!(PSP22::transfer_from(&mut contract, USER.into(), OWNER.into(), 1).is_err());
+            assert_eq!(PSP22::balance_of(&contract, USER.into()), 850_000);
+            assert_eq!(PSP22::balance_of(&contract, OWNER.into()), 50_000);
+        }
+
+        #[ink::test]
+        fn audit_supply_matches_after_mints_transfers_and_burns() {
+            let mut contract = setup();
+            set_caller::<DefaultEnvironment>(OPERATOR.into());
+            assert!(contract.mint(USER.into(), 1_000_000).is_ok());
+            assert!(contract.mint(OWNER.into(), 50_000).is_ok());
+
+            set_caller::<DefaultEnvironment>(USER.into());
+            assert!(contract.transfer(TAX_MAN.into(), 200_000).is_ok());
+            assert!(contract.burn(300_000, "7xKXtg2CW87d97TXJSDpbD5jBkheTqA83TZRuJosgAsU".into()).is_ok());
+            // Emptying a balance keeps the holder indexed
+            set_caller::<DefaultEnvironment>(TAX_MAN.into());
+            assert!(contract.transfer(USER.into(), 200_000).is_ok());
+            assert_eq!(contract.get_holder_count(), 3);
+
+            set_caller::<DefaultEnvironment>(USER.into());
+            assert_eq!(contract.audit_supply(), Err(Error::MissingRole));
+            set_caller::<DefaultEnvironment>(OWNER.into());
+            assert_eq!(contract.audit_supply(), Ok(true));
+
+            // Crafted inconsistency: a balance written around the supply counter
+            contract.balances.insert(AccountId::from(TAX_MAN), &1);
+            assert_eq!(contract.audit_supply(), Ok(false));
+        }
+
+        #[ink::test]
+        fn mint_respects_max_supply() {
+            let mut contract = setup();
+            set_caller::<DefaultEnvironment>(OWNER.into());
+            assert!(contract.set_max_supply(Some(1_000)).is_ok());
+
+            set_caller::<DefaultEnvironment>(OPERATOR.into());
+            assert!(contract.mint(USER.into(), 1_000).is_ok());
+            assert_eq!(contract.mint(USER.into(), 1), Err(Error::MaxSupplyExceeded));
+            assert_eq!(contract.total_supply(), 1_000);
+        }
+
+        #[ink::test]
+        fn max_supply_cannot_go_below_circulating() {
+            let mut contract = setup();
+            set_caller::<DefaultEnvironment>(OPERATOR.into());
+            assert!(contract.mint(USER.into(), 500).is_ok());
+
+            set_caller::<DefaultEnvironment>(OWNER.into());
+            assert_eq!(contract.set_max_supply(Some(499)), Err(Error::MaxSupplyExceeded));
+            assert!(contract.set_max_supply(Some(500)).is_ok());
+            assert!(contract.set_max_supply(None).is_ok());
+            assert_eq!(contract.get_max_supply(), None);
+
+            set_caller::<DefaultEnvironment>(USER.into());
+            assert_eq!(contract.set_max_supply(Some(10_000)), Err(Error::MissingRole));
+        }
+
+        #[ink::test]
+        fn lowering_mint_cap_throttles_immediately() {
+            let mut contract = setup();
+            assert_eq!(contract.get_mint_rate_limit(), (3_600_000, 1_000_000_000_000));
+
+            set_caller::<DefaultEnvironment>(OPERATOR.into());
+            assert!(contract.mint(USER.into(), 600).is_ok());
+
+            set_caller::<DefaultEnvironment>(OWNER.into());
+            assert!(contract.set_mint_rate_limit(3_600_000, 1_000).is_ok());
+
+            set_caller::<DefaultEnvironment>(OPERATOR.into());
+            assert_eq!(contract.mint(USER.into(), 500), Err(Error::RateLimitExceeded));
+            assert!(contract.mint(USER.into(), 400).is_ok());
+        }
+
+        #[ink::test]
+        fn raising_mint_window_extends_accumulation() {
+            let mut contract = setup();
+            set_caller::<DefaultEnvironment>(OWNER.into());
+            assert!(contract.set_mint_rate_limit(7_200_000, 1_000).is_ok());
+
+            set_caller::<DefaultEnvironment>(OPERATOR.into());
+            assert!(contract.mint(USER.into(), 1_000).is_ok());
+
+            // Past the old 1h window the 2h window is still accumulating
+            ink::env::test::set_block_timestamp::<DefaultEnvironment>(3_600_000);
+            assert_eq!(contract.mint(USER.into(), 1), Err(Error::RateLimitExceeded));
+
+            ink::env::test::set_block_timestamp::<DefaultEnvironment>(7_200_000);
+            assert!(contract.mint(USER.into(), 1_000).is_ok());
+        }
+
+        #[ink::test]
+        fn zero_mint_window_rejected() {
+            let mut contract = setup();
+            set_caller::<DefaultEnvironment>(OWNER.into());
+            assert_eq!(contract.set_mint_rate_limit(0, 1_000), Err(Error::InvalidRateLimit));
+
+            set_caller::<DefaultEnvironment>(USER.into());
+            assert_eq!(contract.set_mint_rate_limit(1, 1), Err(Error::MissingRole));
+        }
+
+        #[ink::test]
+        fn mint_cooldown_blocks_same_recipient() {
+            let mut contract = setup();
+            set_caller::<DefaultEnvironment>(OWNER.into());
+            assert!(contract.set_mint_cooldown(60_000).is_ok());
+
+            set_caller::<DefaultEnvironment>(OPERATOR.into());
+            ink::env::test::set_block_timestamp::<DefaultEnvironment>(1_000);
+            assert!(contract.mint(USER.into(), 100).is_ok());
+
+            // Same recipient within cooldown is rejected
+            ink::env::test::set_block_timestamp::<DefaultEnvironment>(30_000);
+            assert_eq!(contract.mint(USER.into(), 100), Err(Error::MintCooldownActive));
+
+            // A different recipient is unaffected
+            assert!(contract.mint(TAX_MAN.into(), 100).is_ok());
+
+            // After the cooldown the original recipient can be minted to again
+            ink::env::test::set_block_timestamp::<DefaultEnvironment>(61_000);
+            assert!(contract.mint(USER.into(), 100).is_ok());
+            assert_eq!(contract.balance_of(USER.into()), 200);
+        }
+
+        #[ink::test]
+        fn mint_cooldown_disabled_by_default() {
+            let mut contract = setup();
+            assert_eq!(contract.get_mint_cooldown(), 0);
+
+            set_caller::<DefaultEnvironment>(OPERATOR.into());
+            assert!(contract.mint(USER.into(), 100).is_ok());
+            assert!(contract.mint(USER.into(), 100).is_ok());
+
+            // Only ADMIN may configure the cooldown
+            assert_eq!(contract.set_mint_cooldown(1), Err(Error::MissingRole));
+        }
+
+        #[ink::test]
+        fn sweep_transfer_fees_moves_accrued_to_staking() {
+            let mut contract = setup();
+            let this: AccountId = ink::env::test::callee::<DefaultEnvironment>();
+            let staking: AccountId = [9; 32].into();
+
+            // No staking manager configured yet
+            assert_eq!(contract.sweep_transfer_fees_to_staking(), Err(Error::StakingManagerNotSet));
+
+            set_caller::<DefaultEnvironment>(OWNER.into());
+            assert!(contract.set_staking_manager(Some(staking)).is_ok());
+
+            // Simulate fees accrued on the contract's own balance
+            set_caller::<DefaultEnvironment>(OPERATOR.into());
+            assert!(contract.mint(this, 5_000).is_ok());
+            contract.accrued_transfer_fees = 5_000;
+
+            set_caller::<DefaultEnvironment>(USER.into());
+            assert_eq!(contract.sweep_transfer_fees_to_staking(), Ok(5_000));
+            assert_eq!(contract.balance_of(staking), 5_000);
+            assert_eq!(contract.balance_of(this), 0);
+            assert_eq!(contract.get_accrued_transfer_fees(), 0);
+
+            // Nothing left to sweep
+            assert_eq!(contract.sweep_transfer_fees_to_staking(), Ok(0));
+        }
+
+        #[ink::test]
+        fn flat_transfer_fee_routes_to_collector() {
+            let mut contract = setup();
+            let this: AccountId = ink::env::test::callee::<DefaultEnvironment>();
+            let recipient: AccountId = [8; 32].into();
+            set_caller::<DefaultEnvironment>(OPERATOR.into());
+            assert!(contract.mint(USER.into(), 1_000).is_ok());
+
+            // Zero by default: unchanged behaviour
+            assert_eq!(contract.get_flat_transfer_fee(), 0);
+            assert_eq!(contract.get_fee_collector(), this);
+            set_caller::<DefaultEnvironment>(USER.into());
+            assert!(contract.transfer(recipient, 300).is_ok());
+            assert_eq!(contract.balance_of(USER.into()), 700);
+            assert_eq!(contract.balance_of(recipient), 300);
+
+            assert_eq!(contract.set_flat_transfer_fee(10), Err(Error::MissingRole));
+            set_caller::<DefaultEnvironment>(OWNER.into());
+            assert!(contract.set_flat_transfer_fee(10).is_ok());
+
+            // Sender pays value + fee; fees held here accrue for the staking sweep
+            set_caller::<DefaultEnvironment>(USER.into());
+            assert!(contract.transfer(recipient, 100).is_ok());
+            assert_eq!(contract.balance_of(USER.into()), 590);
+            assert_eq!(contract.balance_of(recipient), 400);
+            assert_eq!(contract.balance_of(this), 10);
+            assert_eq!(contract.get_accrued_transfer_fees(), 10);
+
+            // Must cover value + fee
+            assert_eq!(contract.transfer(recipient, 585), Err(Error::InsufficientBalance));
+            assert_eq!(contract.balance_of(USER.into()), 590);
+
+            // transfer_from spends the fee from the allowance as well
+            let collector: AccountId = [9; 32].into();
+            set_caller::<DefaultEnvironment>(OWNER.into());
+            assert!(contract.set_fee_collector(collector).is_ok());
+            set_caller::<DefaultEnvironment>(USER.into());
+            assert!(contract.approve(OPERATOR.into(), 100).is_ok());
+            set_caller::<DefaultEnvironment>(OPERATOR.into());
+            assert_eq!(
+                contract.transfer_from(USER.into(), recipient, 100),
+                Err(Error::InsufficientAllowance)
+            );
+            assert!(contract.transfer_from(USER.into(), recipient, 90).is_ok());
+            assert_eq!(contract.balance_of(USER.into()), 490);
+            assert_eq!(contract.balance_of(collector), 10);
+            assert_eq!(contract.get_accrued_transfer_fees(), 10);
+        }
+
         #[ink::test]
         fn approve_and_transfer_from_work() {
             let mut contract = setup();
@@ -674,6 +1796,46 @@ mod lusdt_token {
             assert_eq!(contract.total_supply(), 1_000_000);
         }
 
+        #[ink::test]
+        fn increase_allowance_from_zero() {
+            let mut contract = setup();
+            set_caller::<DefaultEnvironment>(USER.into());
+            assert!(contract.increase_allowance(TAX_MAN.into(), 300).is_ok());
+            assert!(contract.increase_allowance(TAX_MAN.into(), 200).is_ok());
+            assert_eq!(contract.allowance(USER.into(), TAX_MAN.into()), 500);
+        }
+
+        #[ink::test]
+        fn decrease_allowance_saturates_at_zero() {
+            let mut contract = setup();
+            set_caller::<DefaultEnvironment>(USER.into());
+            assert!(contract.approve(TAX_MAN.into(), 100).is_ok());
+            assert!(contract.decrease_allowance(TAX_MAN.into(), 40).is_ok());
+            assert_eq!(contract.allowance(USER.into(), TAX_MAN.into()), 60);
+
+            assert!(contract.decrease_allowance(TAX_MAN.into(), 1_000).is_ok());
+            assert_eq!(contract.allowance(USER.into(), TAX_MAN.into()), 0);
+        }
+
+        #[ink::test]
+        fn adjusted_allowance_limits_transfer_from() {
+            let mut contract = setup();
+            set_caller::<DefaultEnvironment>(OPERATOR.into());
+            assert!(contract.mint(USER.into(), 1_000_000).is_ok());
+
+            set_caller::<DefaultEnvironment>(USER.into());
+            assert!(contract.increase_allowance(TAX_MAN.into(), 500).is_ok());
+            assert!(contract.decrease_allowance(TAX_MAN.into(), 200).is_ok());
+
+            set_caller::<DefaultEnvironment>(TAX_MAN.into());
+            assert_eq!(
+                contract.transfer_from(USER.into(), TAX_MAN.into(), 301),
+                Err(Error::InsufficientAllowance)
+            );
+            assert!(contract.transfer_from(USER.into(), TAX_MAN.into(), 300).is_ok());
+            assert_eq!(contract.allowance(USER.into(), TAX_MAN.into()), 0);
+        }
+
         #[ink::test]
         fn transfer_from_fails_without_approval() {
             let mut contract = setup();
@@ -718,6 +1880,427 @@ mod lusdt_token {
             assert_eq!(contract.total_supply(), 500_000);
         }
 
+        #[ink::test]
+        fn burns_in_same_block_get_unique_request_ids() {
+            type Event = <LusdtToken as ::ink::reflect::ContractEventBase>::Type;
+
+            let mut contract = setup();
+            set_caller::<DefaultEnvironment>(OPERATOR.into());
+            assert!(contract.mint(USER.into(), 1_000_000).is_ok());
+
+            set_caller::<DefaultEnvironment>(USER.into());
+            let solana = "7xKXtg2CW87d97TXJSDpbD5jBkheTqA83TZRuJosgAsU";
+            assert!(contract.burn(100, solana.into()).is_ok());
+            assert!(contract.burn(100, solana.into()).is_ok());
+            assert_eq!(contract.get_redemption_nonce(), 2);
+
+            let request_ids: Vec<u64> = ink::env::test::recorded_events()
+                .filter_map(|e| match <Event as scale::Decode>::decode(&mut &e.data[..]) {
+                    Ok(Event::RedemptionRequested(r)) => Some(r.request_id),
+                    _ => None,
+                })
+                .collect();
+            assert_eq!(request_ids, vec![1, 2]);
+        }
+
+        #[ink::test]
+        fn mint_for_deposit_rejects_replayed_tx_hash() {
+            let mut contract = setup();
+            let first = Hash::from([7u8; 32]);
+            let second = Hash::from([8u8; 32]);
+
+            set_caller::<DefaultEnvironment>(OPERATOR.into());
+            assert!(contract.mint_for_deposit(USER.into(), 1_000, first).is_ok());
+            assert!(contract.is_deposit_processed(first));
+            assert_eq!(
+                contract.mint_for_deposit(OWNER.into(), 1_000, first),
+                Err(Error::DuplicateDeposit)
+            );
+            assert_eq!(contract.balance_of(OWNER.into()), 0);
+
+            assert!(contract.mint_for_deposit(OWNER.into(), 500, second).is_ok());
+            assert_eq!(contract.balance_of(USER.into()), 1_000);
+            assert_eq!(contract.balance_of(OWNER.into()), 500);
+            assert_eq!(contract.total_supply(), 1_500);
+        }
+
+        #[ink::test]
+        fn transfer_tax_is_off_by_default_and_admin_controlled() {
+            let mut contract = setup();
+            assert!(!contract.is_transfer_tax_enabled());
+            assert!(!contract.is_taxed_transfer(USER.into(), OPERATOR.into()));
+
+            set_caller::<DefaultEnvironment>(USER.into());
+            assert_eq!(contract.set_transfer_tax_enabled(true), Err(Error::MissingRole));
+
+            set_caller::<DefaultEnvironment>(OWNER.into());
+            assert!(contract.set_transfer_tax_enabled(true).is_ok());
+            set_caller::<DefaultEnvironment>(USER.into());
+            assert!(contract.is_taxed_transfer(USER.into(), OPERATOR.into()));
+
+            // Fee movements involving the Tax Manager are never taxed
+            assert!(!contract.is_taxed_transfer(USER.into(), TAX_MAN.into()));
+            set_caller::<DefaultEnvironment>(TAX_MAN.into());
+            assert!(!contract.is_taxed_transfer(USER.into(), OPERATOR.into()));
+        }
+
+        #[ink::test]
+        fn taxed_transfers_call_tax_manager_per_leg() {
+            use std::{cell::RefCell, rc::Rc};
+
+            let mut contract = setup();
+            let recipient: AccountId = [8; 32].into();
+            set_caller::<DefaultEnvironment>(OPERATOR.into());
+            assert!(contract.mint(USER.into(), 1_000).is_ok());
+
+            let taxed = Rc::new(RefCell::new(Vec::new()));
+            let log = taxed.clone();
+            mock_peers::register(TAX_MAN.into(), move |call| match call {
+                mock_peers::PeerCall::ProcessFees(operation, user, amount) => {
+                    log.borrow_mut().push((operation, user, amount));
+                    true
+                }
+                _ => false,
+            });
+
+            // Tax off: nothing reaches the Tax Manager
+            set_caller::<DefaultEnvironment>(USER.into());
+            assert!(contract.transfer(recipient, 100).is_ok());
+            assert!(taxed.borrow().is_empty());
+
+            set_caller::<DefaultEnvironment>(OWNER.into());
+            assert!(contract.set_transfer_tax_enabled(true).is_ok());
+            set_caller::<DefaultEnvironment>(USER.into());
+            assert!(contract.transfer(recipient, 100).is_ok());
+            assert!(contract.batch_transfer(vec![(recipient, 10), (OPERATOR.into(), 20)]).is_ok());
+            assert!(contract.approve(OPERATOR.into(), 50).is_ok());
+            set_caller::<DefaultEnvironment>(OPERATOR.into());
+            assert!(contract.transfer_from(USER.into(), recipient, 50).is_ok());
+
+            let user: AccountId = USER.into();
+            assert_eq!(
+                *taxed.borrow(),
+                vec![
+                    (OperationType::Transfer, user, 100),
+                    (OperationType::Transfer, user, 10),
+                    (OperationType::Transfer, user, 20),
+                    (OperationType::Transfer, user, 50),
+                ]
+            );
+        }
+
+        #[ink::test]
+        fn approve_works_while_guarded_operation_in_flight() {
+            let mut contract = setup();
+            // Simulate a callback arriving during a guarded mint/burn
+            contract.locked = true;
+
+            set_caller::<DefaultEnvironment>(USER.into());
+            assert!(contract.approve(TAX_MAN.into(), 1_000).is_ok());
+            assert_eq!(contract.allowance(USER.into(), TAX_MAN.into()), 1_000);
+            // The outer operation's lock is left untouched
+            assert!(contract.locked);
+
+            set_caller::<DefaultEnvironment>(OPERATOR.into());
+            assert_eq!(contract.mint(USER.into(), 1_000), Err(Error::ReentrancyDetected));
+        }
+
+        #[ink::test]
+        fn emergency_pause_records_reason_and_timestamp() {
+            type Event = <LusdtToken as ::ink::reflect::ContractEventBase>::Type;
+
+            let mut contract = setup();
+            assert_eq!(contract.pause_status(), (false, None, None));
+
+            ink::env::test::set_block_timestamp::<DefaultEnvironment>(42_000);
+            assert!(contract.emergency_pause("Security breach detected".into()).is_ok());
+            assert_eq!(
+                contract.pause_status(),
+                (true, Some(String::from("Security breach detected")), Some(42_000))
+            );
+
+            let reasons: Vec<String> = ink::env::test::recorded_events()
+                .filter_map(|e| match <Event as scale::Decode>::decode(&mut &e.data[..]) {
+                    Ok(Event::EmergencyPause(p)) => Some(p.reason),
+                    _ => None,
+                })
+                .collect();
+            assert_eq!(reasons, vec![String::from("Security breach detected")]);
+
+            assert!(contract.emergency_unpause().is_ok());
+            assert_eq!(contract.pause_status(), (false, None, None));
+        }
+
+        #[ink::test]
+        fn cumulative_counters_track_gross_flows() {
+            let mut contract = setup();
+            let solana = "7xKXtg2CW87d97TXJSDpbD5jBkheTqA83TZRuJosgAsU";
+
+            set_caller::<DefaultEnvironment>(OPERATOR.into());
+            assert!(contract.mint(USER.into(), 1_000).is_ok());
+            set_caller::<DefaultEnvironment>(USER.into());
+            assert!(contract.burn(300, solana.into()).is_ok());
+            set_caller::<DefaultEnvironment>(OPERATOR.into());
+            assert!(contract.mint(OWNER.into(), 500).is_ok());
+
+            assert_eq!(contract.get_cumulative_minted(), 1_500);
+            assert_eq!(contract.get_cumulative_burned(), 300);
+            assert_eq!(contract.total_supply(), 1_200);
+        }
+
+        #[ink::test]
+        fn bridge_burn_from_spends_allowance() {
+            type Event = <LusdtToken as ::ink::reflect::ContractEventBase>::Type;
+
+            let mut contract = setup();
+            let solana = "7xKXtg2CW87d97TXJSDpbD5jBkheTqA83TZRuJosgAsU";
+            set_caller::<DefaultEnvironment>(OPERATOR.into());
+            assert!(contract.mint(USER.into(), 1_000).is_ok());
+
+            // No allowance yet
+            assert_eq!(
+                contract.burn_from(USER.into(), 400, solana.into()),
+                Err(Error::InsufficientAllowance)
+            );
+
+            set_caller::<DefaultEnvironment>(USER.into());
+            assert!(contract.approve(OPERATOR.into(), 500).is_ok());
+            // Only the bridge may burn on a user's behalf
+            assert_eq!(contract.burn_from(USER.into(), 400, solana.into()), Err(Error::MissingRole));
+
+            set_caller::<DefaultEnvironment>(OPERATOR.into());
+            assert!(contract.burn_from(USER.into(), 400, solana.into()).is_ok());
+            assert_eq!(contract.balance_of(USER.into()), 600);
+            assert_eq!(contract.total_supply(), 600);
+            assert_eq!(contract.allowance(USER.into(), OPERATOR.into()), 100);
+            assert_eq!(
+                contract.burn_from(USER.into(), 101, solana.into()),
+                Err(Error::InsufficientAllowance)
+            );
+
+            let originators: Vec<AccountId> = ink::env::test::recorded_events()
+                .filter_map(|e| match <Event as scale::Decode>::decode(&mut &e.data[..]) {
+                    Ok(Event::RedemptionRequested(r)) => Some(r.from),
+                    _ => None,
+                })
+                .collect();
+            assert_eq!(originators, vec![AccountId::from(USER)]);
+        }
+
+        #[ink::test]
+        fn redemption_event_carries_configured_format_version() {
+            type Event = <LusdtToken as ::ink::reflect::ContractEventBase>::Type;
+
+            let mut contract = setup();
+            assert_eq!(contract.get_bridge_message_version(), BRIDGE_MESSAGE_VERSION);
+            set_caller::<DefaultEnvironment>(OPERATOR.into());
+            assert!(contract.mint(USER.into(), 1_000_000).is_ok());
+
+            let solana = "7xKXtg2CW87d97TXJSDpbD5jBkheTqA83TZRuJosgAsU";
+            set_caller::<DefaultEnvironment>(USER.into());
+            assert!(contract.burn(100, solana.into()).is_ok());
+            assert_eq!(contract.set_bridge_message_version(2), Err(Error::MissingRole));
+
+            set_caller::<DefaultEnvironment>(OWNER.into());
+            assert!(contract.set_bridge_message_version(2).is_ok());
+            set_caller::<DefaultEnvironment>(USER.into());
+            assert!(contract.burn(100, solana.into()).is_ok());
+
+            let versions: Vec<u16> = ink::env::test::recorded_events()
+                .filter_map(|e| match <Event as scale::Decode>::decode(&mut &e.data[..]) {
+                    Ok(Event::RedemptionRequested(r)) => Some(r.format_version),
+                    _ => None,
+                })
+                .collect();
+            assert_eq!(versions, vec![BRIDGE_MESSAGE_VERSION, 2]);
+        }
+
+        #[ink::test]
+        fn solana_address_validation() {
+            // Mainnet-style addresses, including leading-zero encodings
+            assert!(validate_solana_address("7xKXtg2CW87d97TXJSDpbD5jBkheTqA83TZRuJosgAsU").is_ok());
+            assert!(validate_solana_address("So11111111111111111111111111111111111111112").is_ok());
+            assert!(validate_solana_address("11111111111111111111111111111111").is_ok());
+
+            // Characters outside the base58 alphabet
+            for bad in ["0xKXtg2CW87d97TXJSDpbD5jBkheTqA83TZRuJosgAsU",
+                        "7xKXtg2CW87d97TXJSDpbD5jBkheTqA83TZRuJosgAsO",
+                        "7xKXtg2CW87d97TXJSDpbD5jBkheTqA83TZRuJosgAsl"] {
+                assert_eq!(validate_solana_address(bad), Err(Error::InvalidSolanaAddress));
+            }
+
+            // Valid base58 but decodes to the wrong byte length (23 and 33 bytes)
+            assert_eq!(
+                validate_solana_address("22222222222222222222222222222222"),
+                Err(Error::InvalidSolanaAddress)
+            );
+            assert_eq!(
+                validate_solana_address("zzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzzz"),
+                Err(Error::InvalidSolanaAddress)
+            );
+        }
+
+        #[ink::test]
+        fn burn_rejects_malformed_solana_address() {
+            let mut contract = setup();
+            set_caller::<DefaultEnvironment>(OPERATOR.into());
+            assert!(contract.mint(USER.into(), 1_000).is_ok());
+
+            set_caller::<DefaultEnvironment>(USER.into());
+            assert_eq!(
+                contract.burn(100, "7xKXtg2CW87d97TXJSDpbD5jBkheTqA83TZRuJosgA0U".into()),
+                Err(Error::InvalidSolanaAddress)
+            );
+            assert_eq!(contract.balance_of(USER.into()), 1_000);
+        }
+
+        #[ink::test]
+        fn frozen_accounts_cannot_send_or_receive() {
+            let mut contract = setup();
+            set_caller::<DefaultEnvironment>(OPERATOR.into());
+            assert!(contract.mint(USER.into(), 1_000).is_ok());
+
+            set_caller::<DefaultEnvironment>(OWNER.into());
+            assert!(contract.freeze_account(USER.into()).is_ok());
+            assert!(contract.is_frozen(USER.into()));
+
+            // Frozen sender
+            set_caller::<DefaultEnvironment>(USER.into());
+            assert_eq!(contract.transfer(OPERATOR.into(), 100), Err(Error::AccountFrozen));
+            assert_eq!(
+                contract.burn(100, "7xKXtg2CW87d97TXJSDpbD5jBkheTqA83TZRuJosgAsU".into()),
+                Err(Error::AccountFrozen)
+            );
+            assert!(contract.approve(TAX_MAN.into(), 100).is_ok());
+            set_caller::<DefaultEnvironment>(TAX_MAN.into());
+            assert_eq!(
+                contract.transfer_from(USER.into(), TAX_MAN.into(), 100),
+                Err(Error::AccountFrozen)
+            );
+
+            // Frozen recipient
+            set_caller::<DefaultEnvironment>(OPERATOR.into());
+            assert_eq!(contract.mint(USER.into(), 100), Err(Error::AccountFrozen));
+            assert!(contract.mint(OPERATOR.into(), 100).is_ok());
+            assert_eq!(contract.transfer(USER.into(), 50), Err(Error::AccountFrozen));
+            assert_eq!(contract.balance_of(USER.into()), 1_000);
+        }
+
+        #[ink::test]
+        fn unfreeze_restores_transfers() {
+            let mut contract = setup();
+            set_caller::<DefaultEnvironment>(OPERATOR.into());
+            assert!(contract.mint(USER.into(), 1_000).is_ok());
+
+            set_caller::<DefaultEnvironment>(OWNER.into());
+            assert!(contract.freeze_account(USER.into()).is_ok());
+            assert!(contract.unfreeze_account(USER.into()).is_ok());
+
+            set_caller::<DefaultEnvironment>(USER.into());
+            assert!(contract.transfer(OPERATOR.into(), 100).is_ok());
+            assert_eq!(contract.freeze_account(OPERATOR.into()), Err(Error::MissingRole));
+        }
+
+        #[ink::test]
+        fn burn_works_when_only_mint_paused() {
+            let mut contract = setup();
+            set_caller::<DefaultEnvironment>(OPERATOR.into());
+            assert!(contract.mint(USER.into(), 1_000).is_ok());
+
+            // OWNER holds PAUSER_ROLE from the constructor
+            set_caller::<DefaultEnvironment>(OWNER.into());
+            assert!(contract.set_subsystem_paused(Subsystem::Mint, true).is_ok());
+            assert!(contract.is_subsystem_paused(Subsystem::Mint));
+            assert!(!contract.is_subsystem_paused(Subsystem::Burn));
+
+            set_caller::<DefaultEnvironment>(OPERATOR.into());
+            assert_eq!(contract.mint(USER.into(), 100), Err(Error::ContractPaused));
+
+            // Users can still exit and move funds
+            set_caller::<DefaultEnvironment>(USER.into());
+            assert!(contract.burn(400, "7xKXtg2CW87d97TXJSDpbD5jBkheTqA83TZRuJosgAsU".into()).is_ok());
+            assert!(contract.transfer(OPERATOR.into(), 100).is_ok());
+            assert_eq!(contract.balance_of(USER.into()), 500);
+        }
+
+        #[ink::test]
+        fn master_pause_overrides_subsystems() {
+            let mut contract = setup();
+            set_caller::<DefaultEnvironment>(OWNER.into());
+            assert!(contract.emergency_pause("incident".into()).is_ok());
+            assert!(contract.is_subsystem_paused(Subsystem::Burn));
+            assert!(contract.is_subsystem_paused(Subsystem::Transfer));
+
+            set_caller::<DefaultEnvironment>(USER.into());
+            assert_eq!(contract.set_subsystem_paused(Subsystem::Burn, true), Err(Error::MissingRole));
+        }
+
+        #[ink::test]
+        fn mint_with_receipt_reports_the_mint() {
+            let mut contract = setup();
+            set_caller::<DefaultEnvironment>(OPERATOR.into());
+            assert!(contract.mint(USER.into(), 500).is_ok());
+
+            let receipt = contract.mint_with_receipt(USER.into(), 1_000).unwrap();
+            assert_eq!(receipt, OperationReceipt {
+                gross: 1_000,
+                fee: 0,
+                net: 1_000,
+                new_balance: 1_500,
+                nonce: 2,
+            });
+            assert_eq!(receipt.new_balance, contract.balance_of(USER.into()));
+            assert_eq!(contract.get_mint_nonce(), 2);
+
+            // Zero mints change nothing
+            let receipt = contract.mint_with_receipt(USER.into(), 0).unwrap();
+            assert_eq!((receipt.new_balance, receipt.nonce), (1_500, 2));
+
+            set_caller::<DefaultEnvironment>(USER.into());
+            assert_eq!(contract.mint_with_receipt(USER.into(), 1), Err(Error::MissingRole));
+        }
+
+        #[ink::test]
+        fn batch_transfer_pays_every_recipient() {
+            let mut contract = setup();
+            set_caller::<DefaultEnvironment>(OPERATOR.into());
+            assert!(contract.mint(USER.into(), 1_000).is_ok());
+
+            set_caller::<DefaultEnvironment>(USER.into());
+            let batch = vec![(OPERATOR.into(), 300), (TAX_MAN.into(), 200), (OWNER.into(), 100)];
+            assert!(contract.batch_transfer(batch).is_ok());
+
+            assert_eq!(contract.balance_of(USER.into()), 400);
+            assert_eq!(contract.balance_of(OPERATOR.into()), 300);
+            assert_eq!(contract.balance_of(TAX_MAN.into()), 200);
+            assert_eq!(contract.balance_of(OWNER.into()), 100);
+            assert_eq!(contract.total_supply(), 1_000);
+        }
+
+        #[ink::test]
+        fn batch_transfer_exceeding_balance_changes_nothing() {
+            let mut contract = setup();
+            set_caller::<DefaultEnvironment>(OPERATOR.into());
+            assert!(contract.mint(USER.into(), 1_000).is_ok());
+
+            set_caller::<DefaultEnvironment>(USER.into());
+            let batch = vec![(OPERATOR.into(), 600), (TAX_MAN.into(), 600)];
+            assert_eq!(contract.batch_transfer(batch), Err(Error::InsufficientBalance));
+            assert_eq!(contract.balance_of(USER.into()), 1_000);
+            assert_eq!(contract.balance_of(OPERATOR.into()), 0);
+
+            // Guard released: a normal transfer still works
+            assert!(contract.transfer(OPERATOR.into(), 1).is_ok());
+        }
+
+        #[ink::test]
+        fn batch_transfer_rejects_oversized_batch() {
+            let mut contract = setup();
+            set_caller::<DefaultEnvironment>(USER.into());
+            let batch = vec![(OPERATOR.into(), 0); MAX_BATCH_TRANSFER + 1];
+            assert_eq!(contract.batch_transfer(batch), Err(Error::BatchTooLarge));
+        }
+
         #[ink::test]
         fn transfer_insufficient_balance_fails() {
             let mut contract = setup();
@@ -68,6 +68,16 @@ pub mod mock_lunes_token {
             self.transfer_from_to(from, to, value)
                 .map_err(|_| ink::LangError::CouldNotReadInput)
         }
+
+        #[ink(message)]
+        fn balance_of(&self, owner: AccountId) -> Balance {
+            MockLunesToken::balance_of(self, owner)
+        }
+
+        #[ink(message)]
+        fn allowance(&self, owner: AccountId, spender: AccountId) -> Balance {
+            MockLunesToken::allowance(self, owner, spender)
+        }
     }
 
     impl MockLunesToken {
@@ -147,4 +157,27 @@ pub mod mock_lunes_token {
             Ok(())
         }
     }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use ink::env::{test::set_caller, DefaultEnvironment};
+
+        #[ink::test]
+        fn balances_are_queried_through_the_shared_trait() {
+            let accounts = ink::env::test::default_accounts::<DefaultEnvironment>();
+            set_caller::<DefaultEnvironment>(accounts.alice);
+            let mut token = MockLunesToken::new(1_000);
+
+            assert!(PSP22::transfer(&mut token, accounts.bob, 400).is_ok());
+            assert!(token.approve(accounts.charlie, 150).is_ok());
+
+            assert_eq!(PSP22::balance_of(&token, accounts.alice), 600);
+            assert_eq!(PSP22::balance_of(&token, accounts.bob), 400);
+            assert_eq!(PSP22::allowance(&token, accounts.alice, accounts.charlie), 150);
+            // The inherent messages stay available under their original selectors
+            assert_eq!(token.balance_of(accounts.alice), 600);
+            assert_eq!(token.allowance(accounts.alice, accounts.charlie), 150);
+        }
+    }
 }
@@ -10,7 +10,8 @@
 //! - Staked LUNES can ONLY be returned to the original staker (no admin withdrawal)
 //! - LUSDT rewards can ONLY be claimed by the staker who earned them
 //! - Reward accounting uses the Synthetix "reward-per-token" accumulator pattern
-//! - Owner can ONLY: set authorized depositors, pause/unpause, set cooldown period
+//! - Owner can ONLY: set authorized depositors, pause/unpause, set cooldown period,
+//!   route a share of new deposits to LUNES buyback-and-burn (never to an owner wallet)
 //! - Owner CANNOT: withdraw staked funds, redirect rewards, modify balances
 //!
 //! ## Reward Math (Synthetix pattern)
@@ -25,14 +26,47 @@
 
 #[ink::contract]
 pub mod staking_manager {
+    use ink::prelude::vec::Vec;
     use ink::storage::Mapping;
+    use common::errors::ProtocolError;
     use common::traits::StakingManager as StakingManagerApi;
+    #[cfg(not(test))]
+    use common::traits::PSP22;
 
     /// Precision factor for reward-per-token calculations (18 decimals).
     const PRECISION: u128 = 1_000_000_000_000_000_000; // 1e18
 
+    /// One day in milliseconds (reward-rate unit).
+    const DAY_MS: u64 = 86_400_000;
+
+    /// Maximum number of beneficiaries in a single `stake_for_batch` call.
+    const MAX_BATCH_SIZE: usize = 50;
+
+    /// Number of reward deposits retained in the history ring buffer.
+    const MAX_DEPOSIT_HISTORY: u32 = 1_000;
+
+    /// Maximum entries returned by one `get_deposit_history` page.
+    const MAX_HISTORY_PAGE: u32 = 100;
+
+    /// Maximum entries returned by one `get_stakers_paged` page.
+    const MAX_STAKER_PAGE: u32 = 100;
+
+    /// Days of reward deposits averaged by `get_reward_rate_per_day`.
+    const REWARD_RATE_WINDOW_DAYS: u64 = 7;
+
+    /// Lock tiers as (lock duration, reward multiplier in bps). Tier 0 is unlocked.
+    const LOCK_TIERS: [(u64, u16); 4] = [
+        (0, 10_000),
+        (30 * DAY_MS, 12_500),
+        (90 * DAY_MS, 15_000),
+        (180 * DAY_MS, 20_000),
+    ];
+
     // ─── Storage Types ───────────────────────────────────────────────
 
+    /// A staker's stake as of a snapshot: (snapshot id, stake at that snapshot).
+    type StakeCheckpoint = (u64, Balance);
+
     /// Per-staker accounting data. Stored on-chain per AccountId.
     #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode, Default)]
     #[cfg_attr(
@@ -46,7 +80,86 @@ pub mod staking_manager {
         pub reward_per_token_paid: u128,
         /// Accumulated but unclaimed LUSDT rewards.
         pub pending_rewards: Balance,
-        /// Timestamp when user first staked (or last re-staked).
+        /// Timestamp when the current position was opened (kept across top-ups).
+        pub staked_at: Timestamp,
+        /// Time-weighted period this staker was last settled in.
+        pub tw_period: u32,
+        /// Stake × milliseconds accumulated in `tw_period` up to `tw_last_update`.
+        pub tw_stake_ms: u128,
+        /// Timestamp of the last time-weighted settlement.
+        pub tw_last_update: Timestamp,
+        /// Timestamp of the most recent stake addition; the unstake cooldown runs from here.
+        pub last_stake_at: Timestamp,
+        /// Lock tier (index into `LOCK_TIERS`, 0 = unlocked) applied to the whole position.
+        pub lock_tier: u8,
+        /// The position cannot be unstaked (except via `emergency_unstake`) before this.
+        pub lock_until: Timestamp,
+        /// Snapshot of `locked_reward_per_token_stored` at last user interaction.
+        pub locked_reward_per_token_paid: u128,
+        /// Snapshot of `reward_per_token_stored_lunes` at last user interaction.
+        pub reward_per_token_paid_lunes: u128,
+        /// Accumulated but unclaimed LUNES rewards.
+        pub pending_rewards_lunes: Balance,
+    }
+
+    /// A closed time-weighted reward period (between two reward deposits).
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct TimeWeightedPeriod {
+        pub start: Timestamp,
+        pub end: Timestamp,
+        /// Reward per token held for the whole period (scaled by PRECISION; 0 if not time-weighted).
+        pub reward_per_token: u128,
+        /// Sum of `reward_per_token` over all periods up to and including this one.
+        pub cumulative_reward_per_token: u128,
+    }
+
+    /// One entry of the reward deposit ledger.
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct DepositRecord {
+        pub timestamp: Timestamp,
+        pub depositor: AccountId,
+        pub amount: Balance,
+        /// `reward_per_token_stored` right after this deposit.
+        pub reward_per_token_after: u128,
+    }
+
+    /// An additional reward pool (id ≥ 1) with its own stake and accumulator.
+    /// Pool 0 is the contract's main pool and is not stored here.
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode, Default)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct RewardPool {
+        /// LUNES staked into this pool.
+        pub total_staked: Balance,
+        /// Accumulated reward per staked token for this pool (scaled by PRECISION).
+        pub reward_per_token_stored: u128,
+        /// Total LUSDT deposited into this pool.
+        pub total_deposited: Balance,
+        /// Deposits received while the pool had no stake, distributed with the next deposit.
+        pub queued_rewards: Balance,
+    }
+
+    /// A staker's position in an additional reward pool.
+    #[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode, Default)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct PoolPosition {
+        pub amount: Balance,
+        /// Snapshot of the pool's `reward_per_token_stored` at last interaction.
+        pub reward_per_token_paid: u128,
+        pub pending_rewards: Balance,
         pub staked_at: Timestamp,
     }
 
@@ -68,6 +181,23 @@ pub mod staking_manager {
         total_staked: Balance,
     }
 
+    #[ink(event)]
+    pub struct StakeLockUpdated {
+        #[ink(topic)]
+        user: AccountId,
+        tier: u8,
+        lock_until: Timestamp,
+    }
+
+    #[ink(event)]
+    pub struct EmergencyUnstaked {
+        #[ink(topic)]
+        user: AccountId,
+        amount: Balance,
+        forfeited_rewards: Balance,
+        total_staked: Balance,
+    }
+
     #[ink(event)]
     pub struct RewardsClaimed {
         #[ink(topic)]
@@ -81,6 +211,192 @@ pub mod staking_manager {
         depositor: AccountId,
         amount: Balance,
         new_reward_per_token: u128,
+        /// Accumulator increment from this deposit's instant share (0 if it was held or streamed)
+        reward_per_token_delta: u128,
+        /// Pool size when the deposit landed (for historical APR)
+        total_staked_at_deposit: Balance,
+        staker_count_at_deposit: u32,
+        /// Running total of distributed deposits, including this one
+        cumulative_deposited: Balance,
+    }
+
+    /// Emitted when part of a claimed reward came from a boost mechanic.
+    #[ink(event)]
+    pub struct RewardBoostApplied {
+        #[ink(topic)]
+        user: AccountId,
+        base_amount: Balance,
+        boosted_amount: Balance,
+    }
+
+    #[ink(event)]
+    pub struct RewardFreezeUpdated {
+        #[ink(topic)]
+        user: AccountId,
+        frozen: bool,
+    }
+
+    /// Emitted when LUNES rewards are deposited for stakers.
+    #[ink(event)]
+    pub struct LunesRewardsDeposited {
+        #[ink(topic)]
+        depositor: AccountId,
+        amount: Balance,
+        new_reward_per_token: u128,
+    }
+
+    /// Emitted when a staker claims LUNES rewards.
+    #[ink(event)]
+    pub struct LunesRewardsClaimed {
+        #[ink(topic)]
+        user: AccountId,
+        amount: Balance,
+    }
+
+    /// Emitted when a failed reward payout is moved into escrow for `withdraw_escrow`.
+    #[ink(event)]
+    pub struct RewardsEscrowed {
+        #[ink(topic)]
+        user: AccountId,
+        amount: Balance,
+    }
+
+    /// Emitted when a share of a reward deposit is swapped to LUNES and sent to the BurnEngine.
+    #[ink(event)]
+    pub struct BuybackExecuted {
+        lusdt_amount: Balance,
+        lunes_amount: Balance,
+    }
+
+    /// Emitted when settled rewards are folded into a staker's position.
+    #[ink(event)]
+    pub struct RewardsCompounded {
+        #[ink(topic)]
+        user: AccountId,
+        amount: Balance,
+        new_stake: Balance,
+    }
+
+    /// Emitted when claimed LUSDT is swapped into LUNES for the staker via `auto_compound_target`.
+    #[ink(event)]
+    pub struct RewardsForwarded {
+        #[ink(topic)]
+        user: AccountId,
+        target: AccountId,
+        lusdt_amount: Balance,
+        lunes_amount: Balance,
+    }
+
+    /// Emitted when an unrelated token sent to the contract by mistake is recovered.
+    #[ink(event)]
+    pub struct TokenRescued {
+        #[ink(topic)]
+        token: AccountId,
+        to: AccountId,
+        amount: Balance,
+    }
+
+    /// Emitted when a staker changes their notification preference (consumed by off-chain notifiers).
+    #[ink(event)]
+    pub struct StakerPreferenceSet {
+        #[ink(topic)]
+        user: AccountId,
+        notify_on_deposit: bool,
+    }
+
+    /// Emitted when `recompute_staker_count` corrects a drifted `staker_count`.
+    #[ink(event)]
+    pub struct StakerCountReconciled {
+        previous: u32,
+        actual: u32,
+    }
+
+    /// Emitted when `repair_staker` clamps a reward snapshot that ran ahead of its accumulator.
+    #[ink(event)]
+    pub struct StakerRepaired {
+        #[ink(topic)]
+        user: AccountId,
+        previous_paid: u128,
+        repaired_paid: u128,
+    }
+
+    /// Emitted when `migrate_in` imports a staker from the previous deployment.
+    #[ink(event)]
+    pub struct StakerMigrated {
+        #[ink(topic)]
+        user: AccountId,
+        amount: Balance,
+        total_staked: Balance,
+    }
+
+    /// Emitted when a voting-power snapshot is taken.
+    #[ink(event)]
+    pub struct SnapshotTaken {
+        #[ink(topic)]
+        snapshot_id: u64,
+        total_staked: Balance,
+    }
+
+    /// Emitted when accumulated rounding dust is redistributed to current stakers.
+    #[ink(event)]
+    pub struct RewardsReconciled {
+        amount: Balance,
+        new_reward_per_token: u128,
+    }
+
+    #[ink(event)]
+    pub struct RewardPoolCreated {
+        #[ink(topic)]
+        pool_id: u32,
+    }
+
+    #[ink(event)]
+    pub struct PoolStaked {
+        #[ink(topic)]
+        user: AccountId,
+        #[ink(topic)]
+        pool_id: u32,
+        amount: Balance,
+        pool_total_staked: Balance,
+    }
+
+    #[ink(event)]
+    pub struct PoolUnstaked {
+        #[ink(topic)]
+        user: AccountId,
+        #[ink(topic)]
+        pool_id: u32,
+        amount: Balance,
+        pool_total_staked: Balance,
+    }
+
+    #[ink(event)]
+    pub struct PoolRewardsDeposited {
+        #[ink(topic)]
+        pool_id: u32,
+        #[ink(topic)]
+        depositor: AccountId,
+        amount: Balance,
+        new_reward_per_token: u128,
+    }
+
+    /// Emitted when the owner opens an insurance claim window after a documented shortfall.
+    #[ink(event)]
+    pub struct InsuranceClaimsOpened {
+        #[ink(topic)]
+        round: u32,
+        payout_pool: Balance,
+        total_staked_snapshot: Balance,
+        justification: ink::prelude::string::String,
+    }
+
+    #[ink(event)]
+    pub struct InsuranceClaimPaid {
+        #[ink(topic)]
+        user: AccountId,
+        #[ink(topic)]
+        round: u32,
+        amount: Balance,
     }
 
     #[ink(event)]
@@ -114,26 +430,90 @@ pub mod staking_manager {
         CooldownNotElapsed,
         /// Contract is paused.
         ContractPaused,
+        /// Staker's rewards are frozen by the owner.
+        RewardsFrozen,
+        /// Contract's actual LUSDT balance is lower than the reward owed.
+        InsufficientRewardBalance,
+        /// Batch is empty or exceeds the maximum size.
+        InvalidBatchSize,
+        /// A configuration parameter is out of range.
+        InvalidConfig,
+        /// Buyback swap through the router failed.
+        BuybackFailed,
+        /// Token is custodied by this contract (LUNES / LUSDT) and cannot be rescued.
+        ProtectedToken,
+        /// Transfer of a rescued token failed.
+        TokenTransferFailed,
+        /// Reward pool id does not exist.
+        PoolNotFound,
+        /// No insurance claim window is open.
+        InsuranceClaimsDisabled,
+        /// Caller already claimed in the current insurance round.
+        InsuranceAlreadyClaimed,
+        /// Requested unstake amount exceeds the caller's stake.
+        InsufficientStake,
+        /// Lock tier does not exist, or is shorter than the position's current tier.
+        InvalidLockTier,
+        /// The position is locked until `lock_until`.
+        StakeLocked,
+        /// Claim allowance for the current window is used up; rewards keep accruing.
+        ClaimFrequencyExceeded,
+        /// New reward deposits are paused; existing rewards and streams are unaffected.
+        RewardDepositsPaused,
+        /// The `auto_compound_target` router failed to swap claimed rewards.
+        CompoundSwapFailed,
+        /// The position has not been held for `min_stake_duration_ms` since `staked_at`.
+        MinDurationNotMet,
+        /// The operation requires the contract to be paused.
+        NotPaused,
+        /// The staker was already imported, or already has a position here.
+        AlreadyMigrated,
+        /// A guarded message was re-entered while another was still executing.
+        ReentrancyDetected,
+        /// Token addresses can only change while nothing is staked or owed.
+        TokensInUse,
+        /// The same beneficiary appears more than once in a batch.
+        DuplicateBeneficiary,
+        /// A swap returned less than the reference rate minus `max_swap_slippage_bps`,
+        /// or no reference rate is configured.
+        SlippageExceeded,
+    }
+
+    impl From<Error> for ProtocolError {
+        fn from(error: Error) -> Self {
+            match error {
+                Error::Unauthorized => ProtocolError::Unauthorized,
+                Error::ContractPaused | Error::RewardDepositsPaused => ProtocolError::Paused,
+                Error::InsufficientRewardBalance | Error::InsufficientStake => ProtocolError::InsufficientBalance,
+                Error::ArithmeticOverflow => ProtocolError::Overflow,
+                Error::LunesTransferFailed
+                | Error::LusdtTransferFailed
+                | Error::TokenTransferFailed
+                | Error::CompoundSwapFailed => {
+                    ProtocolError::TransferFailed
+                }
+                Error::ZeroAmount | Error::InvalidConfig | Error::InvalidBatchSize => ProtocolError::InvalidInput,
+                _ => ProtocolError::Other,
+            }
+        }
     }
 
     // ─── Contract Storage ────────────────────────────────────────────
 
-    /// A minimal PSP22 trait for token interaction.
+    /// Minimal swap router interface used for LUNES buybacks.
+    /// `amount_in` of `token_in` must already be held by the router; the output
+    /// is sent to `to` and the amount received is returned.
     #[ink::trait_definition]
-    pub trait PSP22 {
+    pub trait SwapRouter {
         #[ink(message)]
-        fn transfer_from(
+        fn swap_exact_input(
             &mut self,
-            from: AccountId,
+            token_in: AccountId,
+            token_out: AccountId,
+            amount_in: u128,
+            min_amount_out: u128,
             to: AccountId,
-            value: u128,
-        ) -> Result<(), ink::LangError>;
-
-        #[ink(message)]
-        fn transfer(&mut self, to: AccountId, value: u128) -> Result<(), ink::LangError>;
-
-        #[ink(message)]
-        fn balance_of(&self, owner: AccountId) -> u128;
+        ) -> Result<u128, ink::LangError>;
     }
 
     #[ink(storage)]
@@ -146,14 +526,21 @@ pub mod staking_manager {
         lusdt_token: AccountId,
         /// Minimum stake in LUNES smallest unit (100_000 * 10^12 for 12-decimal token).
         min_stake: Balance,
+        /// Per-lock-tier minimum stake overrides (tiers without an entry use `min_stake`).
+        tier_min_stake: Mapping<u8, Balance>,
         /// Total LUNES locked across all stakers.
         total_staked: Balance,
-        /// Accumulated reward per staked token (scaled by PRECISION).
+        /// Accumulated reward per staked token (scaled by PRECISION). While the locked
+        /// sub-pool is enabled this is the flexible sub-pool's accumulator.
         reward_per_token_stored: u128,
         /// Total LUSDT rewards ever deposited.
         total_rewards_deposited: Balance,
         /// Total LUSDT rewards ever claimed.
         total_rewards_claimed: Balance,
+        /// Portion of claimed rewards earned by plain stake weight.
+        total_base_rewards: Balance,
+        /// Portion of claimed rewards attributable to boost mechanics.
+        total_boosted_rewards: Balance,
         /// Per-staker data.
         stakers: Mapping<AccountId, StakerInfo>,
         /// Number of active stakers.
@@ -165,21 +552,192 @@ pub mod staking_manager {
         /// Addresses authorized to call deposit_rewards / notify_reward_amount.
         /// Typically the Tax Manager contract address.
         authorized_depositor: Option<AccountId>,
+        /// Address allowed to pause (not unpause) alongside the owner, e.g. the
+        /// Tax Manager for protocol-wide `pause_all`.
+        pauser: Option<AccountId>,
+        /// Exponential moving average of the reward rate (LUSDT per day).
+        ema_reward_rate: Balance,
+        /// EMA smoothing factor in basis points (weight of the newest sample).
+        ema_alpha_bps: u16,
+        /// Timestamp of the last reward deposit (deploy time before the first one).
+        last_reward_deposit_at: Timestamp,
+        /// Share of each reward deposit (bps) swapped to LUNES and burned instead of distributed.
+        buyback_bps: u16,
+        /// Router used to swap LUSDT → LUNES for buybacks.
+        swap_router: Option<AccountId>,
+        /// BurnEngine receiving bought-back LUNES.
+        burn_engine: Option<AccountId>,
+        /// Expected LUNES per LUSDT (raw units, scaled by `PRECISION`) that router swaps are
+        /// checked against. 0 = not configured, and every swap is rejected.
+        swap_reference_rate: Balance,
+        /// Maximum shortfall (bps) a router swap may return below `swap_reference_rate`.
+        max_swap_slippage_bps: u16,
+        /// Total LUSDT diverted to buybacks.
+        total_buyback_lusdt: Balance,
+        /// Total LUNES bought back and sent to the BurnEngine.
+        total_buyback_lunes: Balance,
+        /// Ring buffer of the most recent reward deposits, keyed by `sequence % MAX_DEPOSIT_HISTORY`.
+        deposit_history: Mapping<u32, DepositRecord>,
+        /// Total deposits ever recorded (next sequence number).
+        deposit_history_count: u32,
+        /// When true, deposits are split by stake × time held during the period since
+        /// the previous deposit instead of by instantaneous stake.
+        time_weighted_rewards: bool,
+        /// Current (open) time-weighted period.
+        tw_period: u32,
+        /// Start of the current period (timestamp of the previous deposit).
+        tw_period_start: Timestamp,
+        /// Total stake × milliseconds accumulated in the current period up to `tw_last_update`.
+        tw_total_stake_ms: u128,
+        /// Last time `tw_total_stake_ms` was accrued.
+        tw_last_update: Timestamp,
+        /// `cumulative_reward_per_token` of the last closed period.
+        tw_cumulative_reward_per_token: u128,
+        /// Closed time-weighted periods.
+        tw_periods: Mapping<u32, TimeWeightedPeriod>,
+        /// Stakers whose settled rewards are folded into their stake on every interaction
+        /// (only possible when the reward token is the stake token).
+        auto_compound: Mapping<AccountId, bool>,
+        /// Stakers whose settled rewards are paid out on every `stake` top-up.
+        auto_claim_on_stake: Mapping<AccountId, bool>,
+        /// Stakers opted into "notify on reward deposit".
+        notification_prefs: Mapping<AccountId, bool>,
+        /// Stakers whose reward accrual is frozen (e.g. pending a compliance investigation).
+        /// Principal stays fully unstakeable; only accrual and claiming are blocked.
+        reward_frozen: Mapping<AccountId, bool>,
+        /// Reentrancy guard held across stake/unstake/claim/deposit bodies.
+        locked: bool,
+        /// Additional reward pools, ids `1..=reward_pool_count` (pool 0 is the main pool).
+        reward_pools: Mapping<u32, RewardPool>,
+        /// Number of additional reward pools created.
+        reward_pool_count: u32,
+        /// Staker positions in additional pools, keyed by (pool id, staker).
+        pool_positions: Mapping<(u32, AccountId), PoolPosition>,
+        /// Accounts with an active main-pool position, kept dense (swap-remove on exit).
+        staker_index: Mapping<u32, AccountId>,
+        /// Length of `staker_index`.
+        staker_index_len: u32,
+        /// Reverse lookup: position of an account in `staker_index`.
+        staker_index_pos: Mapping<AccountId, u32>,
+        /// Paginated `recompute_staker_count` progress: (next index, stakers counted so far).
+        staker_recount: (u32, u32),
+        /// Main-pool rewards lost to rounding or forfeited by frozen stakers (scaled by
+        /// PRECISION), redistributed by `reconcile_rewards`.
+        reward_dust: u128,
+        /// LUSDT held as principal-protection insurance (kept apart from rewards).
+        insurance_balance: Balance,
+        /// Whether an insurance claim window is open.
+        insurance_claim_enabled: bool,
+        /// Current claim round (incremented each time a window opens).
+        insurance_round: u32,
+        /// Insurance balance when the current window opened; claims are pro-rata of this.
+        insurance_payout_pool: Balance,
+        /// `total_staked` when the current window opened.
+        insurance_total_staked: Balance,
+        /// Stake snapshot taken when the current window opened; claims are pro-rata of
+        /// each staker's stake in it, so later top-ups don't raise the payout.
+        insurance_snapshot_id: u64,
+        /// Stakers that claimed, keyed by (round, staker).
+        insurance_claims: Mapping<(u32, AccountId), Balance>,
+        /// Rewards deposited per day: slot `day % REWARD_RATE_WINDOW_DAYS` → (day, amount).
+        reward_days: Mapping<u32, (u64, Balance)>,
+        /// Minimum stake for `can_create_proposal` (0 = any active staker).
+        proposal_threshold: Balance,
+        /// Sum of stakes weighted by lock multiplier; the main-pool reward denominator.
+        total_effective_staked: Balance,
+        /// Share of each deposit (bps) distributed immediately; the rest streams.
+        instant_bps: u16,
+        /// Period over which the streamed share is released linearly.
+        reward_duration_ms: u64,
+        /// Streamed rewards released per millisecond (scaled by PRECISION).
+        stream_rate: u128,
+        /// When the current stream ends.
+        stream_finish: Timestamp,
+        /// Up to when the stream has been folded into `reward_per_token_stored`.
+        stream_last_update: Timestamp,
+        /// Claims allowed per staker per `claim_window_ms` (0 = unlimited).
+        max_claims_per_window: u32,
+        /// Length of the claim-frequency window.
+        claim_window_ms: u64,
+        /// Per-staker (claims, window start) for the claim-frequency limit.
+        claim_counts: Mapping<AccountId, (u32, Timestamp)>,
+        /// Instant rewards deposited while nothing was staked, folded in on the next stake.
+        pending_undistributed: Balance,
+        /// Blocks new reward deposits/notifications only; accrual and claims continue.
+        reward_deposits_paused: bool,
+        /// Pending rewards above this are paid out on the next stake/unstake (0 = unlimited).
+        max_pending_rewards: Balance,
+        /// Share of each deposit (bps) reserved for locked positions (0 = one shared pool).
+        locked_pool_bps: u16,
+        /// Accumulated reward per effective locked token (scaled by PRECISION).
+        locked_reward_per_token_stored: u128,
+        /// Effective stake of positions with a lock tier; the locked sub-pool denominator.
+        total_locked_effective_staked: Balance,
+        /// Router that `claim_and_notify` swaps claimed LUSDT through (None = plain claim).
+        auto_compound_target: Option<AccountId>,
+        /// Minimum time a position must be held since `staked_at` before unstaking (0 = none).
+        /// Unlike the cooldown, top-ups don't restart it.
+        min_stake_duration_ms: u64,
+        /// Governance contract allowed to take voting-power snapshots alongside the owner.
+        governance: Option<AccountId>,
+        /// Id of the latest snapshot (0 = none taken yet).
+        current_snapshot_id: u64,
+        /// `total_staked` at each snapshot.
+        snapshot_total_staked: Mapping<u64, Balance>,
+        /// Per-staker checkpoints keyed by (staker, index): (snapshot id, stake at that
+        /// snapshot). Written only when a stake changes after a new snapshot.
+        stake_checkpoints: Mapping<(AccountId, u32), StakeCheckpoint>,
+        /// Number of checkpoints per staker.
+        stake_checkpoint_count: Mapping<AccountId, u32>,
+        /// Account allowed to push staker state into this deployment via `migrate_in`.
+        migration_source: Option<AccountId>,
+        /// Stakers already imported by `migrate_in`.
+        migrated: Mapping<AccountId, bool>,
+        /// LUSDT rewards whose automatic payout failed, withdrawable via `withdraw_escrow`.
+        unclaimed_escrow: Mapping<AccountId, Balance>,
+        /// Sum of `unclaimed_escrow`.
+        total_escrowed: Balance,
+        /// Accumulated LUNES reward per effective staked token (scaled by PRECISION).
+        /// Independent of the LUSDT accumulators.
+        reward_per_token_stored_lunes: u128,
+        /// Total LUNES rewards ever deposited.
+        total_lunes_rewards_deposited: Balance,
+        /// Total LUNES rewards ever claimed.
+        total_lunes_rewards_claimed: Balance,
     }
 
     // ─── StakingManagerApi trait implementation ──────────────────────
 
     impl StakingManagerApi for StakingManager {
         #[ink(message)]
-        fn deposit_rewards(&mut self, amount: Balance) -> Result<(), ink::LangError> {
-            self._deposit_rewards(amount)
-                .map_err(|_| ink::LangError::CouldNotReadInput)
+        fn deposit_rewards(&mut self, amount: Balance) -> Result<(), ProtocolError> {
+            self.ensure_not_locked()?;
+            let result = self._deposit_rewards(amount);
+            self.unlock();
+            result.map_err(ProtocolError::from)
+        }
+
+        #[ink(message)]
+        fn notify_reward_amount(&mut self, amount: Balance) -> Result<(), ProtocolError> {
+            self.ensure_not_locked()?;
+            let result = self._notify_reward_amount(amount);
+            self.unlock();
+            result.map_err(ProtocolError::from)
         }
 
         #[ink(message)]
-        fn notify_reward_amount(&mut self, amount: Balance) -> Result<(), ink::LangError> {
-            self._notify_reward_amount(amount)
-                .map_err(|_| ink::LangError::CouldNotReadInput)
+        fn notify_lunes_reward_amount(&mut self, amount: Balance) -> Result<(), ProtocolError> {
+            self.ensure_not_locked()?;
+            let result = (|| {
+                if amount == 0 {
+                    return Err(Error::ZeroAmount);
+                }
+                self.ensure_authorized_depositor()?;
+                self.ensure_reward_deposits_open()?;
+                self._distribute_lunes_rewards(amount, self.env().caller())
+            })();
+            self.unlock();
+            result.map_err(ProtocolError::from)
         }
     }
 
@@ -202,15 +760,89 @@ pub mod staking_manager {
                 lunes_token,
                 lusdt_token,
                 min_stake,
+                tier_min_stake: Mapping::default(),
                 total_staked: 0,
                 reward_per_token_stored: 0,
                 total_rewards_deposited: 0,
                 total_rewards_claimed: 0,
+                total_base_rewards: 0,
+                total_boosted_rewards: 0,
                 stakers: Mapping::default(),
                 staker_count: 0,
                 unstake_cooldown_ms: 0,
                 paused: false,
                 authorized_depositor: None,
+                pauser: None,
+                ema_reward_rate: 0,
+                ema_alpha_bps: 2_000, // 20% weight on the newest sample
+                last_reward_deposit_at: Self::env().block_timestamp(),
+                buyback_bps: 0,
+                swap_router: None,
+                burn_engine: None,
+                swap_reference_rate: 0,
+                max_swap_slippage_bps: 100, // 1%
+                total_buyback_lusdt: 0,
+                total_buyback_lunes: 0,
+                deposit_history: Mapping::default(),
+                deposit_history_count: 0,
+                time_weighted_rewards: false,
+                tw_period: 0,
+                tw_period_start: Self::env().block_timestamp(),
+                tw_total_stake_ms: 0,
+                tw_last_update: Self::env().block_timestamp(),
+                tw_cumulative_reward_per_token: 0,
+                tw_periods: Mapping::default(),
+                auto_compound: Mapping::default(),
+                auto_claim_on_stake: Mapping::default(),
+                notification_prefs: Mapping::default(),
+                reward_frozen: Mapping::default(),
+                locked: false,
+                reward_pools: Mapping::default(),
+                reward_pool_count: 0,
+                pool_positions: Mapping::default(),
+                staker_index: Mapping::default(),
+                staker_index_len: 0,
+                staker_recount: (0, 0),
+                staker_index_pos: Mapping::default(),
+                reward_dust: 0,
+                insurance_balance: 0,
+                insurance_claim_enabled: false,
+                insurance_round: 0,
+                insurance_payout_pool: 0,
+                insurance_total_staked: 0,
+                insurance_snapshot_id: 0,
+                insurance_claims: Mapping::default(),
+                reward_days: Mapping::default(),
+                proposal_threshold: 0,
+                total_effective_staked: 0,
+                instant_bps: 10_000,
+                reward_duration_ms: 7 * DAY_MS,
+                stream_rate: 0,
+                stream_finish: 0,
+                stream_last_update: 0,
+                max_claims_per_window: 0,
+                claim_window_ms: DAY_MS,
+                claim_counts: Mapping::default(),
+                pending_undistributed: 0,
+                reward_deposits_paused: false,
+                max_pending_rewards: 0,
+                locked_pool_bps: 0,
+                locked_reward_per_token_stored: 0,
+                total_locked_effective_staked: 0,
+                auto_compound_target: None,
+                min_stake_duration_ms: 0,
+                governance: None,
+                current_snapshot_id: 0,
+                snapshot_total_staked: Mapping::default(),
+                stake_checkpoints: Mapping::default(),
+                stake_checkpoint_count: Mapping::default(),
+                migration_source: None,
+                migrated: Mapping::default(),
+                unclaimed_escrow: Mapping::default(),
+                total_escrowed: 0,
+                reward_per_token_stored_lunes: 0,
+                total_lunes_rewards_deposited: 0,
+                total_lunes_rewards_claimed: 0,
             }
         }
 
@@ -224,556 +856,5202 @@ pub mod staking_manager {
         /// @param amount Amount of LUNES to stake (in smallest unit).
         #[ink(message)]
         pub fn stake(&mut self, amount: Balance) -> Result<(), Error> {
-            self.ensure_not_paused()?;
-
-            let caller = self.env().caller();
-            let mut info = self.stakers.get(caller).unwrap_or_default();
-
-            // Check minimum: new total must be ≥ min_stake
-            let new_total = info.amount.checked_add(amount).ok_or(Error::ArithmeticOverflow)?;
-            if new_total < self.min_stake {
-                return Err(Error::BelowMinimumStake);
-            }
+            self.ensure_not_locked()?;
 
-            // Settle any pending rewards before changing stake
-            self._update_reward(&caller, &mut info)?;
+            let result = (|| {
+                self.ensure_not_paused()?;
 
-            // Transfer LUNES from user to this contract
-            let mut lunes: ink::contract_ref!(PSP22) = self.lunes_token.into();
-            lunes
-                .transfer_from(caller, self.env().account_id(), amount)
-                .map_err(|_| Error::LunesTransferFailed)?;
+                let caller = self.env().caller();
+                self.ensure_meets_minimum(caller, amount)?;
 
-            // Update staker info
-            let was_new = info.amount == 0;
-            info.amount = new_total;
-            if was_new {
-                info.staked_at = self.env().block_timestamp();
-                self.staker_count = self.staker_count.saturating_add(1);
-            }
-            self.stakers.insert(caller, &info);
+                // Transfer LUNES from user to this contract
+                self.token_transfer_from(self.lunes_token, caller, amount)
+                    .map_err(|_| Error::LunesTransferFailed)?;
 
-            // Update global total
-            self.total_staked = self.total_staked.checked_add(amount).ok_or(Error::ArithmeticOverflow)?;
+                self._credit_stake(caller, amount)?;
 
-            self.env().emit_event(Staked {
-                user: caller,
-                amount,
-                total_staked: self.total_staked,
-            });
+                if self.get_auto_claim_on_stake(caller) {
+                    self._auto_claim(caller);
+                }
+                Ok(())
+            })();
 
-            Ok(())
+            self.unlock();
+            result
         }
 
-        /// Unstake ALL staked LUNES. Settles pending rewards automatically.
-        /// Subject to cooldown period if configured.
+        /// Stake LUNES into a lock tier (1 = 30, 2 = 90, 3 = 180 days) for a 1.25x / 1.5x / 2x
+        /// reward weight. The tier applies to the whole position and the lock runs from now;
+        /// a position can move to a longer tier but never back to a shorter one.
+        ///
+        /// @param amount Amount of LUNES to add (in smallest unit).
+        /// @param tier Lock tier to apply.
         #[ink(message)]
-        pub fn unstake(&mut self) -> Result<(), Error> {
-            let caller = self.env().caller();
-            let mut info = self.stakers.get(caller).ok_or(Error::NoActiveStake)?;
+        pub fn stake_locked(&mut self, amount: Balance, tier: u8) -> Result<(), Error> {
+            self.ensure_not_locked()?;
 
-            if info.amount == 0 {
-                return Err(Error::NoActiveStake);
-            }
+            let result = (|| {
+                self.ensure_not_paused()?;
+                if amount == 0 {
+                    return Err(Error::ZeroAmount);
+                }
+                let (duration, _) = match LOCK_TIERS.get(tier as usize) {
+                    Some(params) if tier > 0 => *params,
+                    _ => return Err(Error::InvalidLockTier),
+                };
 
-            // Check cooldown
-            if self.unstake_cooldown_ms > 0 {
-                let elapsed = self.env().block_timestamp().saturating_sub(info.staked_at);
-                if elapsed < self.unstake_cooldown_ms {
-                    return Err(Error::CooldownNotElapsed);
+                let caller = self.env().caller();
+                let mut info = self.stakers.get(caller).unwrap_or_default();
+                if tier < info.lock_tier {
+                    return Err(Error::InvalidLockTier);
+                }
+                let new_total = info.amount.checked_add(amount).ok_or(Error::ArithmeticOverflow)?;
+                if new_total < self.get_tier_min_stake(tier) {
+                    return Err(Error::BelowMinimumStake);
                 }
-            }
 
-            // Settle pending rewards
-            self._update_reward(&caller, &mut info)?;
+                self.token_transfer_from(self.lunes_token, caller, amount)
+                    .map_err(|_| Error::LunesTransferFailed)?;
 
-            let unstake_amount = info.amount;
+                // Settle at the old weight before the multiplier changes
+                self._settle_reward(&caller, &mut info)?;
+                let before = info.clone();
+                info.lock_tier = tier;
+                info.lock_until = info
+                    .lock_until
+                    .max(self.env().block_timestamp().saturating_add(duration));
+                self._reweight(&before, &info);
+                self.stakers.insert(caller, &info);
 
-            // Transfer LUNES back to user
-            let mut lunes: ink::contract_ref!(PSP22) = self.lunes_token.into();
-            lunes
-                .transfer(caller, unstake_amount)
-                .map_err(|_| Error::LunesTransferFailed)?;
+                self._credit_stake(caller, amount)?;
 
-            // Update state
-            self.total_staked = self.total_staked.saturating_sub(unstake_amount);
-            info.amount = 0;
-            info.staked_at = 0;
-            self.staker_count = self.staker_count.saturating_sub(1);
+                self.env().emit_event(StakeLockUpdated {
+                    user: caller,
+                    tier,
+                    lock_until: info.lock_until,
+                });
+                Ok(())
+            })();
 
-            // Auto-claim pending rewards if any
-            let pending = info.pending_rewards;
-            if pending > 0 {
-                let mut lusdt: ink::contract_ref!(PSP22) = self.lusdt_token.into();
-                if lusdt.transfer(caller, pending).is_ok() {
-                    self.total_rewards_claimed = self.total_rewards_claimed.saturating_add(pending);
-                    info.pending_rewards = 0;
+            self.unlock();
+            result
+        }
 
-                    self.env().emit_event(RewardsClaimed {
-                        user: caller,
-                        reward_amount: pending,
-                    });
+        /// Opt in/out of auto-compounding: settled rewards are restaked on every
+        /// interaction instead of held as pending. Only available when rewards are
+        /// paid in the stake token.
+        #[ink(message)]
+        pub fn set_auto_compound(&mut self, enabled: bool) -> Result<(), Error> {
+            let caller = self.env().caller();
+            if enabled {
+                if self.lusdt_token != self.lunes_token {
+                    return Err(Error::InvalidConfig);
                 }
+                self.auto_compound.insert(caller, &true);
+            } else {
+                self.auto_compound.remove(caller);
             }
-
-            self.stakers.insert(caller, &info);
-
-            self.env().emit_event(Unstaked {
-                user: caller,
-                amount: unstake_amount,
-                total_staked: self.total_staked,
-            });
-
             Ok(())
         }
 
-        /// Claim all accumulated LUSDT rewards without unstaking.
+        /// Whether a staker auto-compounds rewards.
         #[ink(message)]
-        pub fn claim_rewards(&mut self) -> Result<(), Error> {
-            let caller = self.env().caller();
-            let mut info = self.stakers.get(caller).ok_or(Error::NoActiveStake)?;
-
-            // Update reward accounting
-            self._update_reward(&caller, &mut info)?;
+        pub fn get_auto_compound(&self, user: AccountId) -> bool {
+            self.auto_compound.get(user).unwrap_or(false)
+        }
 
+        /// Internal: fold settled rewards into the stake for auto-compounding stakers.
+        /// Rewards stay in this contract, so no transfer is needed. Returns whether it compounded.
+        fn _maybe_compound(&mut self, user: AccountId, info: &mut StakerInfo) -> Result<bool, Error> {
             let reward = info.pending_rewards;
-            if reward == 0 {
-                return Err(Error::NoRewardsToClaim);
+            if reward == 0
+                || info.amount == 0
+                || self.lusdt_token != self.lunes_token
+                || !self.get_auto_compound(user)
+                || self.is_reward_frozen(user)
+            {
+                return Ok(false);
             }
 
-            // Transfer LUSDT rewards to user
-            let mut lusdt: ink::contract_ref!(PSP22) = self.lusdt_token.into();
-            lusdt
-                .transfer(caller, reward)
-                .map_err(|_| Error::LusdtTransferFailed)?;
-
-            // Update state
-            self.total_rewards_claimed = self.total_rewards_claimed.saturating_add(reward);
+            self._accrue_total_stake_time();
+            let before = info.clone();
+            self._checkpoint_stake(user, before.amount);
+            info.amount = info.amount.checked_add(reward).ok_or(Error::ArithmeticOverflow)?;
             info.pending_rewards = 0;
-            self.stakers.insert(caller, &info);
+            self.total_staked = self.total_staked.checked_add(reward).ok_or(Error::ArithmeticOverflow)?;
+            self._reweight(&before, info);
+            self._record_claim(user, reward, Self::boosted_portion(&before, reward));
 
-            self.env().emit_event(RewardsClaimed {
-                user: caller,
-                reward_amount: reward,
+            self.env().emit_event(RewardsCompounded {
+                user,
+                amount: reward,
+                new_stake: info.amount,
             });
+            Ok(true)
+        }
 
-            Ok(())
+        /// Opt in/out of paying out settled rewards whenever you top up your stake.
+        #[ink(message)]
+        pub fn set_auto_claim_on_stake(&mut self, enabled: bool) {
+            let caller = self.env().caller();
+            if enabled {
+                self.auto_claim_on_stake.insert(caller, &true);
+            } else {
+                self.auto_claim_on_stake.remove(caller);
+            }
         }
 
-        // ═══════════════════════════════════════════════════════════════
-        // REWARD DEPOSIT — Called by Tax Manager or bridge
-        // ═══════════════════════════════════════════════════════════════
+        /// Whether a staker has opted into auto-claim on top-up.
+        #[ink(message)]
+        pub fn get_auto_claim_on_stake(&self, user: AccountId) -> bool {
+            self.auto_claim_on_stake.get(user).unwrap_or(false)
+        }
 
-        /// Deposit LUSDT rewards. Caller must have approved this contract.
-        /// Transfers LUSDT from caller to this contract and updates reward accounting.
-        fn _deposit_rewards(&mut self, amount: Balance) -> Result<(), Error> {
-            if amount == 0 {
-                return Err(Error::ZeroAmount);
+        /// Internal: best-effort payout of already-settled rewards. Frozen stakers
+        /// and transfer failures leave the rewards pending.
+        fn _auto_claim(&mut self, user: AccountId) {
+            let Some(mut info) = self.stakers.get(user) else {
+                return;
+            };
+            let pending = info.pending_rewards;
+            if pending > 0
+                && !self.is_reward_frozen(user)
+                && self.token_transfer(self.lusdt_token, user, pending).is_ok()
+            {
+                self._record_claim(user, pending, Self::boosted_portion(&info, pending));
+                info.pending_rewards = 0;
+                self.stakers.insert(user, &info);
+
+                self.env().emit_event(RewardsClaimed {
+                    user,
+                    reward_amount: pending,
+                });
             }
-            self.ensure_authorized_depositor()?;
+        }
+
+        /// Stake on behalf of several beneficiaries in one transaction (e.g. distributing
+        /// a treasury allocation). The caller funds the total; each beneficiary appears
+        /// once, with a non-zero amount that brings its position to the minimum stake.
+        /// Topping up someone else's position leaves their unstake cooldown as it was.
+        ///
+        /// @param entries (beneficiary, amount) pairs, at most 50.
+        /// @return Number of positions credited.
+        #[ink(message)]
+        pub fn stake_for_batch(&mut self, entries: Vec<(AccountId, Balance)>) -> Result<u32, Error> {
+            self.ensure_not_locked()?;
+            let result = self._stake_for_batch(entries);
+            self.unlock();
+            result
+        }
+
+        fn _stake_for_batch(&mut self, entries: Vec<(AccountId, Balance)>) -> Result<u32, Error> {
+            self.ensure_not_paused()?;
+            if entries.is_empty() || entries.len() > MAX_BATCH_SIZE {
+                return Err(Error::InvalidBatchSize);
+            }
+
+            // Validate every entry before moving any funds
+            let mut total: Balance = 0;
+            for (i, (beneficiary, amount)) in entries.iter().enumerate() {
+                if entries[..i].iter().any(|(seen, _)| seen == beneficiary) {
+                    return Err(Error::DuplicateBeneficiary);
+                }
+                if *amount == 0 {
+                    return Err(Error::ZeroAmount);
+                }
+                self.ensure_meets_minimum(*beneficiary, *amount)?;
+                total = total.checked_add(*amount).ok_or(Error::ArithmeticOverflow)?;
+            }
+            self.total_staked.checked_add(total).ok_or(Error::ArithmeticOverflow)?;
 
             let caller = self.env().caller();
+            self.token_transfer_from(self.lunes_token, caller, total)
+                .map_err(|_| Error::LunesTransferFailed)?;
 
-            // Transfer LUSDT from caller to this contract
-            let mut lusdt: ink::contract_ref!(PSP22) = self.lusdt_token.into();
-            lusdt
-                .transfer_from(caller, self.env().account_id(), amount)
-                .map_err(|_| Error::LusdtTransferFailed)?;
+            for (beneficiary, amount) in entries.iter() {
+                self._credit_stake(*beneficiary, *amount)?;
+            }
 
-            // Update reward accounting
-            self._distribute_new_rewards(amount, caller)?;
+            Ok(entries.len() as u32)
+        }
 
-            Ok(())
+        /// Settle accrued rewards into `pending_rewards` for up to 50 stakers without paying
+        /// anything out, e.g. so a keeper can refresh accounting before a snapshot. Anyone can
+        /// call; addresses without a position are skipped.
+        ///
+        /// @return Number of positions settled.
+        #[ink(message)]
+        pub fn poke(&mut self, users: Vec<AccountId>) -> Result<u32, Error> {
+            self.ensure_not_locked()?;
+            let result = self._poke(users);
+            self.unlock();
+            result
         }
 
-        /// Notify contract about LUSDT rewards that were transferred directly
-        /// (e.g. Tax Manager sends via PSP22::transfer to this contract address).
-        /// Only callable by owner or authorized depositor.
-        fn _notify_reward_amount(&mut self, amount: Balance) -> Result<(), Error> {
-            if amount == 0 {
-                return Err(Error::ZeroAmount);
+        fn _poke(&mut self, users: Vec<AccountId>) -> Result<u32, Error> {
+            if users.is_empty() || users.len() > MAX_BATCH_SIZE {
+                return Err(Error::InvalidBatchSize);
             }
-            self.ensure_authorized_depositor()?;
-
-            let caller = self.env().caller();
-            self._distribute_new_rewards(amount, caller)?;
+            let mut settled: u32 = 0;
+            for user in users {
+                let Some(mut info) = self.stakers.get(user) else {
+                    continue;
+                };
+                self._settle_reward(&user, &mut info)?;
+                self.stakers.insert(user, &info);
+                settled += 1;
+            }
+            Ok(settled)
+        }
 
-            Ok(())
+        /// Unstake ALL staked LUNES. Settles pending rewards automatically.
+        /// Subject to cooldown period if configured.
+        #[ink(message)]
+        pub fn unstake(&mut self) -> Result<(), Error> {
+            self.ensure_not_locked()?;
+            let result = self._unstake(self.env().caller());
+            self.unlock();
+            result
         }
 
-        /// Internal: update reward-per-token accumulator with new rewards.
-        fn _distribute_new_rewards(&mut self, amount: Balance, depositor: AccountId) -> Result<(), Error> {
-            if self.total_staked > 0 {
-                let reward_increment = amount
-                    .checked_mul(PRECISION)
-                    .and_then(|v| v.checked_div(self.total_staked))
-                    .ok_or(Error::ArithmeticOverflow)?;
+        /// Internal: full unstake body, shared with `unstake_amount`.
+        fn _unstake(&mut self, caller: AccountId) -> Result<(), Error> {
+            let mut info = self.stakers.get(caller).ok_or(Error::NoActiveStake)?;
 
-                self.reward_per_token_stored = self
-                    .reward_per_token_stored
-                    .checked_add(reward_increment)
-                    .ok_or(Error::ArithmeticOverflow)?;
+            if info.amount == 0 {
+                return Err(Error::NoActiveStake);
+            }
+            if self.env().block_timestamp() < info.lock_until {
+                return Err(Error::StakeLocked);
             }
-            // If no stakers, rewards accumulate in the contract balance
-            // and will be distributed when the first staker stakes
 
-            self.total_rewards_deposited = self
-                .total_rewards_deposited
-                .checked_add(amount)
-                .ok_or(Error::ArithmeticOverflow)?;
+            // Check cooldown
+            if self.unstake_cooldown_ms > 0 {
+                let elapsed = self.env().block_timestamp().saturating_sub(info.last_stake_at);
+                if elapsed < self.unstake_cooldown_ms {
+                    return Err(Error::CooldownNotElapsed);
+                }
+            }
+            let held = self.env().block_timestamp().saturating_sub(info.staked_at);
+            if held < self.min_stake_duration_ms {
+                return Err(Error::MinDurationNotMet);
+            }
 
-            self.env().emit_event(RewardsDeposited {
-                depositor,
-                amount,
-                new_reward_per_token: self.reward_per_token_stored,
-            });
+            // Settle pending rewards
+            self._settle_reward(&caller, &mut info)?;
+            self._maybe_compound(caller, &mut info)?;
 
-            Ok(())
-        }
+            let unstake_amount = info.amount;
 
-        /// Internal: settle pending rewards for a staker.
-        fn _update_reward(&self, _user: &AccountId, info: &mut StakerInfo) -> Result<(), Error> {
-            if info.amount > 0 {
-                let reward_delta = self
-                    .reward_per_token_stored
-                    .checked_sub(info.reward_per_token_paid)
-                    .ok_or(Error::ArithmeticOverflow)?;
+            // Update state before any transfer
+            self._accrue_total_stake_time();
+            self.total_staked = self.total_staked.saturating_sub(unstake_amount);
+            let before = info.clone();
+            self._checkpoint_stake(caller, before.amount);
+            info.amount = 0;
+            info.staked_at = 0;
+            info.last_stake_at = 0;
+            info.lock_tier = 0;
+            info.lock_until = 0;
+            self._reweight(&before, &info);
+            self.staker_count = self.staker_count.saturating_sub(1);
+            self._unindex_staker(caller);
 
-                let earned = info
-                    .amount
-                    .checked_mul(reward_delta)
-                    .and_then(|v| v.checked_div(PRECISION))
-                    .ok_or(Error::ArithmeticOverflow)?;
+            // Auto-claim pending rewards if any (frozen rewards stay pending);
+            // a failed payout is escrowed for `withdraw_escrow`
+            let pending = info.pending_rewards;
+            if pending > 0 && !self.is_reward_frozen(caller) {
+                info.pending_rewards = 0;
+                if self.token_transfer(self.lusdt_token, caller, pending).is_ok() {
+                    self._record_claim(caller, pending, Self::boosted_portion(&before, pending));
 
-                info.pending_rewards = info
-                    .pending_rewards
-                    .checked_add(earned)
-                    .ok_or(Error::ArithmeticOverflow)?;
+                    self.env().emit_event(RewardsClaimed {
+                        user: caller,
+                        reward_amount: pending,
+                    });
+                } else {
+                    self._escrow_reward(caller, pending)?;
+                }
             }
-            info.reward_per_token_paid = self.reward_per_token_stored;
-            Ok(())
-        }
 
-        // ═══════════════════════════════════════════════════════════════
-        // READ-ONLY QUERIES
-        // ═══════════════════════════════════════════════════════════════
+            self.stakers.insert(caller, &info);
 
-        /// Get staker information for a given address.
-        #[ink(message)]
-        pub fn get_staker_info(&self, user: AccountId) -> StakerInfo {
-            self.stakers.get(user).unwrap_or_default()
+            // Transfer LUNES back to user
+            self.token_transfer(self.lunes_token, caller, unstake_amount)
+                .map_err(|_| Error::LunesTransferFailed)?;
+
+            self.env().emit_event(Unstaked {
+                user: caller,
+                amount: unstake_amount,
+                total_staked: self.total_staked,
+            });
+
+            Ok(())
         }
 
-        /// Get pending (unclaimed) LUSDT rewards for a user.
-        /// Includes both settled and unsettled rewards.
+        /// Unstake part of the staked LUNES. Settles pending rewards, which stay
+        /// claimable. The remainder must be at least `min_stake`; unstaking the
+        /// whole position behaves exactly like `unstake`.
         #[ink(message)]
-        pub fn get_pending_rewards(&self, user: AccountId) -> Balance {
-            let info = self.stakers.get(user).unwrap_or_default();
-            if info.amount == 0 {
-                return info.pending_rewards;
-            }
+        pub fn unstake_amount(&mut self, amount: Balance) -> Result<(), Error> {
+            self.ensure_not_locked()?;
 
-            let reward_delta = self
-                .reward_per_token_stored
-                .saturating_sub(info.reward_per_token_paid);
+            let result = (|| {
+                if amount == 0 {
+                    return Err(Error::ZeroAmount);
+                }
+                let caller = self.env().caller();
+                let mut info = self.stakers.get(caller).ok_or(Error::NoActiveStake)?;
 
-            let unsettled = info
-                .amount
-                .saturating_mul(reward_delta)
-                / PRECISION;
+                if info.amount == 0 {
+                    return Err(Error::NoActiveStake);
+                }
+                if self.env().block_timestamp() < info.lock_until {
+                    return Err(Error::StakeLocked);
+                }
+                if amount > info.amount {
+                    return Err(Error::InsufficientStake);
+                }
+                if amount == info.amount {
+                    return self._unstake(caller);
+                }
 
-            info.pending_rewards.saturating_add(unsettled)
-        }
+                // Check cooldown
+                if self.unstake_cooldown_ms > 0 {
+                    let elapsed = self.env().block_timestamp().saturating_sub(info.last_stake_at);
+                    if elapsed < self.unstake_cooldown_ms {
+                        return Err(Error::CooldownNotElapsed);
+                    }
+                }
+                let held = self.env().block_timestamp().saturating_sub(info.staked_at);
+                if held < self.min_stake_duration_ms {
+                    return Err(Error::MinDurationNotMet);
+                }
 
-        /// Total LUNES staked across all users.
-        #[ink(message)]
-        pub fn get_total_staked(&self) -> Balance {
-            self.total_staked
+                // Settle pending rewards at the old stake size
+                self._settle_reward(&caller, &mut info)?;
+                self._maybe_compound(caller, &mut info)?;
+                self._claim_over_cap(caller, &mut info)?;
+
+                // The position keeps its lock tier, so it must stay above that tier's minimum
+                if info.amount - amount < self.get_tier_min_stake(info.lock_tier) {
+                    return Err(Error::BelowMinimumStake);
+                }
+
+                self._accrue_total_stake_time();
+                self.total_staked = self.total_staked.saturating_sub(amount);
+                let before = info.clone();
+                self._checkpoint_stake(caller, before.amount);
+                info.amount -= amount;
+                self._reweight(&before, &info);
+                self.stakers.insert(caller, &info);
+
+                self.token_transfer(self.lunes_token, caller, amount)
+                    .map_err(|_| Error::LunesTransferFailed)?;
+
+                self.env().emit_event(Unstaked {
+                    user: caller,
+                    amount,
+                    total_staked: self.total_staked,
+                });
+
+                Ok(())
+            })();
+
+            self.unlock();
+            result
         }
 
-        /// Number of active stakers.
+        /// Unstake ALL staked LUNES immediately, ignoring the cooldown and any lock, but not
+        /// `min_stake_duration_ms`. Pending rewards are forfeited and redistributed to the
+        /// remaining stakers.
         #[ink(message)]
-        pub fn get_staker_count(&self) -> u32 {
-            self.staker_count
+        pub fn emergency_unstake(&mut self) -> Result<(), Error> {
+            self.ensure_not_locked()?;
+
+            let result = (|| {
+                let caller = self.env().caller();
+                let mut info = self.stakers.get(caller).ok_or(Error::NoActiveStake)?;
+
+                if info.amount == 0 {
+                    return Err(Error::NoActiveStake);
+                }
+                let held = self.env().block_timestamp().saturating_sub(info.staked_at);
+                if held < self.min_stake_duration_ms {
+                    return Err(Error::MinDurationNotMet);
+                }
+
+                self._settle_reward(&caller, &mut info)?;
+                let amount = info.amount;
+
+                self._accrue_total_stake_time();
+                self.total_staked = self.total_staked.saturating_sub(amount);
+                self.staker_count = self.staker_count.saturating_sub(1);
+                self._unindex_staker(caller);
+
+                let forfeited = info.pending_rewards;
+                let before = info.clone();
+                self._checkpoint_stake(caller, before.amount);
+                info.amount = 0;
+                info.staked_at = 0;
+                info.last_stake_at = 0;
+                info.lock_tier = 0;
+                info.lock_until = 0;
+                info.pending_rewards = 0;
+                self._reweight(&before, &info);
+                self.stakers.insert(caller, &info);
+
+                self._redistribute_forfeited(forfeited)?;
+
+                self.token_transfer(self.lunes_token, caller, amount)
+                    .map_err(|_| Error::LunesTransferFailed)?;
+
+                self.env().emit_event(EmergencyUnstaked {
+                    user: caller,
+                    amount,
+                    forfeited_rewards: forfeited,
+                    total_staked: self.total_staked,
+                });
+
+                Ok(())
+            })();
+
+            self.unlock();
+            result
         }
 
-        /// Total LUSDT rewards ever deposited into the pool.
+        /// Claim all accumulated LUSDT rewards without unstaking. Also works after a full
+        /// unstake that left rewards pending (e.g. frozen at the time): the record keeps them.
         #[ink(message)]
-        pub fn get_total_rewards_deposited(&self) -> Balance {
-            self.total_rewards_deposited
+        pub fn claim_rewards(&mut self) -> Result<(), Error> {
+            self.ensure_not_locked()?;
+            let caller = self.env().caller();
+            let result = self._claim_rewards(caller).map(|_| ());
+            self.unlock();
+            result
         }
 
-        /// Total LUSDT rewards ever claimed by stakers.
+        /// Claim exactly `amount` of the accumulated LUSDT rewards, leaving the rest pending
+        /// (e.g. for tax-lot accounting). Never auto-compounds.
         #[ink(message)]
-        pub fn get_total_rewards_claimed(&self) -> Balance {
-            self.total_rewards_claimed
+        pub fn claim_rewards_amount(&mut self, amount: Balance) -> Result<(), Error> {
+            if amount == 0 {
+                return Err(Error::ZeroAmount);
+            }
+            self.ensure_not_locked()?;
+            let caller = self.env().caller();
+            let result = self._claim_rewards_to(caller, caller, Some(amount)).map(|_| ());
+            self.unlock();
+            result
         }
 
-        /// Current reward per token stored (scaled by PRECISION).
+        /// Claim LUSDT rewards and, if an `auto_compound_target` router is configured,
+        /// swap them into LUNES sent to the caller, ready to be staked. The swap is bound
+        /// by `set_swap_slippage` like buybacks. Without a target this is `claim_rewards`.
         #[ink(message)]
-        pub fn get_reward_per_token(&self) -> u128 {
-            self.reward_per_token_stored
+        pub fn claim_and_notify(&mut self) -> Result<(), Error> {
+            self.ensure_not_locked()?;
+
+            let result = (|| {
+                let caller = self.env().caller();
+                let Some(target) = self.auto_compound_target else {
+                    return self._claim_rewards(caller).map(|_| ());
+                };
+
+                let lusdt_amount = self._claim_rewards_to(caller, target, None)?;
+                if lusdt_amount == 0 {
+                    return Ok(());
+                }
+                let min_out = self.swap_min_out(lusdt_amount)?;
+                let lunes_amount = self
+                    .router_swap(target, lusdt_amount, min_out, caller)
+                    .map_err(|_| Error::CompoundSwapFailed)?;
+                if lunes_amount < min_out {
+                    return Err(Error::SlippageExceeded);
+                }
+
+                self.env().emit_event(RewardsForwarded {
+                    user: caller,
+                    target,
+                    lusdt_amount,
+                    lunes_amount,
+                });
+                Ok(())
+            })();
+
+            self.unlock();
+            result
         }
 
-        /// Minimum LUNES required to stake.
+        /// Claim like `claim_rewards`, then call `on_rewards_claimed(amount, data)` on the
+        /// caller if it is a contract (e.g. a strategy that reinvests). Callback failures
+        /// are ignored, so the claim always stands.
         #[ink(message)]
-        pub fn get_min_stake(&self) -> Balance {
-            self.min_stake
+        pub fn claim_rewards_with_callback(&mut self, data: Vec<u8>) -> Result<(), Error> {
+            self.ensure_not_locked()?;
+            let caller = self.env().caller();
+            let claimed = self._claim_rewards(caller);
+            // Released before the callback so a strategy can restake from it
+            self.unlock();
+            let amount = claimed?;
+            if amount > 0 {
+                let _ = self.notify_claim_callback(caller, amount, data);
+            }
+            Ok(())
         }
 
-        /// Whether the contract is paused.
-        #[ink(message)]
-        pub fn is_paused(&self) -> bool {
-            self.paused
+        /// Internal: claim body. Returns the amount paid out (0 when auto-compounded).
+        fn _claim_rewards(&mut self, caller: AccountId) -> Result<Balance, Error> {
+            self._claim_rewards_to(caller, caller, None)
         }
 
-        /// Contract owner address.
-        #[ink(message)]
-        pub fn get_owner(&self) -> AccountId {
-            self.owner
+        /// Internal: claim `caller`'s rewards, paying the LUSDT to `recipient`. `requested`
+        /// limits the claim to that amount (no auto-compounding); `None` claims everything.
+        fn _claim_rewards_to(
+            &mut self,
+            caller: AccountId,
+            recipient: AccountId,
+            requested: Option<Balance>,
+        ) -> Result<Balance, Error> {
+            let mut info = self.stakers.get(caller).ok_or(Error::NoActiveStake)?;
+
+            if self.is_reward_frozen(caller) {
+                return Err(Error::RewardsFrozen);
+            }
+            let (claims, window_start) = self.claims_in_window(caller);
+            if self.max_claims_per_window > 0 && claims >= self.max_claims_per_window {
+                return Err(Error::ClaimFrequencyExceeded);
+            }
+
+            // Update reward accounting
+            self._settle_reward(&caller, &mut info)?;
+            if requested.is_none() && self._maybe_compound(caller, &mut info)? {
+                // Auto-compounders "claim" by restaking
+                self.stakers.insert(caller, &info);
+                return Ok(0);
+            }
+
+            let reward = requested.unwrap_or(info.pending_rewards);
+            if reward == 0 || reward > info.pending_rewards {
+                return Err(Error::NoRewardsToClaim);
+            }
+
+            // Surface accounting drift precisely instead of an opaque transfer failure
+            if self.token_balance_of(self.lusdt_token, self.env().account_id()) < reward {
+                return Err(Error::InsufficientRewardBalance);
+            }
+
+            // Update state before the transfer
+            self._record_claim(caller, reward, Self::boosted_portion(&info, reward));
+            info.pending_rewards -= reward;
+            self.stakers.insert(caller, &info);
+            if self.max_claims_per_window > 0 {
+                self.claim_counts.insert(caller, &(claims.saturating_add(1), window_start));
+            }
+
+            // Transfer LUSDT rewards to user
+            self.token_transfer(self.lusdt_token, recipient, reward)
+                .map_err(|_| Error::LusdtTransferFailed)?;
+
+            self.env().emit_event(RewardsClaimed {
+                user: caller,
+                reward_amount: reward,
+            });
+
+            Ok(reward)
         }
 
-        /// Authorized depositor address (typically Tax Manager).
+        /// Withdraw LUSDT rewards whose automatic payout failed and was escrowed.
         #[ink(message)]
-        pub fn get_authorized_depositor(&self) -> Option<AccountId> {
-            self.authorized_depositor
+        pub fn withdraw_escrow(&mut self) -> Result<(), Error> {
+            self.ensure_not_locked()?;
+
+            let result = (|| {
+                let caller = self.env().caller();
+                let amount = self.unclaimed_escrow.get(caller).unwrap_or(0);
+                if amount == 0 {
+                    return Err(Error::NoRewardsToClaim);
+                }
+
+                self.unclaimed_escrow.remove(caller);
+                self.total_escrowed = self.total_escrowed.saturating_sub(amount);
+                // Escrow doesn't keep the tier it was earned at; counted as base
+                self._record_claim(caller, amount, 0);
+
+                self.token_transfer(self.lusdt_token, caller, amount)
+                    .map_err(|_| Error::LusdtTransferFailed)?;
+
+                self.env().emit_event(RewardsClaimed {
+                    user: caller,
+                    reward_amount: amount,
+                });
+                Ok(())
+            })();
+
+            self.unlock();
+            result
         }
 
-        /// Unstake cooldown period in milliseconds.
+        /// Deposit LUNES rewards for current stakers, split by effective stake. Caller must
+        /// have approved this contract. Authorized like `deposit_rewards`; fails with
+        /// `NoActiveStake` while nothing is staked.
+        #[ink(message)]
+        pub fn deposit_lunes_rewards(&mut self, amount: Balance) -> Result<(), Error> {
+            self.ensure_not_locked()?;
+
+            let result = (|| {
+                if amount == 0 {
+                    return Err(Error::ZeroAmount);
+                }
+                self.ensure_authorized_depositor()?;
+                self.ensure_reward_deposits_open()?;
+                if self.total_effective_staked == 0 {
+                    return Err(Error::NoActiveStake);
+                }
+
+                let caller = self.env().caller();
+                self.token_transfer_from(self.lunes_token, caller, amount)
+                    .map_err(|_| Error::LunesTransferFailed)?;
+                self._distribute_lunes_rewards(amount, caller)
+            })();
+
+            self.unlock();
+            result
+        }
+
+        /// Internal: credit LUNES rewards already held by this contract to current
+        /// stakers by effective stake. Fails with `NoActiveStake` while nothing is staked.
+        fn _distribute_lunes_rewards(&mut self, amount: Balance, depositor: AccountId) -> Result<(), Error> {
+            if self.total_effective_staked == 0 {
+                return Err(Error::NoActiveStake);
+            }
+            let increment = amount
+                .checked_mul(PRECISION)
+                .ok_or(Error::ArithmeticOverflow)?
+                / self.total_effective_staked;
+            self.reward_per_token_stored_lunes = self
+                .reward_per_token_stored_lunes
+                .checked_add(increment)
+                .ok_or(Error::ArithmeticOverflow)?;
+            self.total_lunes_rewards_deposited = self
+                .total_lunes_rewards_deposited
+                .checked_add(amount)
+                .ok_or(Error::ArithmeticOverflow)?;
+
+            self.env().emit_event(LunesRewardsDeposited {
+                depositor,
+                amount,
+                new_reward_per_token: self.reward_per_token_stored_lunes,
+            });
+            Ok(())
+        }
+
+        /// Claim accumulated LUNES rewards. Independent of the LUSDT claim.
+        #[ink(message)]
+        pub fn claim_lunes_rewards(&mut self) -> Result<(), Error> {
+            self.ensure_not_locked()?;
+
+            let result = (|| {
+                let caller = self.env().caller();
+                let mut info = self.stakers.get(caller).ok_or(Error::NoActiveStake)?;
+                if self.is_reward_frozen(caller) {
+                    return Err(Error::RewardsFrozen);
+                }
+
+                self._settle_reward(&caller, &mut info)?;
+                let reward = info.pending_rewards_lunes;
+                if reward == 0 {
+                    return Err(Error::NoRewardsToClaim);
+                }
+
+                info.pending_rewards_lunes = 0;
+                self.stakers.insert(caller, &info);
+                self.total_lunes_rewards_claimed = self.total_lunes_rewards_claimed.saturating_add(reward);
+
+                self.token_transfer(self.lunes_token, caller, reward)
+                    .map_err(|_| Error::LunesTransferFailed)?;
+
+                self.env().emit_event(LunesRewardsClaimed {
+                    user: caller,
+                    amount: reward,
+                });
+                Ok(())
+            })();
+
+            self.unlock();
+            result
+        }
+
+        /// Internal: hold a reward whose payout failed until the user withdraws it.
+        fn _escrow_reward(&mut self, user: AccountId, amount: Balance) -> Result<(), Error> {
+            let escrowed = self
+                .unclaimed_escrow
+                .get(user)
+                .unwrap_or(0)
+                .checked_add(amount)
+                .ok_or(Error::ArithmeticOverflow)?;
+            self.unclaimed_escrow.insert(user, &escrowed);
+            self.total_escrowed = self.total_escrowed.checked_add(amount).ok_or(Error::ArithmeticOverflow)?;
+            self.env().emit_event(RewardsEscrowed { user, amount });
+            Ok(())
+        }
+
+        /// Internal: pay out settled rewards once they exceed `max_pending_rewards`.
+        /// Skipped for frozen stakers and when the reward balance can't cover it,
+        /// so the cap never blocks the stake/unstake that triggered it.
+        fn _claim_over_cap(&mut self, user: AccountId, info: &mut StakerInfo) -> Result<(), Error> {
+            let reward = info.pending_rewards;
+            if self.max_pending_rewards == 0 || reward <= self.max_pending_rewards || self.is_reward_frozen(user) {
+                return Ok(());
+            }
+            if self.token_balance_of(self.lusdt_token, self.env().account_id()) < reward {
+                return Ok(());
+            }
+            self.token_transfer(self.lusdt_token, user, reward)
+                .map_err(|_| Error::LusdtTransferFailed)?;
+            self._record_claim(user, reward, Self::boosted_portion(info, reward));
+            info.pending_rewards = 0;
+            self.env().emit_event(RewardsClaimed {
+                user,
+                reward_amount: reward,
+            });
+            Ok(())
+        }
+
+        /// Internal: `(claims, window start)` for `user`'s current claim window.
+        fn claims_in_window(&self, user: AccountId) -> (u32, Timestamp) {
+            let now = self.env().block_timestamp();
+            match self.claim_counts.get(user) {
+                Some((claims, start)) if now.saturating_sub(start) < self.claim_window_ms => (claims, start),
+                _ => (0, now),
+            }
+        }
+
+        /// Recount main-pool positions with a non-zero stake from the staker index, `limit`
+        /// (max `MAX_STAKER_PAGE`) entries from `start`. Start at 0 and continue from where the
+        /// previous page ended; the page reaching the end of the index corrects `staker_count`
+        /// if it drifted. Exits between pages reorder the index, so run all pages together.
+        /// Only owner. Returns the stakers counted so far.
+        #[ink(message)]
+        pub fn recompute_staker_count(&mut self, start: u32, limit: u32) -> Result<u32, Error> {
+            self.ensure_owner()?;
+            if limit == 0 {
+                return Err(Error::InvalidBatchSize);
+            }
+            let (next, counted) = if start == 0 { (0, 0) } else { self.staker_recount };
+            if start != next {
+                return Err(Error::InvalidConfig);
+            }
+
+            let end = start
+                .saturating_add(limit.min(MAX_STAKER_PAGE))
+                .min(self.staker_index_len);
+            let counted = counted.saturating_add(
+                (start..end)
+                    .filter_map(|i| self.staker_index.get(i))
+                    .filter(|user| self.stakers.get(user).is_some_and(|info| info.amount > 0))
+                    .count() as u32,
+            );
+            if end < self.staker_index_len {
+                self.staker_recount = (end, counted);
+                return Ok(counted);
+            }
+
+            self.staker_recount = (0, 0);
+            if counted != self.staker_count {
+                self.env().emit_event(StakerCountReconciled {
+                    previous: self.staker_count,
+                    actual: counted,
+                });
+                self.staker_count = counted;
+            }
+            Ok(counted)
+        }
+
+        /// Clamp a staker's reward snapshots to the current accumulators. A snapshot ahead
+        /// of its accumulator makes every settlement overflow, locking the staker out of
+        /// stake/unstake/claim; nothing is owed for an inverted snapshot, so clamping
+        /// forfeits nothing. No-op for healthy positions. Only owner.
+        #[ink(message)]
+        pub fn repair_staker(&mut self, user: AccountId) -> Result<(), Error> {
+            self.ensure_owner()?;
+            let mut info = self.stakers.get(user).ok_or(Error::NoActiveStake)?;
+            self._accrue_stream();
+
+            let previous_paid = info.reward_per_token_paid;
+            let inverted = previous_paid > self.reward_per_token_stored
+                || info.locked_reward_per_token_paid > self.locked_reward_per_token_stored
+                || info.reward_per_token_paid_lunes > self.reward_per_token_stored_lunes;
+            if !inverted {
+                return Ok(());
+            }
+            info.reward_per_token_paid = previous_paid.min(self.reward_per_token_stored);
+            info.locked_reward_per_token_paid = info
+                .locked_reward_per_token_paid
+                .min(self.locked_reward_per_token_stored);
+            info.reward_per_token_paid_lunes = info
+                .reward_per_token_paid_lunes
+                .min(self.reward_per_token_stored_lunes);
+            self.stakers.insert(user, &info);
+
+            self.env().emit_event(StakerRepaired {
+                user,
+                previous_paid,
+                repaired_paid: info.reward_per_token_paid,
+            });
+            Ok(())
+        }
+
+        /// Redistribute main-pool rewards stranded by rounding (and freeze forfeits)
+        /// to current stakers by bumping `reward_per_token_stored`. Callable by anyone,
+        /// e.g. a keeper. Returns the amount redistributed (0 if there is nothing to move yet).
+        #[ink(message)]
+        pub fn reconcile_rewards(&mut self) -> Result<Balance, Error> {
+            self._accrue_stream();
+            let flexible_staked = self.flexible_effective_staked();
+            if flexible_staked == 0 || self.reward_dust < PRECISION {
+                return Ok(0);
+            }
+
+            let whole = self.reward_dust / PRECISION;
+            let reward_increment = whole
+                .checked_mul(PRECISION)
+                .ok_or(Error::ArithmeticOverflow)?
+                / flexible_staked;
+            if reward_increment == 0 {
+                return Ok(0);
+            }
+            let distributed_scaled = reward_increment
+                .checked_mul(flexible_staked)
+                .ok_or(Error::ArithmeticOverflow)?;
+
+            self.reward_per_token_stored = self
+                .reward_per_token_stored
+                .checked_add(reward_increment)
+                .ok_or(Error::ArithmeticOverflow)?;
+            self.reward_dust = self.reward_dust.saturating_sub(distributed_scaled);
+
+            let amount = distributed_scaled / PRECISION;
+            self.env().emit_event(RewardsReconciled {
+                amount,
+                new_reward_per_token: self.reward_per_token_stored,
+            });
+            Ok(amount)
+        }
+
+        /// Opt in/out of reward-deposit notifications. Stored on-chain and
+        /// emitted as `StakerPreferenceSet` for off-chain notifier services.
+        #[ink(message)]
+        pub fn set_notification_pref(&mut self, enabled: bool) {
+            let caller = self.env().caller();
+            if enabled {
+                self.notification_prefs.insert(caller, &true);
+            } else {
+                self.notification_prefs.remove(caller);
+            }
+            self.env().emit_event(StakerPreferenceSet {
+                user: caller,
+                notify_on_deposit: enabled,
+            });
+        }
+
+        // ═══════════════════════════════════════════════════════════════
+        // REWARD POOLS — Independent programs stakers opt into
+        // ═══════════════════════════════════════════════════════════════
+
+        /// Stake LUNES into a reward pool. Pool 0 is the main pool (same as `stake`);
+        /// other pools have their own stake total and only earn their own deposits.
+        ///
+        /// @param pool_id Target pool.
+        /// @param amount Amount of LUNES to stake (in smallest unit).
+        #[ink(message)]
+        pub fn stake_into_pool(&mut self, pool_id: u32, amount: Balance) -> Result<(), Error> {
+            if pool_id == 0 {
+                return self.stake(amount);
+            }
+            self.ensure_not_locked()?;
+            let result = self._stake_into_pool(pool_id, amount);
+            self.unlock();
+            result
+        }
+
+        fn _stake_into_pool(&mut self, pool_id: u32, amount: Balance) -> Result<(), Error> {
+            self.ensure_not_paused()?;
+            let mut pool = self.reward_pools.get(pool_id).ok_or(Error::PoolNotFound)?;
+
+            let caller = self.env().caller();
+            let mut position = self.pool_positions.get((pool_id, caller)).unwrap_or_default();
+            let new_amount = position.amount.checked_add(amount).ok_or(Error::ArithmeticOverflow)?;
+            if new_amount < self.min_stake {
+                return Err(Error::BelowMinimumStake);
+            }
+
+            self.token_transfer_from(self.lunes_token, caller, amount)
+                .map_err(|_| Error::LunesTransferFailed)?;
+
+            self._update_pool_reward(caller, &pool, &mut position)?;
+            if position.amount == 0 {
+                position.staked_at = self.env().block_timestamp();
+            }
+            position.amount = new_amount;
+            pool.total_staked = pool.total_staked.checked_add(amount).ok_or(Error::ArithmeticOverflow)?;
+
+            self.pool_positions.insert((pool_id, caller), &position);
+            self.reward_pools.insert(pool_id, &pool);
+
+            self.env().emit_event(PoolStaked {
+                user: caller,
+                pool_id,
+                amount,
+                pool_total_staked: pool.total_staked,
+            });
+            Ok(())
+        }
+
+        /// Unstake the whole position from a reward pool, paying out its rewards.
+        /// Pool 0 is the main pool (same as `unstake`). Subject to the cooldown and `min_stake_duration_ms`.
+        #[ink(message)]
+        pub fn unstake_from_pool(&mut self, pool_id: u32) -> Result<(), Error> {
+            if pool_id == 0 {
+                return self.unstake();
+            }
+            self.ensure_not_locked()?;
+            let result = self._unstake_from_pool(pool_id);
+            self.unlock();
+            result
+        }
+
+        fn _unstake_from_pool(&mut self, pool_id: u32) -> Result<(), Error> {
+            let mut pool = self.reward_pools.get(pool_id).ok_or(Error::PoolNotFound)?;
+            let caller = self.env().caller();
+            let mut position = self.pool_positions.get((pool_id, caller)).unwrap_or_default();
+            if position.amount == 0 {
+                return Err(Error::NoActiveStake);
+            }
+
+            if self.unstake_cooldown_ms > 0 {
+                let elapsed = self.env().block_timestamp().saturating_sub(position.staked_at);
+                if elapsed < self.unstake_cooldown_ms {
+                    return Err(Error::CooldownNotElapsed);
+                }
+            }
+            let held = self.env().block_timestamp().saturating_sub(position.staked_at);
+            if held < self.min_stake_duration_ms {
+                return Err(Error::MinDurationNotMet);
+            }
+
+            self._update_pool_reward(caller, &pool, &mut position)?;
+            let unstake_amount = position.amount;
+
+            // Update state before any transfer
+            pool.total_staked = pool.total_staked.saturating_sub(unstake_amount);
+            position.amount = 0;
+            position.staked_at = 0;
+            let pending = position.pending_rewards;
+            let pay_rewards = pending > 0 && !self.is_reward_frozen(caller);
+            if pay_rewards {
+                position.pending_rewards = 0;
+            }
+            self.pool_positions.insert((pool_id, caller), &position);
+            self.reward_pools.insert(pool_id, &pool);
+
+            // Pay out pool rewards if any (frozen rewards stay pending);
+            // a failed payout is escrowed for `withdraw_escrow`
+            if pay_rewards {
+                if self.token_transfer(self.lusdt_token, caller, pending).is_ok() {
+                    // Additional pools have no lock tiers, so nothing is boosted
+                    self._record_claim(caller, pending, 0);
+
+                    self.env().emit_event(RewardsClaimed {
+                        user: caller,
+                        reward_amount: pending,
+                    });
+                } else {
+                    self._escrow_reward(caller, pending)?;
+                }
+            }
+
+            self.token_transfer(self.lunes_token, caller, unstake_amount)
+                .map_err(|_| Error::LunesTransferFailed)?;
+
+            self.env().emit_event(PoolUnstaked {
+                user: caller,
+                pool_id,
+                amount: unstake_amount,
+                pool_total_staked: pool.total_staked,
+            });
+            Ok(())
+        }
+
+        /// Claim the LUSDT rewards earned in a reward pool. Pool 0 is the main pool
+        /// (same as `claim_rewards`).
+        #[ink(message)]
+        pub fn claim_pool_rewards(&mut self, pool_id: u32) -> Result<(), Error> {
+            if pool_id == 0 {
+                return self.claim_rewards();
+            }
+            self.ensure_not_locked()?;
+            let result = self._claim_pool_rewards(pool_id);
+            self.unlock();
+            result
+        }
+
+        fn _claim_pool_rewards(&mut self, pool_id: u32) -> Result<(), Error> {
+            let pool = self.reward_pools.get(pool_id).ok_or(Error::PoolNotFound)?;
+            let caller = self.env().caller();
+            let mut position = self.pool_positions.get((pool_id, caller)).ok_or(Error::NoActiveStake)?;
+
+            if self.is_reward_frozen(caller) {
+                return Err(Error::RewardsFrozen);
+            }
+
+            self._update_pool_reward(caller, &pool, &mut position)?;
+            let reward = position.pending_rewards;
+            if reward == 0 {
+                return Err(Error::NoRewardsToClaim);
+            }
+            if self.token_balance_of(self.lusdt_token, self.env().account_id()) < reward {
+                return Err(Error::InsufficientRewardBalance);
+            }
+
+            // Update state before the transfer (additional pools have no boost)
+            self._record_claim(caller, reward, 0);
+            position.pending_rewards = 0;
+            self.pool_positions.insert((pool_id, caller), &position);
+
+            self.token_transfer(self.lusdt_token, caller, reward)
+                .map_err(|_| Error::LusdtTransferFailed)?;
+
+            self.env().emit_event(RewardsClaimed {
+                user: caller,
+                reward_amount: reward,
+            });
+            Ok(())
+        }
+
+        /// Deposit LUSDT rewards into a specific pool. Caller must have approved this
+        /// contract. Pool 0 is the main pool (same as `deposit_rewards`).
+        /// Only owner or authorized depositor.
+        #[ink(message)]
+        pub fn deposit_pool_rewards(&mut self, pool_id: u32, amount: Balance) -> Result<(), Error> {
+            self.ensure_not_locked()?;
+            let result = self._deposit_pool_rewards(pool_id, amount);
+            self.unlock();
+            result
+        }
+
+        fn _deposit_pool_rewards(&mut self, pool_id: u32, amount: Balance) -> Result<(), Error> {
+            if pool_id == 0 {
+                return self._deposit_rewards(amount);
+            }
+            if amount == 0 {
+                return Err(Error::ZeroAmount);
+            }
+            self.ensure_authorized_depositor()?;
+            self.ensure_reward_deposits_open()?;
+            let pool = self.reward_pools.get(pool_id).ok_or(Error::PoolNotFound)?;
+
+            let caller = self.env().caller();
+            self.token_transfer_from(self.lusdt_token, caller, amount)
+                .map_err(|_| Error::LusdtTransferFailed)?;
+
+            self._distribute_pool_rewards(pool_id, pool, amount, caller)
+        }
+
+        /// Notify a pool about LUSDT rewards already transferred to this contract.
+        /// Pool 0 is the main pool (same as `notify_reward_amount`).
+        /// Only owner or authorized depositor.
+        #[ink(message)]
+        pub fn notify_pool_reward_amount(&mut self, pool_id: u32, amount: Balance) -> Result<(), Error> {
+            self.ensure_not_locked()?;
+            let result = self._notify_pool_reward_amount(pool_id, amount);
+            self.unlock();
+            result
+        }
+
+        fn _notify_pool_reward_amount(&mut self, pool_id: u32, amount: Balance) -> Result<(), Error> {
+            if pool_id == 0 {
+                return self._notify_reward_amount(amount);
+            }
+            if amount == 0 {
+                return Err(Error::ZeroAmount);
+            }
+            self.ensure_authorized_depositor()?;
+            self.ensure_reward_deposits_open()?;
+            let pool = self.reward_pools.get(pool_id).ok_or(Error::PoolNotFound)?;
+
+            let caller = self.env().caller();
+            self._distribute_pool_rewards(pool_id, pool, amount, caller)
+        }
+
+        /// Distribute LUSDT sent straight to this contract without a deposit call: whatever
+        /// the contract holds beyond its LUSDT liabilities goes to the main pool as new
+        /// rewards. Returns the amount synced (0 if there was no surplus).
+        /// Only owner or authorized depositor.
+        #[ink(message)]
+        pub fn sync_rewards(&mut self) -> Result<Balance, Error> {
+            self.ensure_not_locked()?;
+            let result = (|| {
+                self.ensure_authorized_depositor()?;
+                self.ensure_reward_deposits_open()?;
+
+                let balance = self.token_balance_of(self.lusdt_token, self.env().account_id());
+                let surplus = balance.saturating_sub(self.lusdt_liabilities());
+                if surplus == 0 {
+                    return Ok(0);
+                }
+
+                let caller = self.env().caller();
+                self._distribute_new_rewards(surplus, caller)?;
+                Ok(surplus)
+            })();
+            self.unlock();
+            result
+        }
+
+        /// Internal: credit a deposit to one additional pool's accumulator. Deposits made
+        /// while the pool is empty are queued and distributed with the next deposit.
+        fn _distribute_pool_rewards(
+            &mut self,
+            pool_id: u32,
+            mut pool: RewardPool,
+            amount: Balance,
+            depositor: AccountId,
+        ) -> Result<(), Error> {
+            pool.total_deposited = pool.total_deposited.checked_add(amount).ok_or(Error::ArithmeticOverflow)?;
+
+            if pool.total_staked > 0 {
+                let distributable = amount.checked_add(pool.queued_rewards).ok_or(Error::ArithmeticOverflow)?;
+                let reward_increment = distributable
+                    .checked_mul(PRECISION)
+                    .and_then(|v| v.checked_div(pool.total_staked))
+                    .ok_or(Error::ArithmeticOverflow)?;
+                pool.reward_per_token_stored = pool
+                    .reward_per_token_stored
+                    .checked_add(reward_increment)
+                    .ok_or(Error::ArithmeticOverflow)?;
+                pool.queued_rewards = 0;
+            } else {
+                pool.queued_rewards = pool.queued_rewards.checked_add(amount).ok_or(Error::ArithmeticOverflow)?;
+            }
+
+            self.total_rewards_deposited = self
+                .total_rewards_deposited
+                .checked_add(amount)
+                .ok_or(Error::ArithmeticOverflow)?;
+            self.reward_pools.insert(pool_id, &pool);
+
+            self.env().emit_event(PoolRewardsDeposited {
+                pool_id,
+                depositor,
+                amount,
+                new_reward_per_token: pool.reward_per_token_stored,
+            });
+            Ok(())
+        }
+
+        /// Internal: settle a pool position against its pool's accumulator.
+        /// Frozen stakers only have their snapshot advanced, as in the main pool.
+        fn _update_pool_reward(&self, user: AccountId, pool: &RewardPool, position: &mut PoolPosition) -> Result<(), Error> {
+            if position.amount > 0 && !self.is_reward_frozen(user) {
+                let earned = position
+                    .amount
+                    .checked_mul(pool.reward_per_token_stored.saturating_sub(position.reward_per_token_paid))
+                    .and_then(|v| v.checked_div(PRECISION))
+                    .ok_or(Error::ArithmeticOverflow)?;
+                position.pending_rewards = position
+                    .pending_rewards
+                    .checked_add(earned)
+                    .ok_or(Error::ArithmeticOverflow)?;
+            }
+            position.reward_per_token_paid = pool.reward_per_token_stored;
+            Ok(())
+        }
+
+        // ═══════════════════════════════════════════════════════════════
+        // PRINCIPAL PROTECTION — Insurance claims after a shortfall
+        // ═══════════════════════════════════════════════════════════════
+
+        /// Fund the insurance allocation with LUSDT. Caller must have approved this
+        /// contract. Only owner or authorized depositor.
+        #[ink(message)]
+        pub fn deposit_insurance(&mut self, amount: Balance) -> Result<(), Error> {
+            if amount == 0 {
+                return Err(Error::ZeroAmount);
+            }
+            self.ensure_authorized_depositor()?;
+
+            let caller = self.env().caller();
+            self.token_transfer_from(self.lusdt_token, caller, amount)
+                .map_err(|_| Error::LusdtTransferFailed)?;
+            self.insurance_balance = self.insurance_balance.checked_add(amount).ok_or(Error::ArithmeticOverflow)?;
+            Ok(())
+        }
+
+        /// Claim your pro-rata share of the insurance payout pool for the open window.
+        /// Share = payout pool × your stake / total staked when the window opened; only
+        /// stake held when the window opened is covered. Once per round.
+        #[ink(message)]
+        pub fn file_insurance_claim(&mut self) -> Result<Balance, Error> {
+            self.ensure_not_locked()?;
+            let result = self._file_insurance_claim();
+            self.unlock();
+            result
+        }
+
+        fn _file_insurance_claim(&mut self) -> Result<Balance, Error> {
+            if !self.insurance_claim_enabled {
+                return Err(Error::InsuranceClaimsDisabled);
+            }
+            let caller = self.env().caller();
+            let round = self.insurance_round;
+            if self.insurance_claims.contains((round, caller)) {
+                return Err(Error::InsuranceAlreadyClaimed);
+            }
+
+            let covered_stake = self.voting_power_at(caller, self.insurance_snapshot_id);
+            if covered_stake == 0 {
+                return Err(Error::NoActiveStake);
+            }
+
+            let share = self
+                .insurance_payout_pool
+                .checked_mul(covered_stake)
+                .and_then(|v| v.checked_div(self.insurance_total_staked))
+                .ok_or(Error::ArithmeticOverflow)?
+                .min(self.insurance_balance);
+            if share == 0 {
+                return Err(Error::NoRewardsToClaim);
+            }
+
+            self.insurance_balance = self.insurance_balance.saturating_sub(share);
+            self.insurance_claims.insert((round, caller), &share);
+            self.token_transfer(self.lusdt_token, caller, share)
+                .map_err(|_| Error::LusdtTransferFailed)?;
+
+            self.env().emit_event(InsuranceClaimPaid {
+                user: caller,
+                round,
+                amount: share,
+            });
+            Ok(share)
+        }
+
+        // ═══════════════════════════════════════════════════════════════
+        // REWARD DEPOSIT — Called by Tax Manager or bridge
+        // ═══════════════════════════════════════════════════════════════
+
+        /// Deposit LUSDT rewards. Caller must have approved this contract.
+        /// Transfers LUSDT from caller to this contract and updates reward accounting.
+        fn _deposit_rewards(&mut self, amount: Balance) -> Result<(), Error> {
+            if amount == 0 {
+                return Err(Error::ZeroAmount);
+            }
+            self.ensure_authorized_depositor()?;
+            self.ensure_reward_deposits_open()?;
+
+            let caller = self.env().caller();
+
+            // Transfer LUSDT from caller to this contract
+            self.token_transfer_from(self.lusdt_token, caller, amount)
+                .map_err(|_| Error::LusdtTransferFailed)?;
+
+            // Update reward accounting
+            self._distribute_new_rewards(amount, caller)?;
+
+            Ok(())
+        }
+
+        /// Notify contract about LUSDT rewards that were transferred directly
+        /// (e.g. Tax Manager sends via PSP22::transfer to this contract address).
+        /// Only callable by owner or authorized depositor.
+        fn _notify_reward_amount(&mut self, amount: Balance) -> Result<(), Error> {
+            if amount == 0 {
+                return Err(Error::ZeroAmount);
+            }
+            self.ensure_authorized_depositor()?;
+            self.ensure_reward_deposits_open()?;
+
+            let caller = self.env().caller();
+            self._distribute_new_rewards(amount, caller)?;
+
+            Ok(())
+        }
+
+        /// Internal: update reward-per-token accumulator with new rewards.
+        /// The configured buyback share is swapped and burned first; only the rest is distributed.
+        fn _distribute_new_rewards(&mut self, amount: Balance, depositor: AccountId) -> Result<(), Error> {
+            let buyback = self._execute_buyback(amount)?;
+            let amount = amount.checked_sub(buyback).ok_or(Error::ArithmeticOverflow)?;
+
+            // Locked sub-pool share is distributed at once; the rest follows the usual path
+            self._accrue_stream();
+            let locked = self._distribute_locked_share(amount)?;
+            let distributable = amount - locked;
+
+            // Streamed share is released over `reward_duration_ms` (not in time-weighted mode)
+            self._accrue_stream();
+            let streamed = if self.time_weighted_rewards {
+                0
+            } else {
+                distributable.saturating_mul(10_000u128.saturating_sub(self.instant_bps as u128)) / 10_000
+            };
+            let instant = distributable - streamed;
+            self._start_stream(streamed)?;
+
+            let time_weighted = self._close_time_weighted_period(instant)?;
+            let flexible_staked = self.flexible_effective_staked();
+            let mut reward_per_token_delta = 0;
+            if !time_weighted && flexible_staked > 0 {
+                let scaled = instant.checked_mul(PRECISION).ok_or(Error::ArithmeticOverflow)?;
+                let reward_increment = scaled / flexible_staked;
+                reward_per_token_delta = reward_increment;
+                self.reward_dust = self.reward_dust.saturating_add(scaled % flexible_staked);
+
+                self.reward_per_token_stored = self
+                    .reward_per_token_stored
+                    .checked_add(reward_increment)
+                    .ok_or(Error::ArithmeticOverflow)?;
+            } else if !time_weighted {
+                // No stakers: held until the next stake (`_fold_undistributed`)
+                self.pending_undistributed = self
+                    .pending_undistributed
+                    .checked_add(instant)
+                    .ok_or(Error::ArithmeticOverflow)?;
+            }
+
+            self.total_rewards_deposited = self
+                .total_rewards_deposited
+                .checked_add(amount)
+                .ok_or(Error::ArithmeticOverflow)?;
+
+            self._update_ema_reward_rate(amount);
+            self._record_deposit(depositor, amount);
+            self._record_reward_day(amount);
+
+            self.env().emit_event(RewardsDeposited {
+                depositor,
+                amount,
+                new_reward_per_token: self.reward_per_token_stored,
+                reward_per_token_delta,
+                total_staked_at_deposit: self.total_staked,
+                staker_count_at_deposit: self.staker_count,
+                cumulative_deposited: self.total_rewards_deposited,
+            });
+
+            Ok(())
+        }
+
+        /// Internal: credit the locked sub-pool with its `locked_pool_bps` share of `amount`
+        /// (all of it if no flexible stake remains) and return what was credited. Nothing
+        /// is reserved in time-weighted mode or while no position is locked.
+        fn _distribute_locked_share(&mut self, amount: Balance) -> Result<Balance, Error> {
+            let locked_staked = self.total_locked_effective_staked;
+            if self.locked_pool_bps == 0 || self.time_weighted_rewards || locked_staked == 0 {
+                return Ok(0);
+            }
+            let share = if self.flexible_effective_staked() == 0 {
+                amount
+            } else {
+                amount.saturating_mul(self.locked_pool_bps as u128) / 10_000
+            };
+            let scaled = share.checked_mul(PRECISION).ok_or(Error::ArithmeticOverflow)?;
+            self.locked_reward_per_token_stored = self
+                .locked_reward_per_token_stored
+                .checked_add(scaled / locked_staked)
+                .ok_or(Error::ArithmeticOverflow)?;
+            self.reward_dust = self.reward_dust.saturating_add(scaled % locked_staked);
+            Ok(share)
+        }
+
+        /// Internal: add `amount` to the stream, together with whatever the running stream
+        /// has not released yet, and restart it over `reward_duration_ms`.
+        fn _start_stream(&mut self, amount: Balance) -> Result<(), Error> {
+            if amount == 0 {
+                return Ok(());
+            }
+            let now = self.env().block_timestamp();
+            let leftover = if now < self.stream_finish {
+                self.stream_rate.saturating_mul((self.stream_finish - now) as u128)
+            } else {
+                0
+            };
+            let total = amount
+                .checked_mul(PRECISION)
+                .and_then(|scaled| scaled.checked_add(leftover))
+                .ok_or(Error::ArithmeticOverflow)?;
+            let duration = self.reward_duration_ms.max(1) as u128;
+            self.stream_rate = total / duration;
+            self.reward_dust = self.reward_dust.saturating_add(total % duration);
+            self.stream_finish = now.saturating_add(self.reward_duration_ms.max(1));
+            self.stream_last_update = now;
+            Ok(())
+        }
+
+        /// Internal: reward per effective token including the streamed share released
+        /// since the last `_accrue_stream`.
+        fn reward_per_token(&self) -> u128 {
+            let applicable = self.env().block_timestamp().min(self.stream_finish);
+            let flexible_staked = self.flexible_effective_staked();
+            if flexible_staked == 0 || applicable <= self.stream_last_update {
+                return self.reward_per_token_stored;
+            }
+            let released = self
+                .stream_rate
+                .saturating_mul((applicable - self.stream_last_update) as u128);
+            self.reward_per_token_stored
+                .saturating_add(released / flexible_staked)
+        }
+
+        /// Internal: fold the released stream into `reward_per_token_stored`. Must run
+        /// before `total_effective_staked` changes; released rewards with nobody staked
+        /// go to `reward_dust`.
+        fn _accrue_stream(&mut self) {
+            let applicable = self.env().block_timestamp().min(self.stream_finish);
+            if applicable <= self.stream_last_update {
+                return;
+            }
+            let released = self
+                .stream_rate
+                .saturating_mul((applicable - self.stream_last_update) as u128);
+            let flexible_staked = self.flexible_effective_staked();
+            match released.checked_div(flexible_staked) {
+                None => self.reward_dust = self.reward_dust.saturating_add(released),
+                Some(per_token) => {
+                    self.reward_per_token_stored = self.reward_per_token_stored.saturating_add(per_token);
+                    self.reward_dust = self
+                        .reward_dust
+                        .saturating_add(released % flexible_staked);
+                }
+            }
+            self.stream_last_update = applicable;
+        }
+
+        /// Internal: distribute rewards deposited while nothing was staked, once there is stake.
+        fn _fold_undistributed(&mut self) -> Result<(), Error> {
+            if self.pending_undistributed == 0 || self.total_effective_staked == 0 {
+                return Ok(());
+            }
+            let locked = self._distribute_locked_share(self.pending_undistributed)?;
+            let flexible_staked = self.flexible_effective_staked();
+            let remaining = self.pending_undistributed - locked;
+            self.pending_undistributed = 0;
+            if remaining == 0 || flexible_staked == 0 {
+                return Ok(());
+            }
+            let scaled = remaining
+                .checked_mul(PRECISION)
+                .ok_or(Error::ArithmeticOverflow)?;
+            self.reward_per_token_stored = self
+                .reward_per_token_stored
+                .checked_add(scaled / flexible_staked)
+                .ok_or(Error::ArithmeticOverflow)?;
+            self.reward_dust = self
+                .reward_dust
+                .saturating_add(scaled % flexible_staked);
+            Ok(())
+        }
+
+        /// Internal: fold forfeited rewards back into `reward_per_token_stored` for the
+        /// remaining stakers. With nobody staked they go to `reward_dust` for `reconcile_rewards`.
+        fn _redistribute_forfeited(&mut self, amount: Balance) -> Result<(), Error> {
+            if amount == 0 {
+                return Ok(());
+            }
+            let scaled = amount.checked_mul(PRECISION).ok_or(Error::ArithmeticOverflow)?;
+            let flexible_staked = self.flexible_effective_staked();
+            if flexible_staked == 0 {
+                self.reward_dust = self.reward_dust.saturating_add(scaled);
+                return Ok(());
+            }
+            self.reward_dust = self.reward_dust.saturating_add(scaled % flexible_staked);
+            self.reward_per_token_stored = self
+                .reward_per_token_stored
+                .checked_add(scaled / flexible_staked)
+                .ok_or(Error::ArithmeticOverflow)?;
+            Ok(())
+        }
+
+        /// Internal: accrue `total_staked × elapsed` into the current period. Must run
+        /// before every change to `total_staked`.
+        fn _accrue_total_stake_time(&mut self) {
+            let now = self.env().block_timestamp();
+            let elapsed = now.saturating_sub(self.tw_last_update) as u128;
+            self.tw_total_stake_ms = self
+                .tw_total_stake_ms
+                .saturating_add(self.total_staked.saturating_mul(elapsed));
+            self.tw_last_update = now;
+        }
+
+        /// Internal: close the current time-weighted period at a deposit and open the next.
+        /// In time-weighted mode the deposit is assigned to the closed period and `true` is
+        /// returned; otherwise (mode off, or no stake-time in the period) the period is closed
+        /// with zero reward and the caller distributes by instantaneous stake.
+        fn _close_time_weighted_period(&mut self, amount: Balance) -> Result<bool, Error> {
+            self._accrue_total_stake_time();
+            let now = self.env().block_timestamp();
+            let length = now.saturating_sub(self.tw_period_start) as u128;
+
+            // Time-averaged total stake over the period
+            let average_staked = self.tw_total_stake_ms.checked_div(length).unwrap_or(0);
+            let time_weighted = self.time_weighted_rewards && average_staked > 0;
+
+            let reward_per_token = if time_weighted {
+                amount
+                    .checked_mul(PRECISION)
+                    .and_then(|v| v.checked_div(average_staked))
+                    .ok_or(Error::ArithmeticOverflow)?
+            } else {
+                0
+            };
+            self.tw_cumulative_reward_per_token = self
+                .tw_cumulative_reward_per_token
+                .checked_add(reward_per_token)
+                .ok_or(Error::ArithmeticOverflow)?;
+
+            self.tw_periods.insert(
+                self.tw_period,
+                &TimeWeightedPeriod {
+                    start: self.tw_period_start,
+                    end: now,
+                    reward_per_token,
+                    cumulative_reward_per_token: self.tw_cumulative_reward_per_token,
+                },
+            );
+            self.tw_period = self.tw_period.checked_add(1).ok_or(Error::ArithmeticOverflow)?;
+            self.tw_period_start = now;
+            self.tw_total_stake_ms = 0;
+
+            Ok(time_weighted)
+        }
+
+        /// Internal: settle a staker's time-weighted entitlement from closed periods and
+        /// advance their stake-time snapshot. Returns the newly earned amount.
+        fn _settle_time_weighted(&self, info: &mut StakerInfo) -> Result<Balance, Error> {
+            let now = self.env().block_timestamp();
+            let mut earned: Balance = 0;
+
+            if info.tw_period < self.tw_period {
+                if let Some(period) = self.tw_periods.get(info.tw_period) {
+                    // Partial first period: only the stake-time actually held
+                    let held_ms = period.end.saturating_sub(info.tw_last_update) as u128;
+                    let stake_ms = info
+                        .tw_stake_ms
+                        .checked_add(info.amount.checked_mul(held_ms).ok_or(Error::ArithmeticOverflow)?)
+                        .ok_or(Error::ArithmeticOverflow)?;
+                    let length = period.end.saturating_sub(period.start) as u128;
+                    if period.reward_per_token > 0 && length > 0 {
+                        earned = (stake_ms / length)
+                            .checked_mul(period.reward_per_token)
+                            .ok_or(Error::ArithmeticOverflow)?
+                            / PRECISION;
+                    }
+
+                    // Later periods were held in full at the current amount
+                    let full_delta = self
+                        .tw_cumulative_reward_per_token
+                        .saturating_sub(period.cumulative_reward_per_token);
+                    let full = info
+                        .amount
+                        .checked_mul(full_delta)
+                        .ok_or(Error::ArithmeticOverflow)?
+                        / PRECISION;
+                    earned = earned.checked_add(full).ok_or(Error::ArithmeticOverflow)?;
+                }
+                info.tw_period = self.tw_period;
+                info.tw_stake_ms = info
+                    .amount
+                    .checked_mul(now.saturating_sub(self.tw_period_start) as u128)
+                    .ok_or(Error::ArithmeticOverflow)?;
+            } else {
+                let elapsed = now.saturating_sub(info.tw_last_update) as u128;
+                info.tw_stake_ms = info
+                    .tw_stake_ms
+                    .checked_add(info.amount.checked_mul(elapsed).ok_or(Error::ArithmeticOverflow)?)
+                    .ok_or(Error::ArithmeticOverflow)?;
+            }
+            info.tw_last_update = now;
+
+            Ok(earned)
+        }
+
+        /// Internal: swap `buyback_bps` of a deposit to LUNES and send it to the BurnEngine.
+        /// Returns the LUSDT amount diverted (0 when buybacks are disabled).
+        fn _execute_buyback(&mut self, amount: Balance) -> Result<Balance, Error> {
+            let (Some(router), Some(burn_engine)) = (self.swap_router, self.burn_engine) else {
+                return Ok(0);
+            };
+            let lusdt_amount = amount
+                .checked_mul(self.buyback_bps as u128)
+                .ok_or(Error::ArithmeticOverflow)?
+                / 10_000;
+            if lusdt_amount == 0 {
+                return Ok(0);
+            }
+
+            let min_out = self.swap_min_out(lusdt_amount)?;
+            self.token_transfer(self.lusdt_token, router, lusdt_amount)
+                .map_err(|_| Error::LusdtTransferFailed)?;
+            let lunes_amount = self
+                .router_swap(router, lusdt_amount, min_out, burn_engine)
+                .map_err(|_| Error::BuybackFailed)?;
+            if lunes_amount < min_out {
+                return Err(Error::SlippageExceeded);
+            }
+
+            self.total_buyback_lusdt = self
+                .total_buyback_lusdt
+                .checked_add(lusdt_amount)
+                .ok_or(Error::ArithmeticOverflow)?;
+            self.total_buyback_lunes = self
+                .total_buyback_lunes
+                .checked_add(lunes_amount)
+                .ok_or(Error::ArithmeticOverflow)?;
+
+            self.env().emit_event(BuybackExecuted {
+                lusdt_amount,
+                lunes_amount,
+            });
+
+            Ok(lusdt_amount)
+        }
+
+        /// Internal: append a deposit to the history ring buffer, overwriting the oldest entry when full.
+        fn _record_deposit(&mut self, depositor: AccountId, amount: Balance) {
+            let slot = self.deposit_history_count % MAX_DEPOSIT_HISTORY;
+            self.deposit_history.insert(
+                slot,
+                &DepositRecord {
+                    timestamp: self.env().block_timestamp(),
+                    depositor,
+                    amount,
+                    reward_per_token_after: self.reward_per_token_stored,
+                },
+            );
+            self.deposit_history_count = self.deposit_history_count.wrapping_add(1);
+        }
+
+        /// Internal: add a deposit to today's bucket, recycling the slot of an expired day.
+        fn _record_reward_day(&mut self, amount: Balance) {
+            let day = self.env().block_timestamp() / DAY_MS;
+            let slot = (day % REWARD_RATE_WINDOW_DAYS) as u32;
+            let total = match self.reward_days.get(slot) {
+                Some((bucket_day, bucket_amount)) if bucket_day == day => bucket_amount.saturating_add(amount),
+                _ => amount,
+            };
+            self.reward_days.insert(slot, &(day, total));
+        }
+
+        /// Internal: fold a deposit into the EMA reward rate.
+        /// `ema = alpha * instantaneous + (1 - alpha) * ema`, where the instantaneous
+        /// rate is the deposit spread over the time since the previous deposit.
+        fn _update_ema_reward_rate(&mut self, amount: Balance) {
+            let now = self.env().block_timestamp();
+            let elapsed = now.saturating_sub(self.last_reward_deposit_at).max(1);
+            let instantaneous = amount.saturating_mul(DAY_MS as u128) / elapsed as u128;
+
+            self.ema_reward_rate = if self.ema_reward_rate == 0 {
+                // Seed with the first sample instead of biasing towards zero
+                instantaneous
+            } else {
+                let alpha = self.ema_alpha_bps as u128;
+                instantaneous
+                    .saturating_mul(alpha)
+                    .saturating_add(self.ema_reward_rate.saturating_mul(10_000 - alpha))
+                    / 10_000
+            };
+            self.last_reward_deposit_at = now;
+        }
+
+        /// Internal: settle pending rewards for a staker.
+        /// Frozen stakers only have their snapshots advanced, so accrual during
+        /// the freeze (on both tracks) is forfeited to the pool.
+        /// Returns the LUSDT rewards left unassigned (scaled by PRECISION): the rounding
+        /// remainder of the instantaneous share plus anything forfeited by a freeze;
+        /// and the LUNES rewards forfeited by a freeze.
+        fn _update_reward(&self, user: &AccountId, info: &mut StakerInfo) -> Result<(u128, Balance), Error> {
+            let mut earned = self._settle_time_weighted(info)?;
+            let mut dust: u128 = 0;
+            let reward_per_token = self.reward_per_token();
+
+            if info.amount > 0 {
+                let reward_delta = reward_per_token
+                    .checked_sub(info.reward_per_token_paid)
+                    .ok_or(Error::ArithmeticOverflow)?;
+                let locked_delta = self
+                    .locked_reward_per_token_stored
+                    .checked_sub(info.locked_reward_per_token_paid)
+                    .ok_or(Error::ArithmeticOverflow)?;
+
+                let scaled = self
+                    .flexible_amount(info)
+                    .checked_mul(reward_delta)
+                    .ok_or(Error::ArithmeticOverflow)?;
+                let locked_scaled = Self::locked_amount(info)
+                    .checked_mul(locked_delta)
+                    .ok_or(Error::ArithmeticOverflow)?;
+                earned = earned
+                    .checked_add(scaled / PRECISION)
+                    .and_then(|v| v.checked_add(locked_scaled / PRECISION))
+                    .ok_or(Error::ArithmeticOverflow)?;
+                dust = (scaled % PRECISION).saturating_add(locked_scaled % PRECISION);
+            }
+
+            // LUNES track: instantaneous effective stake
+            let lunes_delta = self
+                .reward_per_token_stored_lunes
+                .checked_sub(info.reward_per_token_paid_lunes)
+                .ok_or(Error::ArithmeticOverflow)?;
+            let mut earned_lunes: Balance = 0;
+            if info.amount > 0 && lunes_delta > 0 {
+                earned_lunes = Self::effective_amount(info)
+                    .checked_mul(lunes_delta)
+                    .ok_or(Error::ArithmeticOverflow)?
+                    / PRECISION;
+            }
+            info.reward_per_token_paid_lunes = self.reward_per_token_stored_lunes;
+
+            let mut forfeited_lunes: Balance = 0;
+            if self.is_reward_frozen(*user) {
+                dust = dust.saturating_add(earned.saturating_mul(PRECISION));
+                forfeited_lunes = earned_lunes;
+            } else {
+                info.pending_rewards = info
+                    .pending_rewards
+                    .checked_add(earned)
+                    .ok_or(Error::ArithmeticOverflow)?;
+                info.pending_rewards_lunes = info
+                    .pending_rewards_lunes
+                    .checked_add(earned_lunes)
+                    .ok_or(Error::ArithmeticOverflow)?;
+            }
+            info.reward_per_token_paid = reward_per_token;
+            info.locked_reward_per_token_paid = self.locked_reward_per_token_stored;
+            Ok((dust, forfeited_lunes))
+        }
+
+        /// Internal: reward weight of a position (stake × lock tier multiplier).
+        fn effective_amount(info: &StakerInfo) -> Balance {
+            let multiplier_bps = LOCK_TIERS
+                .get(info.lock_tier as usize)
+                .map(|(_, bps)| *bps)
+                .unwrap_or(10_000);
+            info.amount.saturating_mul(multiplier_bps as u128) / 10_000
+        }
+
+        /// Internal: the part of `amount` owed to the position's lock multiplier
+        /// (`amount × (multiplier − 1) / multiplier`; 0 when unlocked).
+        fn boosted_portion(info: &StakerInfo, amount: Balance) -> Balance {
+            let multiplier_bps = LOCK_TIERS
+                .get(info.lock_tier as usize)
+                .map(|(_, bps)| *bps as u128)
+                .unwrap_or(10_000);
+            amount.saturating_mul(multiplier_bps.saturating_sub(10_000)) / multiplier_bps
+        }
+
+        /// Internal: weight of a position in the locked sub-pool (0 when unlocked).
+        fn locked_amount(info: &StakerInfo) -> Balance {
+            if info.lock_tier > 0 {
+                Self::effective_amount(info)
+            } else {
+                0
+            }
+        }
+
+        /// Internal: weight of a position in the flexible (main) accumulator. Locked
+        /// positions leave it while the locked sub-pool is enabled.
+        fn flexible_amount(&self, info: &StakerInfo) -> Balance {
+            if self.locked_pool_bps > 0 && info.lock_tier > 0 {
+                0
+            } else {
+                Self::effective_amount(info)
+            }
+        }
+
+        /// Internal: denominator of the flexible (main) accumulator.
+        fn flexible_effective_staked(&self) -> Balance {
+            if self.locked_pool_bps > 0 {
+                self.total_effective_staked
+                    .saturating_sub(self.total_locked_effective_staked)
+            } else {
+                self.total_effective_staked
+            }
+        }
+
+        /// Internal: swap a position's previous weight for its current one in
+        /// `total_effective_staked` and `total_locked_effective_staked`.
+        fn _reweight(&mut self, before: &StakerInfo, info: &StakerInfo) {
+            self.total_effective_staked = self
+                .total_effective_staked
+                .saturating_sub(Self::effective_amount(before))
+                .saturating_add(Self::effective_amount(info));
+            self.total_locked_effective_staked = self
+                .total_locked_effective_staked
+                .saturating_sub(Self::locked_amount(before))
+                .saturating_add(Self::locked_amount(info));
+        }
+
+        /// Internal: `_update_reward`, keeping the unassigned remainder for `reconcile_rewards`.
+        fn _settle_reward(&mut self, user: &AccountId, info: &mut StakerInfo) -> Result<(), Error> {
+            self._accrue_stream();
+            let (dust, forfeited_lunes) = self._update_reward(user, info)?;
+            self.reward_dust = self.reward_dust.saturating_add(dust);
+
+            // Forfeited LUNES go to the other stakers (kept unassigned if there are none)
+            let others = self
+                .total_effective_staked
+                .saturating_sub(Self::effective_amount(info));
+            if forfeited_lunes > 0 && others > 0 {
+                let increment = forfeited_lunes
+                    .checked_mul(PRECISION)
+                    .ok_or(Error::ArithmeticOverflow)?
+                    / others;
+                self.reward_per_token_stored_lunes = self
+                    .reward_per_token_stored_lunes
+                    .checked_add(increment)
+                    .ok_or(Error::ArithmeticOverflow)?;
+                info.reward_per_token_paid_lunes = self.reward_per_token_stored_lunes;
+            }
+            Ok(())
+        }
+
+        /// Internal: the user's position after adding `amount` must reach `min_stake`.
+        fn ensure_meets_minimum(&self, user: AccountId, amount: Balance) -> Result<(), Error> {
+            let current = self.stakers.get(user).map(|info| info.amount).unwrap_or(0);
+            let new_total = current.checked_add(amount).ok_or(Error::ArithmeticOverflow)?;
+            if new_total < self.min_stake {
+                return Err(Error::BelowMinimumStake);
+            }
+            Ok(())
+        }
+
+        /// Internal: add already-received LUNES to a user's position, settling
+        /// their pending rewards first.
+        fn _credit_stake(&mut self, user: AccountId, amount: Balance) -> Result<(), Error> {
+            let mut info = self.stakers.get(user).unwrap_or_default();
+
+            // Settle any pending rewards before changing stake
+            self._settle_reward(&user, &mut info)?;
+            self._maybe_compound(user, &mut info)?;
+            self._claim_over_cap(user, &mut info)?;
+
+            // Update staker info
+            let was_new = info.amount == 0;
+            let before = info.clone();
+            self._checkpoint_stake(user, before.amount);
+            info.amount = info.amount.checked_add(amount).ok_or(Error::ArithmeticOverflow)?;
+            self._reweight(&before, &info);
+            // A credit from someone else (`stake_for_batch`) must not restart the owner's cooldown
+            if was_new || user == self.env().caller() {
+                info.last_stake_at = self.env().block_timestamp();
+            }
+            if was_new {
+                info.staked_at = info.last_stake_at;
+                self.staker_count = self.staker_count.saturating_add(1);
+                self._index_staker(user);
+            }
+            self.stakers.insert(user, &info);
+
+            // Update global total
+            self._accrue_total_stake_time();
+            self.total_staked = self.total_staked.checked_add(amount).ok_or(Error::ArithmeticOverflow)?;
+            self._fold_undistributed()?;
+
+            self.env().emit_event(Staked {
+                user,
+                amount,
+                total_staked: self.total_staked,
+            });
+
+            Ok(())
+        }
+
+        /// Internal: before `user`'s stake changes, record its current `amount` for the
+        /// latest snapshot unless already recorded, so there is at most one checkpoint
+        /// per staker per snapshot.
+        fn _checkpoint_stake(&mut self, user: AccountId, amount: Balance) {
+            let snapshot_id = self.current_snapshot_id;
+            if snapshot_id == 0 {
+                return;
+            }
+            let count = self.stake_checkpoint_count.get(user).unwrap_or(0);
+            let recorded = count > 0
+                && self
+                    .stake_checkpoints
+                    .get((user, count - 1))
+                    .is_some_and(|(id, _)| id == snapshot_id);
+            if recorded {
+                return;
+            }
+            self.stake_checkpoints.insert((user, count), &(snapshot_id, amount));
+            self.stake_checkpoint_count.insert(user, &count.saturating_add(1));
+        }
+
+        /// Internal: append a new staker to `staker_index`.
+        fn _index_staker(&mut self, user: AccountId) {
+            if self.staker_index_pos.contains(user) {
+                return;
+            }
+            self.staker_index.insert(self.staker_index_len, &user);
+            self.staker_index_pos.insert(user, &self.staker_index_len);
+            self.staker_index_len = self.staker_index_len.saturating_add(1);
+        }
+
+        /// Internal: drop an exiting staker from `staker_index`, moving the last entry
+        /// into its slot so indices stay dense.
+        fn _unindex_staker(&mut self, user: AccountId) {
+            let Some(pos) = self.staker_index_pos.take(user) else {
+                return;
+            };
+            let last = self.staker_index_len.saturating_sub(1);
+            if pos != last {
+                if let Some(moved) = self.staker_index.get(last) {
+                    self.staker_index.insert(pos, &moved);
+                    self.staker_index_pos.insert(moved, &pos);
+                }
+            }
+            self.staker_index.remove(last);
+            self.staker_index_len = last;
+        }
+
+        /// Internal: account a paid-out claim, splitting it into base and boosted
+        /// portions for transparency. `boosted` must not exceed `amount`.
+        fn _record_claim(&mut self, user: AccountId, amount: Balance, boosted: Balance) {
+            let boosted = boosted.min(amount);
+            let base = amount.saturating_sub(boosted);
+
+            self.total_rewards_claimed = self.total_rewards_claimed.saturating_add(amount);
+            self.total_base_rewards = self.total_base_rewards.saturating_add(base);
+            self.total_boosted_rewards = self.total_boosted_rewards.saturating_add(boosted);
+
+            if boosted > 0 {
+                self.env().emit_event(RewardBoostApplied {
+                    user,
+                    base_amount: base,
+                    boosted_amount: boosted,
+                });
+            }
+        }
+
+        // ═══════════════════════════════════════════════════════════════
+        // READ-ONLY QUERIES
+        // ═══════════════════════════════════════════════════════════════
+
+        /// Get staker information for a given address.
+        #[ink(message)]
+        pub fn get_staker_info(&self, user: AccountId) -> StakerInfo {
+            self.stakers.get(user).unwrap_or_default()
+        }
+
+        /// Get pending (unclaimed) LUSDT rewards for a user.
+        /// Includes both settled and unsettled rewards.
+        #[ink(message)]
+        pub fn get_pending_rewards(&self, user: AccountId) -> Balance {
+            let info = self.stakers.get(user).unwrap_or_default();
+            let settled = info.pending_rewards;
+            if self.is_reward_frozen(user) {
+                return settled;
+            }
+
+            // Settle a copy, including closed time-weighted periods
+            let mut preview = info;
+            match self._update_reward(&user, &mut preview) {
+                Ok(_) => preview.pending_rewards,
+                Err(_) => settled,
+            }
+        }
+
+        /// Whether depositing `amount` now would move `reward_per_token` at the current
+        /// `total_staked` (after any buyback share). Lets depositors batch dust instead
+        /// of making zero-effect deposits.
+        #[ink(message)]
+        pub fn would_distribute_nonzero(&self, amount: Balance) -> bool {
+            if self.total_effective_staked == 0 {
+                return false;
+            }
+            let buyback = if self.swap_router.is_some() && self.burn_engine.is_some() {
+                amount.saturating_mul(self.buyback_bps as u128) / 10_000
+            } else {
+                0
+            };
+            amount
+                .saturating_sub(buyback)
+                .checked_mul(PRECISION)
+                .is_none_or(|scaled| scaled / self.total_effective_staked > 0)
+        }
+
+        /// Whether deposits are distributed by time-weighted stake.
+        #[ink(message)]
+        pub fn is_time_weighted_rewards(&self) -> bool {
+            self.time_weighted_rewards
+        }
+
+        /// Total LUNES staked across all users.
+        #[ink(message)]
+        pub fn get_total_staked(&self) -> Balance {
+            self.total_staked
+        }
+
+        /// Number of active stakers.
+        #[ink(message)]
+        pub fn get_staker_count(&self) -> u32 {
+            self.staker_count
+        }
+
+        /// Total LUSDT rewards ever deposited into the pool.
+        #[ink(message)]
+        pub fn get_total_rewards_deposited(&self) -> Balance {
+            self.total_rewards_deposited
+        }
+
+        /// Total LUSDT rewards ever claimed by stakers.
+        #[ink(message)]
+        pub fn get_total_rewards_claimed(&self) -> Balance {
+            self.total_rewards_claimed
+        }
+
+        /// Average LUSDT rewards per day over the last `REWARD_RATE_WINDOW_DAYS` days
+        /// (including today).
+        #[ink(message)]
+        pub fn get_reward_rate_per_day(&self) -> Balance {
+            let today = self.env().block_timestamp() / DAY_MS;
+            let total = (0..REWARD_RATE_WINDOW_DAYS as u32)
+                .filter_map(|slot| self.reward_days.get(slot))
+                .filter(|(day, _)| *day <= today && today - *day < REWARD_RATE_WINDOW_DAYS)
+                .fold(0u128, |acc, (_, amount)| acc.saturating_add(amount));
+            total / REWARD_RATE_WINDOW_DAYS as u128
+        }
+
+        /// Estimated APR in basis points: the EMA reward rate (`get_ema_reward_rate`)
+        /// annualized against `total_staked`. Raw token units (LUSDT per LUNES); front-ends
+        /// apply prices and decimals. Returns 0 when nothing is staked.
+        #[ink(message)]
+        pub fn get_estimated_apr_bps(&self) -> u128 {
+            if self.total_staked == 0 {
+                return 0;
+            }
+            self.ema_reward_rate
+                .saturating_mul(365 * 10_000)
+                / self.total_staked
+        }
+
+        /// Smoothed reward rate in LUSDT per day, intended for APR displays.
+        #[ink(message)]
+        pub fn get_ema_reward_rate(&self) -> Balance {
+            self.ema_reward_rate
+        }
+
+        /// EMA smoothing factor in basis points.
+        #[ink(message)]
+        pub fn get_ema_alpha_bps(&self) -> u16 {
+            self.ema_alpha_bps
+        }
+
+        /// Active stakers and their stake, `limit` (max `MAX_STAKER_PAGE`) from index `start`.
+        /// Order is not stable across exits: the last staker moves into a leaver's slot.
+        #[ink(message)]
+        pub fn get_stakers_paged(&self, start: u32, limit: u32) -> Vec<(AccountId, Balance)> {
+            let end = start
+                .saturating_add(limit.min(MAX_STAKER_PAGE))
+                .min(self.staker_index_len);
+            (start..end)
+                .filter_map(|i| self.staker_index.get(i))
+                .map(|user| (user, self.stakers.get(user).map(|info| info.amount).unwrap_or(0)))
+                .collect()
+        }
+
+        /// Paginated reward deposit ledger, oldest retained entry first.
+        /// Returns `(timestamp, depositor, amount, reward_per_token_after)`; `start` indexes
+        /// the retained entries (only the last `MAX_DEPOSIT_HISTORY` are kept) and `limit`
+        /// is capped at `MAX_HISTORY_PAGE`.
+        #[ink(message)]
+        pub fn get_deposit_history(&self, start: u32, limit: u32) -> Vec<(Timestamp, AccountId, Balance, u128)> {
+            let retained = self.deposit_history_count.min(MAX_DEPOSIT_HISTORY);
+            let oldest = self.deposit_history_count - retained;
+            let end = start.saturating_add(limit.min(MAX_HISTORY_PAGE)).min(retained);
+
+            (start..end)
+                .filter_map(|i| self.deposit_history.get((oldest + i) % MAX_DEPOSIT_HISTORY))
+                .map(|r| (r.timestamp, r.depositor, r.amount, r.reward_per_token_after))
+                .collect()
+        }
+
+        /// Total reward deposits ever recorded (including ones rotated out of the history).
+        #[ink(message)]
+        pub fn get_deposit_history_count(&self) -> u32 {
+            self.deposit_history_count
+        }
+
+        /// Buyback configuration: (bps, router, burn engine).
+        #[ink(message)]
+        pub fn get_buyback_config(&self) -> (u16, Option<AccountId>, Option<AccountId>) {
+            (self.buyback_bps, self.swap_router, self.burn_engine)
+        }
+
+        /// Swap price bound: (reference LUNES per LUSDT scaled by 1e18, max slippage bps).
+        #[ink(message)]
+        pub fn get_swap_slippage(&self) -> (Balance, u16) {
+            (self.swap_reference_rate, self.max_swap_slippage_bps)
+        }
+
+        /// Totals bought back so far: (LUSDT spent, LUNES sent to the BurnEngine).
+        #[ink(message)]
+        pub fn get_buyback_totals(&self) -> (Balance, Balance) {
+            (self.total_buyback_lusdt, self.total_buyback_lunes)
+        }
+
+        /// Claimed rewards earned by plain stake weight.
+        #[ink(message)]
+        pub fn get_total_base_rewards(&self) -> Balance {
+            self.total_base_rewards
+        }
+
+        /// Claimed rewards attributable to boost mechanics.
+        #[ink(message)]
+        pub fn get_total_boosted_rewards(&self) -> Balance {
+            self.total_boosted_rewards
+        }
+
+        /// Current reward per token, including the released stream (scaled by PRECISION).
+        #[ink(message)]
+        pub fn get_reward_per_token(&self) -> u128 {
+            self.reward_per_token()
+        }
+
+        /// `(instant_bps, reward_duration_ms)`: share of each deposit distributed at once
+        /// and the period over which the rest streams.
+        #[ink(message)]
+        pub fn get_reward_split(&self) -> (u16, u64) {
+            (self.instant_bps, self.reward_duration_ms)
+        }
+
+        /// Minimum LUNES required to stake.
+        #[ink(message)]
+        pub fn get_min_stake(&self) -> Balance {
+            self.min_stake
+        }
+
+        /// Minimum LUNES required to stake into a lock tier.
+        /// Falls back to the global minimum when the tier has no override.
+        #[ink(message)]
+        pub fn get_tier_min_stake(&self, tier: u8) -> Balance {
+            self.tier_min_stake.get(tier).unwrap_or(self.min_stake)
+        }
+
+        /// Stake `user` held when snapshot `snapshot_id` was taken (0 for unknown ids).
+        /// Stakes added after the snapshot don't count, so staking right before a
+        /// vote gains nothing.
+        #[ink(message)]
+        pub fn voting_power_at(&self, user: AccountId, snapshot_id: u64) -> Balance {
+            if snapshot_id == 0 || snapshot_id > self.current_snapshot_id {
+                return 0;
+            }
+            // First checkpoint written at or after the snapshot holds the stake at that time
+            let (mut low, mut high) = (0u32, self.stake_checkpoint_count.get(user).unwrap_or(0));
+            while low < high {
+                let mid = low + (high - low) / 2;
+                match self.stake_checkpoints.get((user, mid)) {
+                    Some((id, _)) if id < snapshot_id => low = mid + 1,
+                    _ => high = mid,
+                }
+            }
+            match self.stake_checkpoints.get((user, low)) {
+                Some((_, amount)) => amount,
+                // Unchanged since the snapshot
+                None => self.stakers.get(user).map(|info| info.amount).unwrap_or(0),
+            }
+        }
+
+        /// `total_staked` when snapshot `snapshot_id` was taken.
+        #[ink(message)]
+        pub fn total_staked_at(&self, snapshot_id: u64) -> Balance {
+            self.snapshot_total_staked.get(snapshot_id).unwrap_or(0)
+        }
+
+        /// Id of the latest voting-power snapshot (0 = none).
+        #[ink(message)]
+        pub fn get_current_snapshot_id(&self) -> u64 {
+            self.current_snapshot_id
+        }
+
+        /// Governance contract allowed to take snapshots, if any.
+        #[ink(message)]
+        pub fn get_governance(&self) -> Option<AccountId> {
+            self.governance
+        }
+
+        /// Whether `user` stakes enough to create a governance proposal.
+        /// Intended for external governance contracts.
+        #[ink(message)]
+        pub fn can_create_proposal(&self, user: AccountId) -> bool {
+            let staked = self.stakers.get(user).map(|info| info.amount).unwrap_or(0);
+            staked > 0 && staked >= self.proposal_threshold
+        }
+
+        /// Minimum stake required by `can_create_proposal`.
+        #[ink(message)]
+        pub fn get_proposal_threshold(&self) -> Balance {
+            self.proposal_threshold
+        }
+
+        /// Whether the contract is paused.
+        #[ink(message)]
+        pub fn is_paused(&self) -> bool {
+            self.paused
+        }
+
+        /// Contract owner address.
+        #[ink(message)]
+        pub fn get_owner(&self) -> AccountId {
+            self.owner
+        }
+
+        /// Authorized depositor address (typically Tax Manager).
+        #[ink(message)]
+        pub fn get_authorized_depositor(&self) -> Option<AccountId> {
+            self.authorized_depositor
+        }
+
+        /// Unstake cooldown period in milliseconds.
         #[ink(message)]
         pub fn get_cooldown_ms(&self) -> u64 {
             self.unstake_cooldown_ms
         }
 
-        /// Get the undistributed LUSDT reward balance
-        /// (deposited - claimed = what's still in the contract for rewards).
-        #[ink(message)]
-        pub fn get_undistributed_rewards(&self) -> Balance {
-            self.total_rewards_deposited.saturating_sub(self.total_rewards_claimed)
-        }
+        /// Minimum holding time (ms since `staked_at`) before unstaking.
+        #[ink(message)]
+        pub fn get_min_stake_duration_ms(&self) -> u64 {
+            self.min_stake_duration_ms
+        }
+
+        /// Whether a user wants reward-deposit notifications.
+        #[ink(message)]
+        pub fn get_notification_pref(&self, user: AccountId) -> bool {
+            self.notification_prefs.get(user).unwrap_or(false)
+        }
+
+        /// Whether a staker's reward accrual is currently frozen.
+        #[ink(message)]
+        pub fn is_reward_frozen(&self, user: AccountId) -> bool {
+            self.reward_frozen.get(user).unwrap_or(false)
+        }
+
+        /// Additional reward pool state (`None` for pool 0 or unknown ids).
+        #[ink(message)]
+        pub fn get_reward_pool(&self, pool_id: u32) -> Option<RewardPool> {
+            self.reward_pools.get(pool_id)
+        }
+
+        /// Number of additional reward pools (valid ids are `1..=count`).
+        #[ink(message)]
+        pub fn get_reward_pool_count(&self) -> u32 {
+            self.reward_pool_count
+        }
+
+        /// A staker's position in an additional reward pool.
+        #[ink(message)]
+        pub fn get_pool_position(&self, pool_id: u32, user: AccountId) -> PoolPosition {
+            self.pool_positions.get((pool_id, user)).unwrap_or_default()
+        }
+
+        /// Pending LUSDT rewards in a pool, including unsettled ones. Pool 0 is the main pool.
+        #[ink(message)]
+        pub fn get_pool_pending_rewards(&self, pool_id: u32, user: AccountId) -> Balance {
+            if pool_id == 0 {
+                return self.get_pending_rewards(user);
+            }
+            let mut position = self.get_pool_position(pool_id, user);
+            let settled = position.pending_rewards;
+            let Some(pool) = self.reward_pools.get(pool_id) else {
+                return settled;
+            };
+            match self._update_pool_reward(user, &pool, &mut position) {
+                Ok(()) => position.pending_rewards,
+                Err(_) => settled,
+            }
+        }
+
+        /// Main-pool rewards currently stranded by rounding or freeze forfeits (whole units).
+        #[ink(message)]
+        pub fn get_reward_dust(&self) -> Balance {
+            self.reward_dust / PRECISION
+        }
+
+        /// Insurance state: (claim window open, insurance balance, current round).
+        #[ink(message)]
+        pub fn get_insurance_state(&self) -> (bool, Balance, u32) {
+            (self.insurance_claim_enabled, self.insurance_balance, self.insurance_round)
+        }
+
+        /// Amount a staker received in an insurance round (0 if they didn't claim).
+        #[ink(message)]
+        pub fn get_insurance_claim(&self, round: u32, user: AccountId) -> Balance {
+            self.insurance_claims.get((round, user)).unwrap_or(0)
+        }
+
+        /// LUSDT deposited while nothing was staked, waiting for the next stake.
+        #[ink(message)]
+        pub fn get_stranded_rewards(&self) -> Balance {
+            self.pending_undistributed
+        }
+
+        /// Pending rewards above which the next stake/unstake auto-claims (0 = unlimited).
+        #[ink(message)]
+        pub fn get_max_pending_rewards(&self) -> Balance {
+            self.max_pending_rewards
+        }
+
+        /// Pending (unclaimed) LUNES rewards for a user, including unsettled ones.
+        /// Frozen stakers accrue nothing new.
+        #[ink(message)]
+        pub fn get_pending_rewards_lunes(&self, user: AccountId) -> Balance {
+            let mut preview = self.stakers.get(user).unwrap_or_default();
+            let settled = preview.pending_rewards_lunes;
+            match self._update_reward(&user, &mut preview) {
+                Ok(_) => preview.pending_rewards_lunes,
+                Err(_) => settled,
+            }
+        }
+
+        /// Total LUNES rewards ever (deposited, claimed).
+        #[ink(message)]
+        pub fn get_lunes_reward_totals(&self) -> (Balance, Balance) {
+            (self.total_lunes_rewards_deposited, self.total_lunes_rewards_claimed)
+        }
+
+        /// LUSDT rewards escrowed for `user` after a failed payout.
+        #[ink(message)]
+        pub fn get_escrowed_rewards(&self, user: AccountId) -> Balance {
+            self.unclaimed_escrow.get(user).unwrap_or(0)
+        }
+
+        /// Total LUSDT held in reward escrow.
+        #[ink(message)]
+        pub fn get_total_escrowed(&self) -> Balance {
+            self.total_escrowed
+        }
+
+        /// Router `claim_and_notify` swaps claimed LUSDT through, if any.
+        #[ink(message)]
+        pub fn get_auto_compound_target(&self) -> Option<AccountId> {
+            self.auto_compound_target
+        }
+
+        /// Share of each deposit (bps) reserved for locked positions (0 = one shared pool).
+        #[ink(message)]
+        pub fn get_locked_pool_bps(&self) -> u16 {
+            self.locked_pool_bps
+        }
+
+        /// Reward per effective token of the (flexible, locked) sub-pools, scaled by PRECISION.
+        #[ink(message)]
+        pub fn get_sub_pool_reward_per_token(&self) -> (u128, u128) {
+            (self.reward_per_token(), self.locked_reward_per_token_stored)
+        }
+
+        /// `(max_claims_per_window, claim_window_ms)`; 0 claims means unlimited.
+        #[ink(message)]
+        pub fn get_claim_frequency(&self) -> (u32, u64) {
+            (self.max_claims_per_window, self.claim_window_ms)
+        }
+
+        /// Claims `user` has made in their current claim window.
+        #[ink(message)]
+        pub fn get_claims_in_window(&self, user: AccountId) -> u32 {
+            self.claims_in_window(user).0
+        }
+
+        /// Get the undistributed LUSDT reward balance
+        /// (deposited - claimed = what's still in the contract for rewards).
+        #[ink(message)]
+        pub fn get_undistributed_rewards(&self) -> Balance {
+            self.total_rewards_deposited.saturating_sub(self.total_rewards_claimed)
+        }
+
+        /// Whether the contract holds enough LUSDT for what it owes. Returns (LUSDT balance,
+        /// liabilities, solvent), where liabilities are the undistributed rewards (escrow
+        /// included) plus the insurance balance. For monitors alarming on a shortfall.
+        #[ink(message)]
+        pub fn get_reward_solvency(&self) -> (Balance, Balance, bool) {
+            let balance = self.token_balance_of(self.lusdt_token, self.env().account_id());
+            let liabilities = self.lusdt_liabilities();
+            (balance, liabilities, balance >= liabilities)
+        }
+
+        /// LUSDT the contract owes: undistributed rewards plus insurance. Should both tokens
+        /// be the same contract, staked principal and unclaimed LUNES rewards share the
+        /// balance and are counted too, so they are never mistaken for surplus.
+        fn lusdt_liabilities(&self) -> Balance {
+            let mut liabilities = self
+                .get_undistributed_rewards()
+                .saturating_add(self.insurance_balance);
+            if self.lunes_token == self.lusdt_token {
+                let pool_staked = (1..=self.reward_pool_count)
+                    .filter_map(|id| self.reward_pools.get(id))
+                    .fold(0, |sum: Balance, pool| sum.saturating_add(pool.total_staked));
+                liabilities = liabilities
+                    .saturating_add(self.total_staked)
+                    .saturating_add(pool_staked)
+                    .saturating_add(
+                        self.total_lunes_rewards_deposited
+                            .saturating_sub(self.total_lunes_rewards_claimed),
+                    );
+            }
+            liabilities
+        }
+
+        // ═══════════════════════════════════════════════════════════════
+        // ADMIN — Limited powers (CANNOT withdraw funds)
+        // ═══════════════════════════════════════════════════════════════
+
+        /// Point staking at a redeployed LUNES token. Migration-only: requires nothing
+        /// staked (main or additional pools) and no outstanding rewards or insurance. Only owner.
+        #[ink(message)]
+        pub fn set_lunes_token(&mut self, lunes_token: AccountId) -> Result<(), Error> {
+            self.ensure_owner()?;
+            self.ensure_tokens_unused()?;
+            self.lunes_token = lunes_token;
+            self.env().emit_event(AdminUpdated {
+                name: "LunesToken".into(),
+            });
+            Ok(())
+        }
+
+        /// Point rewards at a redeployed LUSDT token. Migration-only: requires nothing
+        /// staked and no outstanding rewards or insurance. Only owner.
+        #[ink(message)]
+        pub fn set_lusdt_token(&mut self, lusdt_token: AccountId) -> Result<(), Error> {
+            self.ensure_owner()?;
+            self.ensure_tokens_unused()?;
+            self.lusdt_token = lusdt_token;
+            self.env().emit_event(AdminUpdated {
+                name: "LusdtToken".into(),
+            });
+            Ok(())
+        }
+
+        /// Returns (lunes_token, lusdt_token).
+        #[ink(message)]
+        pub fn get_token_addresses(&self) -> (AccountId, AccountId) {
+            (self.lunes_token, self.lusdt_token)
+        }
+
+        /// Switching tokens with funds in flight would strand (or hand the owner) balances
+        /// held in the old token.
+        fn ensure_tokens_unused(&self) -> Result<(), Error> {
+            if self.total_staked != 0
+                || self.get_undistributed_rewards() != 0
+                || self.total_lunes_rewards_deposited != self.total_lunes_rewards_claimed
+                || self.insurance_balance != 0
+                || (1..=self.reward_pool_count)
+                    .any(|id| self.reward_pools.get(id).is_some_and(|pool| pool.total_staked != 0))
+            {
+                return Err(Error::TokensInUse);
+            }
+            Ok(())
+        }
+
+        /// Set the authorized depositor (Tax Manager contract address).
+        /// Only owner. This address can call deposit_rewards / notify_reward_amount.
+        #[ink(message)]
+        pub fn set_authorized_depositor(&mut self, depositor: AccountId) -> Result<(), Error> {
+            self.ensure_owner()?;
+            self.authorized_depositor = Some(depositor);
+            self.env().emit_event(AdminUpdated {
+                name: "AuthorizedDepositor".into(),
+            });
+            Ok(())
+        }
+
+        /// Set unstake cooldown period in milliseconds. 0 = no cooldown.
+        /// Only owner.
+        #[ink(message)]
+        pub fn set_cooldown(&mut self, cooldown_ms: u64) -> Result<(), Error> {
+            self.ensure_owner()?;
+            self.unstake_cooldown_ms = cooldown_ms;
+            self.env().emit_event(AdminUpdated {
+                name: "Cooldown".into(),
+            });
+            Ok(())
+        }
+
+        /// Set the minimum time a position must be held since it was opened before it
+        /// can be unstaked (0 = none). Independent of the cooldown. Only owner.
+        #[ink(message)]
+        pub fn set_min_stake_duration(&mut self, duration_ms: u64) -> Result<(), Error> {
+            self.ensure_owner()?;
+            self.min_stake_duration_ms = duration_ms;
+            self.env().emit_event(AdminUpdated {
+                name: "MinStakeDuration".into(),
+            });
+            Ok(())
+        }
+
+        /// Set (or clear) the address allowed to pause. Only owner.
+        #[ink(message)]
+        pub fn set_pauser(&mut self, pauser: Option<AccountId>) -> Result<(), Error> {
+            self.ensure_owner()?;
+            self.pauser = pauser;
+            self.env().emit_event(AdminUpdated {
+                name: "Pauser".into(),
+            });
+            Ok(())
+        }
+
+        /// Get the configured pauser.
+        #[ink(message)]
+        pub fn get_pauser(&self) -> Option<AccountId> {
+            self.pauser
+        }
+
+        /// Pause the contract (blocks new stakes, but allows unstake + claim).
+        /// Owner or pauser.
+        #[ink(message)]
+        pub fn pause(&mut self) -> Result<(), Error> {
+            if self.pauser != Some(self.env().caller()) {
+                self.ensure_owner()?;
+            }
+            self.paused = true;
+            self.env().emit_event(AdminUpdated {
+                name: "Paused".into(),
+            });
+            Ok(())
+        }
+
+        /// Unpause the contract.
+        /// Only owner.
+        #[ink(message)]
+        pub fn unpause(&mut self) -> Result<(), Error> {
+            self.ensure_owner()?;
+            self.paused = false;
+            self.env().emit_event(AdminUpdated {
+                name: "Unpaused".into(),
+            });
+            Ok(())
+        }
+
+        /// Pause or resume new reward deposits and notifications. Only owner.
+        /// Unlike `pause`, staking is untouched, and rewards already deposited
+        /// (including an in-flight stream) keep accruing and stay claimable.
+        #[ink(message)]
+        pub fn set_reward_deposits_paused(&mut self, paused: bool) -> Result<(), Error> {
+            self.ensure_owner()?;
+            self.reward_deposits_paused = paused;
+            self.env().emit_event(AdminUpdated {
+                name: "RewardDepositsPaused".into(),
+            });
+            Ok(())
+        }
+
+        /// Whether new reward deposits are paused.
+        #[ink(message)]
+        pub fn is_reward_deposits_paused(&self) -> bool {
+            self.reward_deposits_paused
+        }
+
+        /// Freeze or unfreeze a staker's reward accrual. Only owner.
+        /// Rewards earned before the freeze are settled and kept; accrual while
+        /// frozen is forfeited to the pool. Principal can always be unstaked.
+        #[ink(message)]
+        pub fn set_reward_frozen(&mut self, user: AccountId, frozen: bool) -> Result<(), Error> {
+            self.ensure_owner()?;
+
+            // Settle under the current state so the freeze boundary is exact
+            if let Some(mut info) = self.stakers.get(user) {
+                self._settle_reward(&user, &mut info)?;
+                self.stakers.insert(user, &info);
+            }
+
+            if frozen {
+                self.reward_frozen.insert(user, &true);
+            } else {
+                self.reward_frozen.remove(user);
+            }
+            self.env().emit_event(RewardFreezeUpdated { user, frozen });
+            Ok(())
+        }
+
+        /// Distribute future deposits by stake × time held since the previous deposit
+        /// (true) or by instantaneous stake (false). Takes effect from the next deposit. Only owner.
+        #[ink(message)]
+        pub fn set_time_weighted_rewards(&mut self, enabled: bool) -> Result<(), Error> {
+            self.ensure_owner()?;
+            self.time_weighted_rewards = enabled;
+            self.env().emit_event(AdminUpdated {
+                name: "TimeWeightedRewards".into(),
+            });
+            Ok(())
+        }
+
+        /// Reserve `locked_bps` of every deposit for positions with a lock tier, split among
+        /// them by effective stake; the rest goes to unlocked positions only (0 = one shared
+        /// pool, the default). Turning the sub-pool on or off is only possible while no
+        /// position is locked, since it moves locked positions between accumulators. Only owner.
+        #[ink(message)]
+        pub fn set_locked_pool_bps(&mut self, locked_bps: u16) -> Result<(), Error> {
+            self.ensure_owner()?;
+            let toggles = (locked_bps == 0) != (self.locked_pool_bps == 0);
+            if locked_bps > 10_000 || (toggles && self.total_locked_effective_staked > 0) {
+                return Err(Error::InvalidConfig);
+            }
+            self._accrue_stream();
+            self.locked_pool_bps = locked_bps;
+            self.env().emit_event(AdminUpdated {
+                name: "LockedPoolBps".into(),
+            });
+            Ok(())
+        }
+
+        /// Limit reward claims to `max_claims` per staker per `window_ms` (0 = unlimited).
+        /// Blocked claims lose nothing: rewards keep accruing until the window resets. Only owner.
+        #[ink(message)]
+        pub fn set_claim_frequency(&mut self, max_claims: u32, window_ms: u64) -> Result<(), Error> {
+            self.ensure_owner()?;
+            if max_claims > 0 && window_ms == 0 {
+                return Err(Error::InvalidConfig);
+            }
+            self.max_claims_per_window = max_claims;
+            self.claim_window_ms = window_ms;
+            self.env().emit_event(AdminUpdated {
+                name: "ClaimFrequency".into(),
+            });
+            Ok(())
+        }
+
+        /// Auto-claim a staker's rewards on their next stake or partial unstake once
+        /// pending rewards exceed `max` (0 = unlimited, the default). Only owner.
+        #[ink(message)]
+        pub fn set_max_pending_rewards(&mut self, max: Balance) -> Result<(), Error> {
+            self.ensure_owner()?;
+            self.max_pending_rewards = max;
+            self.env().emit_event(AdminUpdated {
+                name: "MaxPendingRewards".into(),
+            });
+            Ok(())
+        }
+
+        /// Set the swap router `claim_and_notify` forwards claimed LUSDT to
+        /// (None = plain claim). Only owner.
+        #[ink(message)]
+        pub fn set_auto_compound_target(&mut self, target: Option<AccountId>) -> Result<(), Error> {
+            self.ensure_owner()?;
+            self.auto_compound_target = target;
+            self.env().emit_event(AdminUpdated {
+                name: "AutoCompoundTarget".into(),
+            });
+            Ok(())
+        }
+
+        /// Set how deposits are released: `instant_bps` at once (10000 = all, the default)
+        /// and the rest linearly over `duration_ms`. Applies to future deposits. Only owner.
+        #[ink(message)]
+        pub fn set_reward_split(&mut self, instant_bps: u16, duration_ms: u64) -> Result<(), Error> {
+            self.ensure_owner()?;
+            if instant_bps > 10_000 || duration_ms == 0 {
+                return Err(Error::InvalidConfig);
+            }
+            self.instant_bps = instant_bps;
+            self.reward_duration_ms = duration_ms;
+            self.env().emit_event(AdminUpdated {
+                name: "RewardSplit".into(),
+            });
+            Ok(())
+        }
+
+        /// Set the EMA smoothing factor (1-10000 bps). Higher reacts faster. Only owner.
+        #[ink(message)]
+        pub fn set_ema_alpha_bps(&mut self, alpha_bps: u16) -> Result<(), Error> {
+            self.ensure_owner()?;
+            if alpha_bps == 0 || alpha_bps > 10_000 {
+                return Err(Error::InvalidConfig);
+            }
+            self.ema_alpha_bps = alpha_bps;
+            self.env().emit_event(AdminUpdated {
+                name: "EmaAlpha".into(),
+            });
+            Ok(())
+        }
+
+        /// Update minimum stake requirement. Only owner.
+        #[ink(message)]
+        pub fn set_min_stake(&mut self, new_min: Balance) -> Result<(), Error> {
+            self.ensure_owner()?;
+            self.min_stake = new_min;
+            self.env().emit_event(AdminUpdated {
+                name: "MinStake".into(),
+            });
+            Ok(())
+        }
+
+        /// Configure LUSDT → LUNES buybacks. A non-zero `buyback_bps` requires both a
+        /// router and a BurnEngine; pass 0 to distribute deposits fully to stakers. Only owner.
+        #[ink(message)]
+        pub fn set_buyback_config(
+            &mut self,
+            buyback_bps: u16,
+            swap_router: Option<AccountId>,
+            burn_engine: Option<AccountId>,
+        ) -> Result<(), Error> {
+            self.ensure_owner()?;
+            if buyback_bps > 10_000
+                || (buyback_bps > 0 && (swap_router.is_none() || burn_engine.is_none()))
+            {
+                return Err(Error::InvalidConfig);
+            }
+            self.buyback_bps = buyback_bps;
+            self.swap_router = swap_router;
+            self.burn_engine = burn_engine;
+            self.env().emit_event(AdminUpdated {
+                name: "BuybackConfig".into(),
+            });
+            Ok(())
+        }
+
+        /// Set the price bound for router swaps: `reference_rate` is the expected LUNES per
+        /// LUSDT in raw units scaled by 1e18, and swaps returning more than `max_slippage_bps`
+        /// below it are rejected. Keep the rate current; a rate of 0 halts swaps. Only owner.
+        #[ink(message)]
+        pub fn set_swap_slippage(&mut self, reference_rate: Balance, max_slippage_bps: u16) -> Result<(), Error> {
+            self.ensure_owner()?;
+            if max_slippage_bps > 10_000 {
+                return Err(Error::InvalidConfig);
+            }
+            self.swap_reference_rate = reference_rate;
+            self.max_swap_slippage_bps = max_slippage_bps;
+            self.env().emit_event(AdminUpdated {
+                name: "SwapSlippage".into(),
+            });
+            Ok(())
+        }
+
+        /// Set the governance contract allowed to call `snapshot` (None = owner only). Only owner.
+        #[ink(message)]
+        pub fn set_governance(&mut self, governance: Option<AccountId>) -> Result<(), Error> {
+            self.ensure_owner()?;
+            self.governance = governance;
+            self.env().emit_event(AdminUpdated {
+                name: "Governance".into(),
+            });
+            Ok(())
+        }
+
+        /// Take a voting-power snapshot of `total_staked` and every stake, returning its id.
+        /// Per-staker stakes are recorded lazily on their next change. Owner or governance.
+        #[ink(message)]
+        pub fn snapshot(&mut self) -> Result<u64, Error> {
+            let caller = self.env().caller();
+            if caller != self.owner && Some(caller) != self.governance {
+                return Err(Error::Unauthorized);
+            }
+            self._take_snapshot()
+        }
+
+        fn _take_snapshot(&mut self) -> Result<u64, Error> {
+            let snapshot_id = self.current_snapshot_id.checked_add(1).ok_or(Error::ArithmeticOverflow)?;
+            self.current_snapshot_id = snapshot_id;
+            self.snapshot_total_staked.insert(snapshot_id, &self.total_staked);
+            self.env().emit_event(SnapshotTaken {
+                snapshot_id,
+                total_staked: self.total_staked,
+            });
+            Ok(snapshot_id)
+        }
+
+        /// Set the minimum stake for creating governance proposals. Only owner.
+        #[ink(message)]
+        pub fn set_proposal_threshold(&mut self, threshold: Balance) -> Result<(), Error> {
+            self.ensure_owner()?;
+            self.proposal_threshold = threshold;
+            self.env().emit_event(AdminUpdated {
+                name: "ProposalThreshold".into(),
+            });
+            Ok(())
+        }
+
+        /// Set a lock tier's own minimum stake (0 clears it back to the global minimum).
+        /// Unlocked stakes always use the global `min_stake`. Only owner.
+        #[ink(message)]
+        pub fn set_tier_min_stake(&mut self, tier: u8, new_min: Balance) -> Result<(), Error> {
+            self.ensure_owner()?;
+            if new_min == 0 {
+                self.tier_min_stake.remove(tier);
+            } else {
+                self.tier_min_stake.insert(tier, &new_min);
+            }
+            self.env().emit_event(AdminUpdated {
+                name: "TierMinStake".into(),
+            });
+            Ok(())
+        }
+
+        /// Open an insurance claim window for a documented shortfall. The current
+        /// insurance balance becomes the payout pool, split pro-rata by stake at this
+        /// moment (recorded as a stake snapshot). Only owner.
+        ///
+        /// @param justification Public reference to the shortfall (incident report, etc.).
+        #[ink(message)]
+        pub fn open_insurance_claims(&mut self, justification: ink::prelude::string::String) -> Result<u32, Error> {
+            self.ensure_owner()?;
+            if self.insurance_balance == 0 || self.total_staked == 0 || justification.is_empty() {
+                return Err(Error::InvalidConfig);
+            }
+
+            self.insurance_round = self.insurance_round.checked_add(1).ok_or(Error::ArithmeticOverflow)?;
+            self.insurance_claim_enabled = true;
+            self.insurance_payout_pool = self.insurance_balance;
+            self.insurance_total_staked = self.total_staked;
+            self.insurance_snapshot_id = self._take_snapshot()?;
+
+            self.env().emit_event(InsuranceClaimsOpened {
+                round: self.insurance_round,
+                payout_pool: self.insurance_payout_pool,
+                total_staked_snapshot: self.insurance_total_staked,
+                justification,
+            });
+            Ok(self.insurance_round)
+        }
+
+        /// Close the insurance claim window. Unclaimed funds stay in the insurance balance. Only owner.
+        #[ink(message)]
+        pub fn close_insurance_claims(&mut self) -> Result<(), Error> {
+            self.ensure_owner()?;
+            self.insurance_claim_enabled = false;
+            self.env().emit_event(AdminUpdated {
+                name: "InsuranceClaimsClosed".into(),
+            });
+            Ok(())
+        }
+
+        /// Create an additional reward pool and return its id. Only owner.
+        #[ink(message)]
+        pub fn create_reward_pool(&mut self) -> Result<u32, Error> {
+            self.ensure_owner()?;
+            let pool_id = self.reward_pool_count.checked_add(1).ok_or(Error::ArithmeticOverflow)?;
+            self.reward_pool_count = pool_id;
+            self.reward_pools.insert(pool_id, &RewardPool::default());
+            self.env().emit_event(RewardPoolCreated { pool_id });
+            Ok(pool_id)
+        }
+
+        /// Recover a PSP22 token sent here by mistake. Staked LUNES and reward
+        /// LUSDT are protected and can never be moved by the owner. Only owner.
+        #[ink(message)]
+        pub fn rescue_foreign_token(&mut self, token: AccountId, to: AccountId, amount: Balance) -> Result<(), Error> {
+            self.ensure_owner()?;
+            if token == self.lunes_token || token == self.lusdt_token {
+                return Err(Error::ProtectedToken);
+            }
+            self.token_transfer(token, to, amount)
+                .map_err(|_| Error::TokenTransferFailed)?;
+            self.env().emit_event(TokenRescued { token, to, amount });
+            Ok(())
+        }
+
+        /// Upgradeable contract: set new code hash. Only owner.
+        #[ink(message)]
+        pub fn set_code(&mut self, code_hash: Hash) -> Result<(), Error> {
+            self.ensure_owner()?;
+            self.env().set_code_hash(&code_hash).unwrap_or_else(|err| {
+                panic!(
+                    "Failed to `set_code_hash` to {:?} due to {:?}",
+                    code_hash, err
+                )
+            });
+            Ok(())
+        }
+
+        // ═══════════════════════════════════════════════════════════════
+        // MIGRATION — move staker state to a new deployment
+        // ═══════════════════════════════════════════════════════════════
+
+        /// Read a staker's record for migration, with rewards earned so far settled into
+        /// `pending_rewards`. Owner only, and only while paused so the state can't change
+        /// between export and import.
+        #[ink(message)]
+        pub fn export_staker(&self, user: AccountId) -> Result<StakerInfo, Error> {
+            self.ensure_owner()?;
+            if !self.paused {
+                return Err(Error::NotPaused);
+            }
+            let mut info = self.stakers.get(user).ok_or(Error::NoActiveStake)?;
+            self._update_reward(&user, &mut info)?;
+            Ok(info)
+        }
+
+        /// Set the account allowed to call `migrate_in` (None disables imports). Only owner.
+        #[ink(message)]
+        pub fn set_migration_source(&mut self, source: Option<AccountId>) -> Result<(), Error> {
+            self.ensure_owner()?;
+            self.migration_source = source;
+            self.env().emit_event(AdminUpdated {
+                name: "MigrationSource".into(),
+            });
+            Ok(())
+        }
+
+        /// Account allowed to call `migrate_in`, if any.
+        #[ink(message)]
+        pub fn get_migration_source(&self) -> Option<AccountId> {
+            self.migration_source
+        }
+
+        /// Import a staker exported from the previous deployment. Stake, lock and pending
+        /// rewards carry over; reward snapshots restart at this contract's accumulators.
+        /// `total_staked` and `staker_count` grow with each import, and each staker can be
+        /// imported once. Pending rewards count as deposited, so they stay liabilities until
+        /// claimed. The LUNES (and LUSDT for pending rewards) must be moved separately.
+        /// Only the `migration_source`.
+        #[ink(message)]
+        pub fn migrate_in(&mut self, user: AccountId, info: StakerInfo) -> Result<(), Error> {
+            if self.migration_source != Some(self.env().caller()) {
+                return Err(Error::Unauthorized);
+            }
+            let existing = self.stakers.get(user).map(|i| i.amount).unwrap_or(0);
+            if self.migrated.get(user).unwrap_or(false) || existing > 0 {
+                return Err(Error::AlreadyMigrated);
+            }
+
+            self._accrue_stream();
+            self._accrue_total_stake_time();
+            self._checkpoint_stake(user, 0);
+
+            let mut info = info;
+            info.reward_per_token_paid = self.reward_per_token_stored;
+            info.locked_reward_per_token_paid = self.locked_reward_per_token_stored;
+            info.reward_per_token_paid_lunes = self.reward_per_token_stored_lunes;
+            info.tw_period = self.tw_period;
+            info.tw_stake_ms = 0;
+            info.tw_last_update = self.env().block_timestamp();
+
+            self.total_staked = self.total_staked.checked_add(info.amount).ok_or(Error::ArithmeticOverflow)?;
+            // Imported pending rewards are owed by this contract from now on
+            self.total_rewards_deposited = self
+                .total_rewards_deposited
+                .checked_add(info.pending_rewards)
+                .ok_or(Error::ArithmeticOverflow)?;
+            self.total_lunes_rewards_deposited = self
+                .total_lunes_rewards_deposited
+                .checked_add(info.pending_rewards_lunes)
+                .ok_or(Error::ArithmeticOverflow)?;
+            self._reweight(&StakerInfo::default(), &info);
+            if info.amount > 0 {
+                self.staker_count = self.staker_count.saturating_add(1);
+                self._index_staker(user);
+            }
+            self.stakers.insert(user, &info);
+            self.migrated.insert(user, &true);
+
+            self.env().emit_event(StakerMigrated {
+                user,
+                amount: info.amount,
+                total_staked: self.total_staked,
+            });
+            Ok(())
+        }
+
+        // ─── Token Interactions ──────────────────────────────────────
+
+        /// PSP22 `transfer` from this contract. The off-chain test environment
+        /// cannot invoke other contracts, so unit tests use an in-memory ledger.
+        #[cfg(not(test))]
+        fn token_transfer(&self, token: AccountId, to: AccountId, amount: Balance) -> Result<(), ink::LangError> {
+            let mut psp22: ink::contract_ref!(PSP22) = token.into();
+            psp22.transfer(to, amount)
+        }
+
+        /// PSP22 `transfer_from` into this contract (requires prior approval).
+        #[cfg(not(test))]
+        fn token_transfer_from(&self, token: AccountId, from: AccountId, amount: Balance) -> Result<(), ink::LangError> {
+            let mut psp22: ink::contract_ref!(PSP22) = token.into();
+            psp22.transfer_from(from, self.env().account_id(), amount)
+        }
+
+        /// PSP22 `balance_of` query.
+        #[cfg(not(test))]
+        fn token_balance_of(&self, token: AccountId, owner: AccountId) -> Balance {
+            let psp22: ink::contract_ref!(PSP22) = token.into();
+            psp22.balance_of(owner)
+        }
+
+        #[cfg(test)]
+        fn token_balance_of(&self, token: AccountId, owner: AccountId) -> Balance {
+            mock_psp22::balance_of(token, owner)
+        }
+
+        #[cfg(test)]
+        fn token_transfer(&self, token: AccountId, to: AccountId, amount: Balance) -> Result<(), ink::LangError> {
+            mock_psp22::transfer(token, self.env().account_id(), to, amount)
+        }
+
+        #[cfg(test)]
+        fn token_transfer_from(&self, token: AccountId, from: AccountId, amount: Balance) -> Result<(), ink::LangError> {
+            mock_psp22::transfer(token, from, self.env().account_id(), amount)
+        }
+
+        /// Call `on_rewards_claimed(amount, data)` on `target` if it is a contract.
+        /// Returns whether the callback ran and succeeded.
+        #[cfg(not(test))]
+        fn notify_claim_callback(&self, target: AccountId, amount: Balance, data: Vec<u8>) -> bool {
+            use ink::env::call::{build_call, ExecutionInput, Selector};
+            if !self.env().is_contract(&target) {
+                return false;
+            }
+            matches!(
+                build_call::<Environment>()
+                    .call(target)
+                    .exec_input(
+                        ExecutionInput::new(Selector::new(ink::selector_bytes!("on_rewards_claimed")))
+                            .push_arg(amount)
+                            .push_arg(data),
+                    )
+                    .returns::<()>()
+                    .try_invoke(),
+                Ok(Ok(()))
+            )
+        }
+
+        #[cfg(test)]
+        fn notify_claim_callback(&self, target: AccountId, amount: Balance, data: Vec<u8>) -> bool {
+            mock_strategy::on_rewards_claimed(target, amount, data)
+        }
+
+        /// Minimum LUNES a swap of `amount_in` LUSDT must return: the reference rate less
+        /// `max_swap_slippage_bps`. Fails while no reference rate is configured.
+        fn swap_min_out(&self, amount_in: Balance) -> Result<Balance, Error> {
+            if self.swap_reference_rate == 0 {
+                return Err(Error::SlippageExceeded);
+            }
+            let expected = amount_in
+                .checked_mul(self.swap_reference_rate)
+                .ok_or(Error::ArithmeticOverflow)?
+                / PRECISION;
+            Ok(expected
+                .checked_mul(10_000 - self.max_swap_slippage_bps as u128)
+                .ok_or(Error::ArithmeticOverflow)?
+                / 10_000)
+        }
+
+        /// Swap LUSDT already held by the router into LUNES sent to `to`. The router
+        /// enforces `min_out`; callers re-check the returned amount.
+        #[cfg(not(test))]
+        fn router_swap(
+            &self,
+            router: AccountId,
+            amount_in: Balance,
+            min_out: Balance,
+            to: AccountId,
+        ) -> Result<Balance, ink::LangError> {
+            let mut swap: ink::contract_ref!(SwapRouter) = router.into();
+            swap.swap_exact_input(self.lusdt_token, self.lunes_token, amount_in, min_out, to)
+        }
+
+        #[cfg(test)]
+        fn router_swap(
+            &self,
+            router: AccountId,
+            amount_in: Balance,
+            _min_out: Balance,
+            to: AccountId,
+        ) -> Result<Balance, ink::LangError> {
+            mock_router::swap(router, self.lunes_token, amount_in, to)
+        }
+
+        // ─── Internal Helpers ────────────────────────────────────────
+
+        fn ensure_owner(&self) -> Result<(), Error> {
+            if self.env().caller() != self.owner {
+                Err(Error::Unauthorized)
+            } else {
+                Ok(())
+            }
+        }
+
+        fn ensure_not_locked(&mut self) -> Result<(), Error> {
+            if self.locked {
+                return Err(Error::ReentrancyDetected);
+            }
+            self.locked = true;
+            Ok(())
+        }
+
+        fn unlock(&mut self) {
+            self.locked = false;
+        }
+
+        fn ensure_not_paused(&self) -> Result<(), Error> {
+            if self.paused {
+                Err(Error::ContractPaused)
+            } else {
+                Ok(())
+            }
+        }
+
+        fn ensure_authorized_depositor(&self) -> Result<(), Error> {
+            let caller = self.env().caller();
+            if caller == self.owner {
+                return Ok(());
+            }
+            if let Some(depositor) = self.authorized_depositor {
+                if caller == depositor {
+                    return Ok(());
+                }
+            }
+            Err(Error::Unauthorized)
+        }
+
+        fn ensure_reward_deposits_open(&self) -> Result<(), Error> {
+            if self.reward_deposits_paused {
+                Err(Error::RewardDepositsPaused)
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    // ─── Off-chain PSP22 Ledger (tests only) ────────────────────────
+
+    #[cfg(test)]
+    pub(crate) mod mock_psp22 {
+        use super::{AccountId, Balance};
+        use std::cell::RefCell;
+        use std::collections::HashMap;
+
+        thread_local! {
+            static BALANCES: RefCell<HashMap<(AccountId, AccountId), Balance>> = RefCell::new(HashMap::new());
+            static REJECT_NEXT: RefCell<Vec<AccountId>> = const { RefCell::new(Vec::new()) };
+        }
+
+        /// Make the next `transfer` of `token` fail, whatever the balances.
+        pub fn reject_next_transfer(token: AccountId) {
+            REJECT_NEXT.with(|r| r.borrow_mut().push(token));
+        }
+
+        pub fn set_balance(token: AccountId, owner: AccountId, amount: Balance) {
+            BALANCES.with(|b| b.borrow_mut().insert((token, owner), amount));
+        }
+
+        pub fn balance_of(token: AccountId, owner: AccountId) -> Balance {
+            BALANCES.with(|b| *b.borrow().get(&(token, owner)).unwrap_or(&0))
+        }
+
+        pub fn transfer(token: AccountId, from: AccountId, to: AccountId, amount: Balance) -> Result<(), ink::LangError> {
+            let rejected = REJECT_NEXT.with(|r| {
+                let mut tokens = r.borrow_mut();
+                let pos = tokens.iter().position(|t| *t == token);
+                pos.map(|i| tokens.remove(i)).is_some()
+            });
+            if rejected {
+                return Err(ink::LangError::CouldNotReadInput);
+            }
+            let from_balance = balance_of(token, from);
+            if from_balance < amount {
+                return Err(ink::LangError::CouldNotReadInput);
+            }
+            set_balance(token, from, from_balance - amount);
+            set_balance(token, to, balance_of(token, to) + amount);
+            Ok(())
+        }
+    }
+
+    /// In-memory swap router for unit tests: pays out LUNES from its own
+    /// mock balance at a fixed rate per LUSDT received.
+    #[cfg(test)]
+    pub(crate) mod mock_router {
+        use super::{mock_psp22, AccountId, Balance};
+        use std::cell::Cell;
+
+        thread_local! {
+            static LUNES_PER_LUSDT: Cell<Balance> = const { Cell::new(1) };
+        }
+
+        pub fn set_rate(lunes_per_lusdt: Balance) {
+            LUNES_PER_LUSDT.with(|r| r.set(lunes_per_lusdt));
+        }
+
+        pub fn swap(router: AccountId, token_out: AccountId, amount_in: Balance, to: AccountId) -> Result<Balance, ink::LangError> {
+            let amount_out = amount_in * LUNES_PER_LUSDT.with(|r| r.get());
+            mock_psp22::transfer(token_out, router, to, amount_out)?;
+            Ok(amount_out)
+        }
+    }
+
+    /// Stand-in for strategy contracts implementing `on_rewards_claimed` in unit tests.
+    #[cfg(test)]
+    pub(crate) mod mock_strategy {
+        use super::{AccountId, Balance};
+        use std::cell::RefCell;
+        use std::collections::HashMap;
+
+        thread_local! {
+            /// Registered strategies and whether their callback reverts.
+            static STRATEGIES: RefCell<HashMap<AccountId, bool>> = RefCell::new(HashMap::new());
+            static RECEIVED: RefCell<Vec<(AccountId, Balance, Vec<u8>)>> = const { RefCell::new(Vec::new()) };
+        }
+
+        pub fn register(strategy: AccountId, reverts: bool) {
+            STRATEGIES.with(|s| s.borrow_mut().insert(strategy, reverts));
+        }
+
+        pub fn received() -> Vec<(AccountId, Balance, Vec<u8>)> {
+            RECEIVED.with(|r| r.borrow().clone())
+        }
+
+        pub fn on_rewards_claimed(target: AccountId, amount: Balance, data: Vec<u8>) -> bool {
+            match STRATEGIES.with(|s| s.borrow().get(&target).copied()) {
+                Some(false) => {
+                    RECEIVED.with(|r| r.borrow_mut().push((target, amount, data)));
+                    true
+                }
+                // Reverting contract, or a plain account (not called)
+                _ => false,
+            }
+        }
+    }
+
+    // ─── Unit Tests ─────────────────────────────────────────────────
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use ink::env::{
+            test::{set_caller, DefaultAccounts},
+            DefaultEnvironment,
+        };
+
+        /// Address the contract under test runs at (holds staked LUNES / LUSDT rewards).
+        const CONTRACT: [u8; 32] = [0xAA; 32];
+
+        fn setup_accounts() -> DefaultAccounts<DefaultEnvironment> {
+            ink::env::test::default_accounts::<DefaultEnvironment>()
+        }
+
+        fn create_contract() -> (StakingManager, DefaultAccounts<DefaultEnvironment>) {
+            let accounts = setup_accounts();
+            set_caller::<DefaultEnvironment>(accounts.alice);
+            ink::env::test::set_callee::<DefaultEnvironment>(AccountId::from(CONTRACT));
+
+            let min_stake: Balance = 100_000_000_000_000_000; // 100k LUNES (12 decimals)
+            let contract = StakingManager::new(
+                accounts.bob,     // lunes_token (mock)
+                accounts.charlie, // lusdt_token (mock)
+                min_stake,
+            );
+
+            (contract, accounts)
+        }
+
+        /// Mint `amount` mock LUNES to `user` and stake it.
+        fn stake_as(contract: &mut StakingManager, user: AccountId, amount: Balance) {
+            let lunes = contract.lunes_token;
+            mock_psp22::set_balance(lunes, user, mock_psp22::balance_of(lunes, user) + amount);
+            set_caller::<DefaultEnvironment>(user);
+            contract.stake(amount).unwrap();
+        }
+
+        /// Send `amount` mock LUSDT to the contract and notify it as the owner.
+        fn fund_rewards(contract: &mut StakingManager, amount: Balance) {
+            let lusdt = contract.lusdt_token;
+            let this = AccountId::from(CONTRACT);
+            mock_psp22::set_balance(lusdt, this, mock_psp22::balance_of(lusdt, this) + amount);
+            set_caller::<DefaultEnvironment>(contract.owner);
+            contract._notify_reward_amount(amount).unwrap();
+        }
+
+        #[ink::test]
+        fn rewards_deposited_event_carries_pool_stats() {
+            type Event = <StakingManager as ::ink::reflect::ContractEventBase>::Type;
+
+            let (mut contract, accounts) = create_contract();
+            let amount = contract.get_min_stake();
+            stake_as(&mut contract, accounts.django, amount);
+            stake_as(&mut contract, accounts.eve, amount * 2);
+            fund_rewards(&mut contract, 600_000);
+            fund_rewards(&mut contract, 400_000);
+
+            let deposits: Vec<RewardsDeposited> = ink::env::test::recorded_events()
+                .filter_map(|e| match <Event as scale::Decode>::decode(&mut &e.data[..]) {
+                    Ok(Event::RewardsDeposited(d)) => Some(d),
+                    _ => None,
+                })
+                .collect();
+
+            assert_eq!(deposits.len(), 2);
+            for d in deposits.iter() {
+                assert_eq!(d.total_staked_at_deposit, amount * 3);
+                assert_eq!(d.staker_count_at_deposit, 2);
+            }
+            assert_eq!(deposits[0].amount, 600_000);
+            assert_eq!(deposits[0].cumulative_deposited, 600_000);
+            assert_eq!(deposits[1].cumulative_deposited, 1_000_000);
+            assert_eq!(deposits[1].cumulative_deposited, contract.get_total_rewards_deposited());
+
+            assert_eq!(deposits[0].reward_per_token_delta, 600_000 * PRECISION / (amount * 3));
+            assert_eq!(deposits[1].reward_per_token_delta, 400_000 * PRECISION / (amount * 3));
+            assert_eq!(
+                deposits[1].new_reward_per_token,
+                deposits[0].new_reward_per_token + deposits[1].reward_per_token_delta
+            );
+        }
+
+        #[ink::test]
+        fn constructor_works() {
+            let (contract, accounts) = create_contract();
+            assert_eq!(contract.get_owner(), accounts.alice);
+            assert_eq!(contract.get_total_staked(), 0);
+            assert_eq!(contract.get_staker_count(), 0);
+            assert_eq!(contract.get_min_stake(), 100_000_000_000_000_000);
+            assert!(!contract.is_paused());
+        }
+
+        #[ink::test]
+        fn staker_info_default() {
+            let (contract, accounts) = create_contract();
+            let info = contract.get_staker_info(accounts.bob);
+            assert_eq!(info.amount, 0);
+            assert_eq!(info.pending_rewards, 0);
+        }
+
+        #[ink::test]
+        fn admin_functions_require_owner() {
+            let (mut contract, accounts) = create_contract();
+
+            // Non-owner cannot pause
+            set_caller::<DefaultEnvironment>(accounts.bob);
+            assert_eq!(contract.pause(), Err(Error::Unauthorized));
+
+            // Owner can pause
+            set_caller::<DefaultEnvironment>(accounts.alice);
+            assert_eq!(contract.pause(), Ok(()));
+            assert!(contract.is_paused());
+
+            // Owner can unpause
+            assert_eq!(contract.unpause(), Ok(()));
+            assert!(!contract.is_paused());
+        }
+
+        #[ink::test]
+        fn set_authorized_depositor() {
+            let (mut contract, accounts) = create_contract();
+
+            assert_eq!(contract.get_authorized_depositor(), None);
+
+            set_caller::<DefaultEnvironment>(accounts.alice);
+            assert_eq!(
+                contract.set_authorized_depositor(accounts.django),
+                Ok(())
+            );
+            assert_eq!(
+                contract.get_authorized_depositor(),
+                Some(accounts.django)
+            );
+        }
+
+        #[ink::test]
+        fn set_cooldown() {
+            let (mut contract, accounts) = create_contract();
+
+            set_caller::<DefaultEnvironment>(accounts.alice);
+            assert_eq!(contract.set_cooldown(86_400_000), Ok(())); // 24 hours
+            assert_eq!(contract.get_cooldown_ms(), 86_400_000);
+        }
+
+        #[ink::test]
+        fn lunes_rewards_are_tracked_independently() {
+            let (mut contract, accounts) = create_contract();
+            let min = contract.get_min_stake();
+            let lunes = contract.lunes_token;
+            stake_as(&mut contract, accounts.django, min);
+            stake_as(&mut contract, accounts.eve, min * 3);
+
+            // Unauthorized depositors are rejected like for LUSDT
+            mock_psp22::set_balance(lunes, accounts.frank, 4_000_000);
+            set_caller::<DefaultEnvironment>(accounts.frank);
+            assert_eq!(contract.deposit_lunes_rewards(4_000_000), Err(Error::Unauthorized));
+
+            mock_psp22::set_balance(lunes, contract.owner, 4_000_000);
+            set_caller::<DefaultEnvironment>(contract.owner);
+            assert_eq!(contract.deposit_lunes_rewards(4_000_000), Ok(()));
+            fund_rewards(&mut contract, 800_000);
+
+            // Both tracks split 1:3, and each is claimed on its own
+            assert_eq!(contract.get_pending_rewards_lunes(accounts.django), 1_000_000);
+            assert_eq!(contract.get_pending_rewards_lunes(accounts.eve), 3_000_000);
+            assert_eq!(contract.get_pending_rewards(accounts.django), 200_000);
+            assert_eq!(contract.get_pending_rewards(accounts.eve), 600_000);
+
+            set_caller::<DefaultEnvironment>(accounts.django);
+            assert_eq!(contract.claim_lunes_rewards(), Ok(()));
+            assert_eq!(mock_psp22::balance_of(lunes, accounts.django), 1_000_000);
+            assert_eq!(contract.get_pending_rewards(accounts.django), 200_000);
+            assert_eq!(contract.claim_lunes_rewards(), Err(Error::NoRewardsToClaim));
+
+            set_caller::<DefaultEnvironment>(accounts.eve);
+            assert_eq!(contract.claim_rewards(), Ok(()));
+            assert_eq!(contract.get_pending_rewards_lunes(accounts.eve), 3_000_000);
+            assert_eq!(contract.claim_lunes_rewards(), Ok(()));
+            assert_eq!(mock_psp22::balance_of(lunes, accounts.eve), 3_000_000);
+            assert_eq!(contract.get_lunes_reward_totals(), (4_000_000, 4_000_000));
+
+            // Staked principal is untouched by the reward payouts
+            assert_eq!(mock_psp22::balance_of(lunes, AccountId::from(CONTRACT)), min * 4);
+        }
+
+        #[ink::test]
+        fn poke_settles_pending_rewards() {
+            let (mut contract, accounts) = create_contract();
+            let min = contract.get_min_stake();
+            stake_as(&mut contract, accounts.django, min);
+            stake_as(&mut contract, accounts.eve, min * 3);
+            fund_rewards(&mut contract, 1_000_000);
+
+            let expected = [
+                contract.get_pending_rewards(accounts.django),
+                contract.get_pending_rewards(accounts.eve),
+            ];
+            assert!(expected[0] > 0 && expected[1] > expected[0]);
+            assert_eq!(contract.get_staker_info(accounts.django).pending_rewards, 0);
+
+            set_caller::<DefaultEnvironment>(accounts.frank);
+            assert_eq!(contract.poke(vec![accounts.django, accounts.eve, accounts.frank]), Ok(2));
+            assert_eq!(contract.get_staker_info(accounts.django).pending_rewards, expected[0]);
+            assert_eq!(contract.get_staker_info(accounts.eve).pending_rewards, expected[1]);
+            assert_eq!(contract.get_pending_rewards(accounts.django), expected[0]);
+            assert_eq!(contract.get_pending_rewards(accounts.eve), expected[1]);
+
+            assert_eq!(contract.poke(vec![]), Err(Error::InvalidBatchSize));
+            assert_eq!(contract.poke(vec![accounts.eve; MAX_BATCH_SIZE + 1]), Err(Error::InvalidBatchSize));
+        }
+
+        #[ink::test]
+        fn partial_reward_claims() {
+            let (mut contract, accounts) = create_contract();
+            let lusdt = contract.lusdt_token;
+            let min = contract.get_min_stake();
+            stake_as(&mut contract, accounts.django, min);
+            fund_rewards(&mut contract, 1_000_000);
+            let pending = contract.get_pending_rewards(accounts.django);
+            assert!(pending > 400_000);
+
+            set_caller::<DefaultEnvironment>(accounts.django);
+            assert_eq!(contract.claim_rewards_amount(0), Err(Error::ZeroAmount));
+            assert_eq!(contract.claim_rewards_amount(pending + 1), Err(Error::NoRewardsToClaim));
+
+            assert!(contract.claim_rewards_amount(400_000).is_ok());
+            assert_eq!(mock_psp22::balance_of(lusdt, accounts.django), 400_000);
+            assert_eq!(contract.get_pending_rewards(accounts.django), pending - 400_000);
+
+            assert!(contract.claim_rewards_amount(pending - 400_000).is_ok());
+            assert_eq!(mock_psp22::balance_of(lusdt, accounts.django), pending);
+            assert_eq!(contract.get_pending_rewards(accounts.django), 0);
+            assert_eq!(contract.claim_rewards_amount(1), Err(Error::NoRewardsToClaim));
+        }
+
+        #[ink::test]
+        fn token_addresses_change_only_when_empty() {
+            let (mut contract, accounts) = create_contract();
+            let new_lunes = AccountId::from([0xCD; 32]);
+            let new_lusdt = AccountId::from([0xCE; 32]);
+
+            set_caller::<DefaultEnvironment>(accounts.django);
+            assert_eq!(contract.set_lunes_token(new_lunes), Err(Error::Unauthorized));
+
+            set_caller::<DefaultEnvironment>(accounts.alice);
+            assert!(contract.set_lunes_token(new_lunes).is_ok());
+            assert!(contract.set_lusdt_token(new_lusdt).is_ok());
+            assert_eq!(contract.get_token_addresses(), (new_lunes, new_lusdt));
+
+            stake_as(&mut contract, accounts.django, 100_000_000_000_000_000);
+            set_caller::<DefaultEnvironment>(accounts.alice);
+            assert_eq!(contract.set_lunes_token(accounts.bob), Err(Error::TokensInUse));
+            assert_eq!(contract.set_lusdt_token(accounts.charlie), Err(Error::TokensInUse));
+            assert_eq!(contract.get_token_addresses(), (new_lunes, new_lusdt));
+        }
+
+        #[ink::test]
+        fn reward_solvency_flags_shortfall() {
+            let (mut contract, accounts) = create_contract();
+            let lusdt = contract.lusdt_token;
+            let this = AccountId::from(CONTRACT);
+            assert_eq!(contract.get_reward_solvency(), (0, 0, true));
+
+            let min = contract.get_min_stake();
+            stake_as(&mut contract, accounts.django, min);
+            fund_rewards(&mut contract, 1_000_000);
+            assert_eq!(contract.get_reward_solvency(), (1_000_000, 1_000_000, true));
+
+            // Holdings drop below what's owed
+            mock_psp22::set_balance(lusdt, this, 600_000);
+            assert_eq!(contract.get_reward_solvency(), (600_000, 1_000_000, false));
+
+            // Claims reduce liabilities along with holdings
+            mock_psp22::set_balance(lusdt, this, 1_500_000);
+            set_caller::<DefaultEnvironment>(accounts.django);
+            contract.claim_rewards().unwrap();
+            assert_eq!(contract.get_reward_solvency(), (500_000, 0, true));
+        }
+
+        #[ink::test]
+        fn sync_rewards_distributes_direct_transfers() {
+            let (mut contract, accounts) = create_contract();
+            let min = contract.get_min_stake();
+            stake_as(&mut contract, accounts.django, min);
+            fund_rewards(&mut contract, 1_000_000);
+
+            // Nothing beyond what is already owed
+            set_caller::<DefaultEnvironment>(contract.owner);
+            assert_eq!(contract.sync_rewards(), Ok(0));
+
+            // LUSDT sent straight to the contract address sits uncounted until synced
+            let lusdt = contract.lusdt_token;
+            let this = AccountId::from(CONTRACT);
+            mock_psp22::set_balance(lusdt, this, mock_psp22::balance_of(lusdt, this) + 400_000);
+            set_caller::<DefaultEnvironment>(accounts.django);
+            assert_eq!(contract.sync_rewards(), Err(Error::Unauthorized));
+            set_caller::<DefaultEnvironment>(contract.owner);
+            assert_eq!(contract.sync_rewards(), Ok(400_000));
+            assert_eq!(contract.get_undistributed_rewards(), 1_400_000);
+
+            // Staked LUNES is a different token and never counts as surplus
+            assert_eq!(mock_psp22::balance_of(contract.lunes_token, this), min);
+            assert_eq!(contract.sync_rewards(), Ok(0));
+
+            set_caller::<DefaultEnvironment>(accounts.django);
+            contract.claim_rewards().unwrap();
+            assert_eq!(mock_psp22::balance_of(lusdt, accounts.django), 1_400_000);
+            assert_eq!(contract.get_reward_solvency(), (0, 0, true));
+        }
+
+        #[ink::test]
+        fn failed_unstake_payout_is_escrowed() {
+            let (mut contract, accounts) = create_contract();
+            let min = contract.get_min_stake();
+            stake_as(&mut contract, accounts.django, min);
+            fund_rewards(&mut contract, 1_000_000);
+
+            // The automatic LUSDT payout on unstake fails; the LUNES still come back
+            mock_psp22::reject_next_transfer(contract.lusdt_token);
+            set_caller::<DefaultEnvironment>(accounts.django);
+            assert_eq!(contract.unstake(), Ok(()));
+            let info = contract.get_staker_info(accounts.django);
+            assert_eq!((info.amount, info.pending_rewards), (0, 0));
+            assert_eq!(contract.get_escrowed_rewards(accounts.django), 1_000_000);
+            assert_eq!(contract.get_total_escrowed(), 1_000_000);
+            assert_eq!(mock_psp22::balance_of(contract.lusdt_token, accounts.django), 0);
+            assert_eq!(contract.claim_rewards(), Err(Error::NoRewardsToClaim));
+
+            // The user pulls the escrowed rewards with a retry
+            assert_eq!(contract.withdraw_escrow(), Ok(()));
+            assert_eq!(mock_psp22::balance_of(contract.lusdt_token, accounts.django), 1_000_000);
+            assert_eq!(contract.get_escrowed_rewards(accounts.django), 0);
+            assert_eq!(contract.get_total_escrowed(), 0);
+            assert_eq!(contract.get_total_rewards_claimed(), 1_000_000);
+            assert_eq!(contract.withdraw_escrow(), Err(Error::NoRewardsToClaim));
+        }
+
+        #[ink::test]
+        fn reentrant_calls_are_blocked() {
+            let (mut contract, accounts) = create_contract();
+            let min = contract.get_min_stake();
+            stake_as(&mut contract, accounts.django, min);
+            fund_rewards(&mut contract, 1_000_000);
+
+            // A malicious token re-entering during the claim transfer finds the lock held
+            contract.locked = true;
+            set_caller::<DefaultEnvironment>(accounts.django);
+            assert_eq!(contract.claim_rewards(), Err(Error::ReentrancyDetected));
+            assert_eq!(contract.unstake(), Err(Error::ReentrancyDetected));
+            assert_eq!(contract.unstake_amount(1), Err(Error::ReentrancyDetected));
+            assert_eq!(contract.stake(min), Err(Error::ReentrancyDetected));
+            assert_eq!(contract.stake_for_batch(vec![(accounts.eve, min)]), Err(Error::ReentrancyDetected));
+            assert_eq!(contract.poke(vec![accounts.django]), Err(Error::ReentrancyDetected));
+            assert_eq!(contract.stake_into_pool(1, min), Err(Error::ReentrancyDetected));
+            assert_eq!(contract.unstake_from_pool(1), Err(Error::ReentrancyDetected));
+            assert_eq!(contract.claim_pool_rewards(1), Err(Error::ReentrancyDetected));
+            set_caller::<DefaultEnvironment>(contract.owner);
+            assert_eq!(
+                StakingManagerApi::notify_reward_amount(&mut contract, 1_000),
+                Err(ProtocolError::Other)
+            );
+            assert_eq!(contract.deposit_pool_rewards(1, 1_000), Err(Error::ReentrancyDetected));
+            assert_eq!(contract.notify_pool_reward_amount(1, 1_000), Err(Error::ReentrancyDetected));
+            contract.locked = false;
+
+            // The lock is released after both failed and successful calls
+            set_caller::<DefaultEnvironment>(accounts.eve);
+            assert_eq!(contract.claim_rewards(), Err(Error::NoActiveStake));
+            set_caller::<DefaultEnvironment>(accounts.django);
+            assert_eq!(contract.claim_rewards(), Ok(()));
+            assert!(!contract.locked);
+            assert_eq!(mock_psp22::balance_of(contract.lusdt_token, accounts.django), 1_000_000);
+        }
+
+        #[ink::test]
+        fn stakers_migrate_to_new_deployment() {
+            let (mut old, accounts) = create_contract();
+            let min = old.get_min_stake();
+            stake_as(&mut old, accounts.django, min);
+            stake_as(&mut old, accounts.frank, min * 3);
+            fund_rewards(&mut old, 1_000_000);
+            set_caller::<DefaultEnvironment>(accounts.django);
+            old.claim_rewards().unwrap();
+
+            set_caller::<DefaultEnvironment>(accounts.alice);
+            assert_eq!(old.export_staker(accounts.django), Err(Error::NotPaused));
+            old.pause().unwrap();
+            set_caller::<DefaultEnvironment>(accounts.eve);
+            assert_eq!(old.export_staker(accounts.django), Err(Error::Unauthorized));
+            set_caller::<DefaultEnvironment>(accounts.alice);
+            let exported = [accounts.django, accounts.frank]
+                .map(|user| (user, old.export_staker(user).unwrap()));
+            assert_eq!(old.export_staker(accounts.eve), Err(Error::NoActiveStake));
+            let (old_total, old_count) = (old.get_total_staked(), old.get_staker_count());
+
+            // New deployment at a different address
+            ink::env::test::set_callee::<DefaultEnvironment>(AccountId::from([0xAB; 32]));
+            let mut new = StakingManager::new(accounts.bob, accounts.charlie, min);
+            new.set_migration_source(Some(accounts.eve)).unwrap();
+            assert_eq!(new.migrate_in(accounts.django, exported[0].1.clone()), Err(Error::Unauthorized));
+
+            set_caller::<DefaultEnvironment>(accounts.eve);
+            for (user, info) in exported.iter() {
+                assert_eq!(new.migrate_in(*user, info.clone()), Ok(()));
+            }
+            assert_eq!(
+                new.migrate_in(accounts.django, exported[0].1.clone()),
+                Err(Error::AlreadyMigrated)
+            );
+
+            assert_eq!((new.get_total_staked(), new.get_staker_count()), (old_total, old_count));
+            assert_eq!(new.get_staker_info(accounts.frank).amount, min * 3);
+            assert_eq!(new.get_pending_rewards(accounts.frank), 750_000);
+            assert_eq!(new.get_stakers_paged(0, 10).len(), 2);
+
+            // Imported stakers earn from the new contract's deposits in proportion
+            fund_rewards(&mut new, 400_000);
+            assert_eq!(new.get_pending_rewards(accounts.django), 100_000);
+            assert_eq!(new.get_pending_rewards(accounts.frank), 750_000 + 300_000);
+        }
+
+        #[ink::test]
+        fn migrated_pending_rewards_are_not_surplus() {
+            let (mut old, accounts) = create_contract();
+            let min = old.get_min_stake();
+            stake_as(&mut old, accounts.django, min);
+            fund_rewards(&mut old, 1_000_000);
+            set_caller::<DefaultEnvironment>(accounts.alice);
+            old.pause().unwrap();
+            let exported = old.export_staker(accounts.django).unwrap();
+
+            ink::env::test::set_callee::<DefaultEnvironment>(AccountId::from([0xAB; 32]));
+            let mut new = StakingManager::new(accounts.bob, accounts.charlie, min);
+            new.set_migration_source(Some(accounts.eve)).unwrap();
+            set_caller::<DefaultEnvironment>(accounts.eve);
+            new.migrate_in(accounts.django, exported).unwrap();
+            assert_eq!(new.get_undistributed_rewards(), 1_000_000);
+
+            // The LUSDT backing the imported rewards arrives; it is owed, not surplus
+            let this = AccountId::from([0xAB; 32]);
+            mock_psp22::set_balance(new.lusdt_token, this, 1_000_000);
+            set_caller::<DefaultEnvironment>(new.owner);
+            assert_eq!(new.sync_rewards(), Ok(0));
+            assert_eq!(new.get_reward_solvency(), (1_000_000, 1_000_000, true));
+
+            set_caller::<DefaultEnvironment>(accounts.django);
+            new.claim_rewards().unwrap();
+            assert_eq!(mock_psp22::balance_of(new.lusdt_token, accounts.django), 1_000_000);
+            assert_eq!(new.get_reward_solvency(), (0, 0, true));
+        }
+
+        #[ink::test]
+        fn voting_power_is_fixed_at_snapshot() {
+            let (mut contract, accounts) = create_contract();
+            let min = contract.get_min_stake();
+            stake_as(&mut contract, accounts.django, min * 2);
+
+            set_caller::<DefaultEnvironment>(accounts.eve);
+            assert_eq!(contract.snapshot(), Err(Error::Unauthorized));
+            set_caller::<DefaultEnvironment>(accounts.alice);
+            contract.set_governance(Some(accounts.eve)).unwrap();
+            set_caller::<DefaultEnvironment>(accounts.eve);
+            assert_eq!(contract.snapshot(), Ok(1));
+            assert_eq!(contract.total_staked_at(1), min * 2);
+
+            // Changes after the snapshot don't move it; late stakers have no power in it
+            set_caller::<DefaultEnvironment>(accounts.django);
+            contract.unstake_amount(min).unwrap();
+            stake_as(&mut contract, accounts.frank, min);
+            assert_eq!(contract.voting_power_at(accounts.django, 1), min * 2);
+            assert_eq!(contract.voting_power_at(accounts.frank, 1), 0);
+
+            // Only one checkpoint per snapshot, however many changes follow
+            set_caller::<DefaultEnvironment>(accounts.django);
+            contract.unstake().unwrap();
+            assert_eq!(contract.stake_checkpoint_count.get(accounts.django), Some(1));
+            assert_eq!(contract.voting_power_at(accounts.django, 1), min * 2);
+
+            set_caller::<DefaultEnvironment>(accounts.alice);
+            assert_eq!(contract.snapshot(), Ok(2));
+            assert_eq!(contract.voting_power_at(accounts.django, 2), 0);
+            assert_eq!(contract.voting_power_at(accounts.frank, 2), min);
+            assert_eq!(contract.voting_power_at(accounts.frank, 3), 0);
+        }
+
+        #[ink::test]
+        fn min_stake_duration_outlasts_cooldown() {
+            let (mut contract, accounts) = create_contract();
+            let amount = contract.get_min_stake();
+            set_caller::<DefaultEnvironment>(accounts.alice);
+            contract.set_cooldown(1_000).unwrap();
+            contract.set_min_stake_duration(10_000).unwrap();
+            assert_eq!(contract.get_min_stake_duration_ms(), 10_000);
+
+            ink::env::test::set_block_timestamp::<DefaultEnvironment>(100);
+            stake_as(&mut contract, accounts.django, amount * 2);
+
+            // Cooldown has passed, the minimum duration hasn't
+            ink::env::test::set_block_timestamp::<DefaultEnvironment>(5_000);
+            set_caller::<DefaultEnvironment>(accounts.django);
+            assert_eq!(contract.unstake_amount(amount), Err(Error::MinDurationNotMet));
+            assert_eq!(contract.unstake(), Err(Error::MinDurationNotMet));
+            assert_eq!(contract.emergency_unstake(), Err(Error::MinDurationNotMet));
+            assert_eq!(contract.get_staker_info(accounts.django).amount, amount * 2);
+
+            ink::env::test::set_block_timestamp::<DefaultEnvironment>(10_100);
+            assert_eq!(contract.unstake_amount(amount), Ok(()));
+            assert_eq!(contract.unstake(), Ok(()));
+        }
+
+        #[ink::test]
+        fn pool_unstake_waits_for_min_stake_duration() {
+            let (mut contract, accounts) = create_contract();
+            let amount = contract.get_min_stake();
+            set_caller::<DefaultEnvironment>(accounts.alice);
+            contract.set_min_stake_duration(10_000).unwrap();
+            let pool_id = contract.create_reward_pool().unwrap();
+
+            ink::env::test::set_block_timestamp::<DefaultEnvironment>(100);
+            mock_psp22::set_balance(contract.lunes_token, accounts.django, amount);
+            set_caller::<DefaultEnvironment>(accounts.django);
+            contract.stake_into_pool(pool_id, amount).unwrap();
+
+            ink::env::test::set_block_timestamp::<DefaultEnvironment>(5_000);
+            assert_eq!(contract.unstake_from_pool(pool_id), Err(Error::MinDurationNotMet));
+            assert_eq!(contract.get_pool_position(pool_id, accounts.django).amount, amount);
+
+            ink::env::test::set_block_timestamp::<DefaultEnvironment>(10_100);
+            assert_eq!(contract.unstake_from_pool(pool_id), Ok(()));
+            assert_eq!(mock_psp22::balance_of(contract.lunes_token, accounts.django), amount);
+        }
+
+        #[ink::test]
+        fn top_up_restarts_cooldown_but_keeps_staked_at() {
+            let (mut contract, accounts) = create_contract();
+            let amount = contract.get_min_stake();
+            set_caller::<DefaultEnvironment>(accounts.alice);
+            contract.set_cooldown(1_000).unwrap();
+
+            ink::env::test::set_block_timestamp::<DefaultEnvironment>(100);
+            stake_as(&mut contract, accounts.bob, amount * 2);
+            ink::env::test::set_block_timestamp::<DefaultEnvironment>(900);
+            stake_as(&mut contract, accounts.bob, amount);
+
+            let info = contract.get_staker_info(accounts.bob);
+            assert_eq!(info.staked_at, 100);
+            assert_eq!(info.last_stake_at, 900);
+
+            // Cooldown counts from the top-up, not the original entry
+            ink::env::test::set_block_timestamp::<DefaultEnvironment>(1_500);
+            set_caller::<DefaultEnvironment>(accounts.bob);
+            assert_eq!(contract.unstake(), Err(Error::CooldownNotElapsed));
+            assert_eq!(contract.unstake_amount(amount), Err(Error::CooldownNotElapsed));
+
+            ink::env::test::set_block_timestamp::<DefaultEnvironment>(1_900);
+            assert_eq!(contract.unstake_amount(amount), Ok(()));
+            assert_eq!(contract.get_staker_info(accounts.bob).staked_at, 100);
+        }
+
+        #[ink::test]
+        fn set_min_stake() {
+            let (mut contract, accounts) = create_contract();
+
+            set_caller::<DefaultEnvironment>(accounts.alice);
+            assert_eq!(contract.set_min_stake(200_000_000_000_000_000), Ok(()));
+            assert_eq!(contract.get_min_stake(), 200_000_000_000_000_000);
+        }
+
+        #[ink::test]
+        fn proposal_gate_follows_threshold() {
+            let (mut contract, accounts) = create_contract();
+            let min = contract.get_min_stake();
+            stake_as(&mut contract, accounts.django, min);
+            stake_as(&mut contract, accounts.eve, min * 5);
+
+            // Default: any active staker
+            assert!(contract.can_create_proposal(accounts.django));
+            assert!(!contract.can_create_proposal(accounts.frank));
+
+            set_caller::<DefaultEnvironment>(accounts.bob);
+            assert_eq!(contract.set_proposal_threshold(min * 2), Err(Error::Unauthorized));
+            set_caller::<DefaultEnvironment>(accounts.alice);
+            assert_eq!(contract.set_proposal_threshold(min * 2), Ok(()));
+            assert_eq!(contract.get_proposal_threshold(), min * 2);
+
+            assert!(!contract.can_create_proposal(accounts.django));
+            assert!(contract.can_create_proposal(accounts.eve));
+        }
+
+        #[ink::test]
+        fn lock_tier_multiplies_reward_weight() {
+            let (mut contract, accounts) = create_contract();
+            let min = contract.get_min_stake();
+            let lunes = contract.lunes_token;
+
+            mock_psp22::set_balance(lunes, accounts.django, min);
+            set_caller::<DefaultEnvironment>(accounts.django);
+            assert_eq!(contract.stake_locked(min, 4), Err(Error::InvalidLockTier));
+            assert_eq!(contract.stake_locked(min, 0), Err(Error::InvalidLockTier));
+            assert_eq!(contract.stake_locked(min, 3), Ok(())); // 180 days, 2x
+            let info = contract.get_staker_info(accounts.django);
+            assert_eq!((info.lock_tier, info.lock_until), (3, 180 * DAY_MS));
+            stake_as(&mut contract, accounts.eve, min);
+            assert_eq!(contract.get_total_staked(), min * 2);
+
+            // Same stake, same deposit: the 2x tier earns twice as much
+            fund_rewards(&mut contract, 3_000_000);
+            assert_eq!(contract.get_pending_rewards(accounts.django), 2_000_000);
+            assert_eq!(contract.get_pending_rewards(accounts.eve), 1_000_000);
+
+            // Locked until lock_until; shorter tiers can't replace a longer one
+            set_caller::<DefaultEnvironment>(accounts.django);
+            assert_eq!(contract.unstake(), Err(Error::StakeLocked));
+            assert_eq!(contract.unstake_amount(1), Err(Error::StakeLocked));
+            assert_eq!(contract.stake_locked(min, 1), Err(Error::InvalidLockTier));
+
+            ink::env::test::set_block_timestamp::<DefaultEnvironment>(180 * DAY_MS);
+            assert_eq!(contract.unstake(), Ok(()));
+            assert_eq!(mock_psp22::balance_of(lunes, accounts.django), min);
+            assert_eq!(contract.get_staker_info(accounts.django).lock_tier, 0);
+        }
+
+        #[ink::test]
+        fn locked_sub_pool_splits_deposits() {
+            let (mut contract, accounts) = create_contract();
+            let min = contract.get_min_stake();
+            let lunes = contract.lunes_token;
+            assert_eq!(contract.set_locked_pool_bps(10_001), Err(Error::InvalidConfig));
+            assert_eq!(contract.set_locked_pool_bps(4_000), Ok(()));
+
+            mock_psp22::set_balance(lunes, accounts.django, min);
+            set_caller::<DefaultEnvironment>(accounts.django);
+            contract.stake_locked(min, 3).unwrap(); // 2x
+            stake_as(&mut contract, accounts.eve, min);
+
+            // 40% to the locked sub-pool, 60% to the flexible one, regardless of the 2x weight
+            fund_rewards(&mut contract, 1_000_000);
+            assert_eq!(contract.get_pending_rewards(accounts.django), 400_000);
+            assert_eq!(contract.get_pending_rewards(accounts.eve), 600_000);
+
+            // Within the locked sub-pool, shares follow effective stake (2x vs 1.25x)
+            mock_psp22::set_balance(lunes, accounts.frank, min);
+            set_caller::<DefaultEnvironment>(accounts.frank);
+            contract.stake_locked(min, 1).unwrap();
+            fund_rewards(&mut contract, 650_000);
+            assert_eq!(contract.get_pending_rewards(accounts.django), 400_000 + 160_000);
+            assert_eq!(contract.get_pending_rewards(accounts.frank), 100_000);
+            assert_eq!(contract.get_pending_rewards(accounts.eve), 600_000 + 390_000);
+
+            // Can't fold locked positions back into the shared pool while they exist
+            set_caller::<DefaultEnvironment>(contract.owner);
+            assert_eq!(contract.set_locked_pool_bps(0), Err(Error::InvalidConfig));
+            assert_eq!(contract.set_locked_pool_bps(5_000), Ok(()));
+            assert_eq!(contract.get_locked_pool_bps(), 5_000);
+        }
+
+        #[ink::test]
+        fn emergency_unstake_ignores_lock() {
+            let (mut contract, accounts) = create_contract();
+            let min = contract.get_min_stake();
+            let lunes = contract.lunes_token;
+            mock_psp22::set_balance(lunes, accounts.django, min);
+            set_caller::<DefaultEnvironment>(accounts.django);
+            contract.stake_locked(min, 2).unwrap();
+
+            assert_eq!(contract.unstake(), Err(Error::StakeLocked));
+            assert_eq!(contract.emergency_unstake(), Ok(()));
+            assert_eq!(mock_psp22::balance_of(lunes, accounts.django), min);
+            assert_eq!(contract.get_staker_info(accounts.django).lock_until, 0);
+        }
+
+        #[ink::test]
+        fn tier_min_stake_overrides_global() {
+            let (mut contract, accounts) = create_contract();
+            let global = contract.get_min_stake();
+
+            // Unconfigured tiers fall back to the global minimum
+            assert_eq!(contract.get_tier_min_stake(2), global);
+
+            assert_eq!(contract.set_tier_min_stake(2, global * 5), Ok(()));
+            assert_eq!(contract.get_tier_min_stake(2), global * 5);
+            assert_eq!(contract.get_tier_min_stake(1), global);
+            assert_eq!(contract.get_min_stake(), global);
+
+            // Clearing restores the fallback
+            assert_eq!(contract.set_tier_min_stake(2, 0), Ok(()));
+            assert_eq!(contract.get_tier_min_stake(2), global);
+
+            set_caller::<DefaultEnvironment>(accounts.bob);
+            assert_eq!(contract.set_tier_min_stake(2, 1), Err(Error::Unauthorized));
+        }
+
+        #[ink::test]
+        fn reward_accounting_math() {
+            // Test the reward-per-token math with mock values
+            let (contract, _) = create_contract();
+
+            // No stakers, no rewards
+            assert_eq!(contract.get_reward_per_token(), 0);
+            assert_eq!(contract.get_total_rewards_deposited(), 0);
+            assert_eq!(contract.get_undistributed_rewards(), 0);
+        }
+
+        #[ink::test]
+        fn rewards_deposited_with_no_stakers_go_to_first_staker() {
+            let (mut contract, accounts) = create_contract();
+            let min = contract.get_min_stake();
+            fund_rewards(&mut contract, 1_000_000);
+            assert_eq!(contract.get_stranded_rewards(), 1_000_000);
+            assert_eq!(contract.get_reward_per_token(), 0);
+
+            stake_as(&mut contract, accounts.django, min);
+            assert_eq!(contract.get_stranded_rewards(), 0);
+            assert_eq!(contract.get_pending_rewards(accounts.django), 1_000_000);
+
+            // Later stakers don't share it
+            stake_as(&mut contract, accounts.eve, min);
+            assert_eq!(contract.get_pending_rewards(accounts.eve), 0);
+
+            set_caller::<DefaultEnvironment>(accounts.django);
+            assert_eq!(contract.claim_rewards(), Ok(()));
+            assert_eq!(mock_psp22::balance_of(contract.lusdt_token, accounts.django), 1_000_000);
+        }
+
+        #[ink::test]
+        fn get_pending_rewards_no_stake() {
+            let (contract, accounts) = create_contract();
+            assert_eq!(contract.get_pending_rewards(accounts.bob), 0);
+        }
+
+        #[ink::test]
+        fn frozen_staker_stops_accruing_but_can_unstake() {
+            let (mut contract, accounts) = create_contract();
+            let min = contract.get_min_stake();
+            stake_as(&mut contract, accounts.django, min);
+            stake_as(&mut contract, accounts.eve, min);
+            fund_rewards(&mut contract, 1_000_000);
+
+            set_caller::<DefaultEnvironment>(accounts.alice);
+            assert_eq!(contract.set_reward_frozen(accounts.django, true), Ok(()));
+            assert!(contract.is_reward_frozen(accounts.django));
+
+            // Rewards deposited during the freeze only accrue to eve's share
+            fund_rewards(&mut contract, 1_000_000);
+            assert_eq!(contract.get_pending_rewards(accounts.django), 500_000);
+            assert_eq!(contract.get_pending_rewards(accounts.eve), 1_000_000);
+
+            // The LUNES track is frozen too
+            mock_psp22::set_balance(contract.lunes_token, contract.owner, 1_000_000);
+            set_caller::<DefaultEnvironment>(contract.owner);
+            contract.deposit_lunes_rewards(1_000_000).unwrap();
+            assert_eq!(contract.get_pending_rewards_lunes(accounts.django), 0);
+            assert_eq!(contract.get_pending_rewards_lunes(accounts.eve), 500_000);
+
+            set_caller::<DefaultEnvironment>(accounts.django);
+            assert_eq!(contract.claim_rewards(), Err(Error::RewardsFrozen));
+
+            // Principal is still fully unstakeable; frozen rewards stay pending
+            assert_eq!(contract.unstake(), Ok(()));
+            assert_eq!(mock_psp22::balance_of(accounts.bob, accounts.django), min);
+            assert_eq!(mock_psp22::balance_of(accounts.charlie, accounts.django), 0);
+            assert_eq!(contract.get_staker_info(accounts.django).pending_rewards, 500_000);
+
+            // Django's forfeited LUNES went to eve when django settled
+            assert_eq!(contract.get_staker_info(accounts.django).pending_rewards_lunes, 0);
+            assert_eq!(contract.get_pending_rewards_lunes(accounts.eve), 1_000_000);
+        }
+
+        #[ink::test]
+        fn unfreeze_forfeits_accrual_during_freeze() {
+            let (mut contract, accounts) = create_contract();
+            let min = contract.get_min_stake();
+            stake_as(&mut contract, accounts.django, min);
+            stake_as(&mut contract, accounts.eve, min);
+
+            set_caller::<DefaultEnvironment>(accounts.alice);
+            contract.set_reward_frozen(accounts.django, true).unwrap();
+            fund_rewards(&mut contract, 1_000_000);
+
+            set_caller::<DefaultEnvironment>(accounts.alice);
+            contract.set_reward_frozen(accounts.django, false).unwrap();
+            assert_eq!(contract.get_pending_rewards(accounts.django), 0);
+
+            // Accrual resumes normally after the freeze is lifted
+            fund_rewards(&mut contract, 1_000_000);
+            assert_eq!(contract.get_pending_rewards(accounts.django), 500_000);
+
+            set_caller::<DefaultEnvironment>(accounts.django);
+            assert_eq!(contract.claim_rewards(), Ok(()));
+            assert_eq!(mock_psp22::balance_of(accounts.charlie, accounts.django), 500_000);
+        }
+
+        #[ink::test]
+        fn rescue_foreign_token_respects_protected_set() {
+            let (mut contract, accounts) = create_contract();
+            let this = AccountId::from(CONTRACT);
+            let foreign = AccountId::from([0x77; 32]);
+            mock_psp22::set_balance(foreign, this, 1_000);
+
+            assert_eq!(contract.rescue_foreign_token(foreign, accounts.alice, 1_000), Ok(()));
+            assert_eq!(mock_psp22::balance_of(foreign, accounts.alice), 1_000);
+
+            // Staked LUNES and reward LUSDT are off-limits
+            assert_eq!(
+                contract.rescue_foreign_token(contract.lunes_token, accounts.alice, 1),
+                Err(Error::ProtectedToken)
+            );
+            assert_eq!(
+                contract.rescue_foreign_token(contract.lusdt_token, accounts.alice, 1),
+                Err(Error::ProtectedToken)
+            );
+
+            set_caller::<DefaultEnvironment>(accounts.bob);
+            assert_eq!(contract.rescue_foreign_token(foreign, accounts.bob, 0), Err(Error::Unauthorized));
+        }
+
+        /// Alice stakes for the whole 100s period, Bob joins for the last 10s.
+        fn late_joiner_scenario(time_weighted: bool) -> (Balance, Balance) {
+            let (mut contract, accounts) = create_contract();
+            set_caller::<DefaultEnvironment>(accounts.alice);
+            contract.set_time_weighted_rewards(time_weighted).unwrap();
+            let min = contract.get_min_stake();
+
+            stake_as(&mut contract, accounts.eve, min);
+            ink::env::test::set_block_timestamp::<DefaultEnvironment>(90_000);
+            stake_as(&mut contract, accounts.frank, min);
+            ink::env::test::set_block_timestamp::<DefaultEnvironment>(100_000);
+            fund_rewards(&mut contract, 1_100_000);
+
+            (
+                contract.get_pending_rewards(accounts.eve),
+                contract.get_pending_rewards(accounts.frank),
+            )
+        }
+
+        #[ink::test]
+        fn instantaneous_distribution_ignores_time_held() {
+            assert_eq!(late_joiner_scenario(false), (550_000, 550_000));
+        }
+
+        #[ink::test]
+        fn time_weighted_distribution_rewards_consistency() {
+            // 100s vs 10s of equal stake: 10/11 and 1/11 of the deposit
+            assert_eq!(late_joiner_scenario(true), (1_000_000, 100_000));
+        }
+
+        #[ink::test]
+        fn time_weighted_settles_across_periods_and_unstake() {
+            let (mut contract, accounts) = create_contract();
+            contract.set_time_weighted_rewards(true).unwrap();
+            let min = contract.get_min_stake();
+
+            stake_as(&mut contract, accounts.eve, min);
+            stake_as(&mut contract, accounts.frank, min);
+
+            // Period 1: both hold for the full 50s
+            ink::env::test::set_block_timestamp::<DefaultEnvironment>(50_000);
+            fund_rewards(&mut contract, 1_000_000);
+
+            // Period 2: frank leaves halfway, eve stays
+            ink::env::test::set_block_timestamp::<DefaultEnvironment>(75_000);
+            set_caller::<DefaultEnvironment>(accounts.frank);
+            contract.unstake().unwrap();
+            assert_eq!(mock_psp22::balance_of(contract.lusdt_token, accounts.frank), 500_000);
+
+            ink::env::test::set_block_timestamp::<DefaultEnvironment>(100_000);
+            fund_rewards(&mut contract, 900_000);
+
+            // Stake-time in period 2: eve 50s, frank 25s → 600k / 300k
+            assert_eq!(contract.get_pending_rewards(accounts.eve), 500_000 + 600_000);
+            assert_eq!(contract.get_pending_rewards(accounts.frank), 300_000);
+
+            set_caller::<DefaultEnvironment>(accounts.frank);
+            assert_eq!(contract.claim_rewards(), Ok(()));
+            assert_eq!(mock_psp22::balance_of(contract.lusdt_token, accounts.frank), 800_000);
+        }
+
+        #[ink::test]
+        fn pauser_can_pause_but_not_unpause() {
+            let (mut contract, accounts) = create_contract();
+            set_caller::<DefaultEnvironment>(accounts.eve);
+            assert_eq!(contract.pause(), Err(Error::Unauthorized));
+
+            set_caller::<DefaultEnvironment>(accounts.alice);
+            contract.set_pauser(Some(accounts.eve)).unwrap();
+
+            set_caller::<DefaultEnvironment>(accounts.eve);
+            assert_eq!(contract.pause(), Ok(()));
+            assert!(contract.is_paused());
+            assert_eq!(contract.unpause(), Err(Error::Unauthorized));
+        }
+
+        #[ink::test]
+        fn would_distribute_nonzero_detects_dust() {
+            let (mut contract, accounts) = create_contract();
+            assert!(!contract.would_distribute_nonzero(1_000_000));
+
+            // 10^25 staked: an increment needs amount * 1e18 >= 1e25, i.e. amount >= 1e7
+            stake_as(&mut contract, accounts.django, 10_000_000_000_000_000_000_000_000);
+            assert!(!contract.would_distribute_nonzero(1));
+            assert!(!contract.would_distribute_nonzero(9_999_999));
+            assert!(contract.would_distribute_nonzero(10_000_000));
+            assert!(contract.would_distribute_nonzero(1_000_000_000));
+        }
+
+        #[ink::test]
+        fn top_up_with_auto_claim_pays_out_rewards() {
+            let (mut contract, accounts) = create_contract();
+            let min = contract.get_min_stake();
+            stake_as(&mut contract, accounts.django, min);
+            fund_rewards(&mut contract, 1_000_000);
+
+            set_caller::<DefaultEnvironment>(accounts.django);
+            contract.set_auto_claim_on_stake(true);
+            assert!(contract.get_auto_claim_on_stake(accounts.django));
+
+            stake_as(&mut contract, accounts.django, min);
+            assert_eq!(mock_psp22::balance_of(contract.lusdt_token, accounts.django), 1_000_000);
+            assert_eq!(contract.get_pending_rewards(accounts.django), 0);
+            assert_eq!(contract.get_total_rewards_claimed(), 1_000_000);
+        }
+
+        #[ink::test]
+        fn top_up_without_auto_claim_keeps_rewards_pending() {
+            let (mut contract, accounts) = create_contract();
+            let min = contract.get_min_stake();
+            stake_as(&mut contract, accounts.django, min);
+            fund_rewards(&mut contract, 1_000_000);
+
+            stake_as(&mut contract, accounts.django, min);
+            assert_eq!(mock_psp22::balance_of(contract.lusdt_token, accounts.django), 0);
+            assert_eq!(contract.get_pending_rewards(accounts.django), 1_000_000);
+        }
+
+        #[ink::test]
+        fn auto_compound_grows_stake_for_same_token_pool() {
+            let accounts = setup_accounts();
+            set_caller::<DefaultEnvironment>(accounts.alice);
+            ink::env::test::set_callee::<DefaultEnvironment>(AccountId::from(CONTRACT));
+            // LUNES-rewards LUNES-staking pool
+            let mut contract = StakingManager::new(accounts.bob, accounts.bob, 1_000);
+
+            stake_as(&mut contract, accounts.django, 1_000);
+            set_caller::<DefaultEnvironment>(accounts.django);
+            contract.set_auto_compound(true).unwrap();
+
+            fund_rewards(&mut contract, 500);
+            stake_as(&mut contract, accounts.django, 1_000);
+
+            // 1000 + 500 compounded + 1000 top-up; nothing left pending
+            assert_eq!(contract.get_staker_info(accounts.django).amount, 2_500);
+            assert_eq!(contract.get_total_staked(), 2_500);
+            assert_eq!(contract.get_pending_rewards(accounts.django), 0);
+
+            // A claim compounds instead of paying out
+            fund_rewards(&mut contract, 250);
+            set_caller::<DefaultEnvironment>(accounts.django);
+            assert_eq!(contract.claim_rewards(), Ok(()));
+            assert_eq!(contract.get_staker_info(accounts.django).amount, 2_750);
+            assert_eq!(contract.get_pending_rewards(accounts.django), 0);
+        }
+
+        #[ink::test]
+        fn auto_compound_requires_same_token() {
+            let (mut contract, accounts) = create_contract();
+            set_caller::<DefaultEnvironment>(accounts.django);
+            assert_eq!(contract.set_auto_compound(true), Err(Error::InvalidConfig));
+            assert!(!contract.get_auto_compound(accounts.django));
+        }
+
+        #[ink::test]
+        fn notification_pref_set_and_read() {
+            let (mut contract, accounts) = create_contract();
+            assert!(!contract.get_notification_pref(accounts.django));
+
+            let events_before = ink::env::test::recorded_events().count();
+            set_caller::<DefaultEnvironment>(accounts.django);
+            contract.set_notification_pref(true);
+            assert!(contract.get_notification_pref(accounts.django));
+            assert_eq!(ink::env::test::recorded_events().count(), events_before + 1);
+
+            // Preferences are per caller
+            assert!(!contract.get_notification_pref(accounts.eve));
+
+            contract.set_notification_pref(false);
+            assert!(!contract.get_notification_pref(accounts.django));
+            assert_eq!(ink::env::test::recorded_events().count(), events_before + 2);
+        }
+
+        #[ink::test]
+        fn unboosted_claims_count_as_base_rewards() {
+            let (mut contract, accounts) = create_contract();
+            let min = contract.get_min_stake();
+            stake_as(&mut contract, accounts.django, min);
+            fund_rewards(&mut contract, 1_000_000);
+
+            let events_before = ink::env::test::recorded_events().count();
+            set_caller::<DefaultEnvironment>(accounts.django);
+            assert_eq!(contract.claim_rewards(), Ok(()));
+
+            assert_eq!(contract.get_total_base_rewards(), 1_000_000);
+            assert_eq!(contract.get_total_boosted_rewards(), 0);
+            // Only RewardsClaimed — no RewardBoostApplied for a plain claim
+            assert_eq!(ink::env::test::recorded_events().count(), events_before + 1);
+        }
+
+        #[ink::test]
+        fn boosted_claims_split_counters_and_emit_event() {
+            let (mut contract, accounts) = create_contract();
+            let min = contract.get_min_stake();
+            mock_psp22::set_balance(contract.lunes_token, accounts.django, min);
+            set_caller::<DefaultEnvironment>(accounts.django);
+            contract.stake_locked(min, 2).unwrap(); // 90 days, 1.5x
+            fund_rewards(&mut contract, 1_500_000);
+
+            // A third of a 1.5x claim is the boost
+            let events_before = ink::env::test::recorded_events().count();
+            set_caller::<DefaultEnvironment>(accounts.django);
+            assert_eq!(contract.claim_rewards(), Ok(()));
+
+            assert_eq!(contract.get_total_rewards_claimed(), 1_500_000);
+            assert_eq!(contract.get_total_base_rewards(), 1_000_000);
+            assert_eq!(contract.get_total_boosted_rewards(), 500_000);
+            // RewardBoostApplied plus RewardsClaimed
+            assert_eq!(ink::env::test::recorded_events().count(), events_before + 2);
+        }
+
+        #[ink::test]
+        fn claim_fails_when_reward_balance_short() {
+            let (mut contract, accounts) = create_contract();
+            let min = contract.get_min_stake();
+            stake_as(&mut contract, accounts.django, min);
+            fund_rewards(&mut contract, 1_000_000);
+
+            // Accounting drifted: contract only holds part of what it owes
+            mock_psp22::set_balance(accounts.charlie, AccountId::from(CONTRACT), 400_000);
+
+            set_caller::<DefaultEnvironment>(accounts.django);
+            assert_eq!(contract.claim_rewards(), Err(Error::InsufficientRewardBalance));
+            assert_eq!(contract.get_pending_rewards(accounts.django), 1_000_000);
+        }
+
+        #[ink::test]
+        fn stake_for_batch_credits_each_beneficiary() {
+            let (mut contract, accounts) = create_contract();
+            let min = contract.get_min_stake();
+            stake_as(&mut contract, accounts.django, min);
+            fund_rewards(&mut contract, 1_000_000);
+
+            // Treasury (frank) funds three positions at once
+            mock_psp22::set_balance(accounts.bob, accounts.frank, 4 * min);
+            set_caller::<DefaultEnvironment>(accounts.frank);
+            let entries = vec![
+                (accounts.django, min),
+                (accounts.eve, 2 * min),
+                (accounts.alice, min),
+            ];
+            assert_eq!(contract.stake_for_batch(entries), Ok(3));
+
+            assert_eq!(contract.get_staker_info(accounts.django).amount, 2 * min);
+            assert_eq!(contract.get_staker_info(accounts.eve).amount, 2 * min);
+            assert_eq!(contract.get_staker_info(accounts.alice).amount, min);
+            assert_eq!(contract.get_total_staked(), 5 * min);
+            assert_eq!(contract.get_staker_count(), 3);
+            assert_eq!(mock_psp22::balance_of(accounts.bob, accounts.frank), 0);
+
+            // Django's earlier rewards were settled before the top-up
+            assert_eq!(contract.get_staker_info(accounts.django).pending_rewards, 1_000_000);
+        }
+
+        #[ink::test]
+        fn stake_for_batch_does_not_extend_beneficiary_cooldown() {
+            let (mut contract, accounts) = create_contract();
+            let min = contract.get_min_stake();
+            contract.set_cooldown(1_000).unwrap();
+            ink::env::test::set_block_timestamp::<DefaultEnvironment>(100);
+            stake_as(&mut contract, accounts.django, min);
+
+            // A third party tops the victim up with dust just before the cooldown ends
+            ink::env::test::set_block_timestamp::<DefaultEnvironment>(1_000);
+            mock_psp22::set_balance(accounts.bob, accounts.frank, 1);
+            set_caller::<DefaultEnvironment>(accounts.frank);
+            assert_eq!(contract.stake_for_batch(vec![(accounts.django, 1)]), Ok(1));
+            assert_eq!(contract.get_staker_info(accounts.django).last_stake_at, 100);
+
+            ink::env::test::set_block_timestamp::<DefaultEnvironment>(1_100);
+            set_caller::<DefaultEnvironment>(accounts.django);
+            assert_eq!(contract.unstake(), Ok(()));
+        }
+
+        #[ink::test]
+        fn stake_for_batch_validates_entries() {
+            let (mut contract, accounts) = create_contract();
+            let min = contract.get_min_stake();
+            mock_psp22::set_balance(accounts.bob, accounts.frank, 10 * min);
+            set_caller::<DefaultEnvironment>(accounts.frank);
+
+            assert_eq!(contract.stake_for_batch(vec![]), Err(Error::InvalidBatchSize));
+            let oversized = vec![(accounts.eve, min); MAX_BATCH_SIZE + 1];
+            assert_eq!(contract.stake_for_batch(oversized), Err(Error::InvalidBatchSize));
+
+            // One beneficiary below the minimum rejects the whole batch
+            let entries = vec![(accounts.django, min), (accounts.eve, min - 1)];
+            assert_eq!(contract.stake_for_batch(entries), Err(Error::BelowMinimumStake));
+
+            // Each beneficiary once, each with something to stake
+            let entries = vec![(accounts.django, min), (accounts.eve, min), (accounts.django, min)];
+            assert_eq!(contract.stake_for_batch(entries), Err(Error::DuplicateBeneficiary));
+            let entries = vec![(accounts.django, min), (accounts.eve, 0)];
+            assert_eq!(contract.stake_for_batch(entries), Err(Error::ZeroAmount));
+
+            // The batch total must fit alongside what is already staked
+            let entries = vec![(accounts.django, Balance::MAX), (accounts.eve, min)];
+            assert_eq!(contract.stake_for_batch(entries), Err(Error::ArithmeticOverflow));
+            assert_eq!(contract.get_total_staked(), 0);
+            assert_eq!(mock_psp22::balance_of(accounts.bob, accounts.frank), 10 * min);
+        }
+
+        #[ink::test]
+        fn ema_reward_rate_smooths_spiky_deposits() {
+            let (mut contract, _) = create_contract();
+            let day = DAY_MS;
+
+            // 1M/day, then a 10M spike, then back to 1M/day
+            ink::env::test::set_block_timestamp::<DefaultEnvironment>(day);
+            fund_rewards(&mut contract, 1_000_000);
+            assert_eq!(contract.get_ema_reward_rate(), 1_000_000);
+
+            ink::env::test::set_block_timestamp::<DefaultEnvironment>(2 * day);
+            fund_rewards(&mut contract, 10_000_000);
+            // 20% * 10M + 80% * 1M — far below the raw 10M/day spike
+            assert_eq!(contract.get_ema_reward_rate(), 2_800_000);
+
+            ink::env::test::set_block_timestamp::<DefaultEnvironment>(3 * day);
+            fund_rewards(&mut contract, 1_000_000);
+            // 20% * 1M + 80% * 2.8M — decays gradually instead of dropping to 1M
+            assert_eq!(contract.get_ema_reward_rate(), 2_440_000);
+        }
+
+        #[ink::test]
+        fn claim_frequency_limit_preserves_accrual() {
+            let (mut contract, accounts) = create_contract();
+            let min = contract.get_min_stake();
+            let lusdt = contract.lusdt_token;
+            assert_eq!(contract.set_claim_frequency(2, 0), Err(Error::InvalidConfig));
+            assert_eq!(contract.set_claim_frequency(2, DAY_MS), Ok(()));
+            stake_as(&mut contract, accounts.django, min);
+
+            for _ in 0..2 {
+                fund_rewards(&mut contract, 100_000);
+                set_caller::<DefaultEnvironment>(accounts.django);
+                assert_eq!(contract.claim_rewards(), Ok(()));
+            }
+            assert_eq!(contract.get_claims_in_window(accounts.django), 2);
+
+            // Allowance used up: claiming is blocked but rewards keep accruing
+            fund_rewards(&mut contract, 100_000);
+            fund_rewards(&mut contract, 50_000);
+            set_caller::<DefaultEnvironment>(accounts.django);
+            assert_eq!(contract.claim_rewards(), Err(Error::ClaimFrequencyExceeded));
+            assert_eq!(contract.get_pending_rewards(accounts.django), 150_000);
+
+            // New window
+            ink::env::test::set_block_timestamp::<DefaultEnvironment>(DAY_MS);
+            assert_eq!(contract.get_claims_in_window(accounts.django), 0);
+            assert_eq!(contract.claim_rewards(), Ok(()));
+            assert_eq!(mock_psp22::balance_of(lusdt, accounts.django), 350_000);
+        }
+
+        #[ink::test]
+        fn split_deposit_streams_half_over_duration() {
+            let (mut contract, accounts) = create_contract();
+            let min = contract.get_min_stake();
+            assert_eq!(contract.get_reward_split(), (10_000, 7 * DAY_MS));
+            assert_eq!(contract.set_reward_split(10_001, 1_000), Err(Error::InvalidConfig));
+            assert_eq!(contract.set_reward_split(5_000, 0), Err(Error::InvalidConfig));
+            assert_eq!(contract.set_reward_split(5_000, 1_000), Ok(()));
+
+            stake_as(&mut contract, accounts.django, min);
+            fund_rewards(&mut contract, 2_000_000);
+
+            // Half at once, the other half linearly over 1s
+            assert_eq!(contract.get_pending_rewards(accounts.django), 1_000_000);
+            ink::env::test::set_block_timestamp::<DefaultEnvironment>(500);
+            assert_eq!(contract.get_pending_rewards(accounts.django), 1_500_000);
+
+            // A staker joining mid-stream only gets what streams after they join
+            stake_as(&mut contract, accounts.eve, min);
+            ink::env::test::set_block_timestamp::<DefaultEnvironment>(1_000);
+            assert_eq!(contract.get_pending_rewards(accounts.django), 1_750_000);
+            assert_eq!(contract.get_pending_rewards(accounts.eve), 250_000);
+
+            // Nothing more after the stream ends
+            ink::env::test::set_block_timestamp::<DefaultEnvironment>(5_000);
+            assert_eq!(contract.get_pending_rewards(accounts.django), 1_750_000);
+            set_caller::<DefaultEnvironment>(accounts.django);
+            assert_eq!(contract.claim_rewards(), Ok(()));
+            assert_eq!(mock_psp22::balance_of(contract.lusdt_token, accounts.django), 1_750_000);
+        }
+
+        #[ink::test]
+        fn reward_deposit_pause_keeps_stream_running() {
+            let (mut contract, accounts) = create_contract();
+            let min = contract.get_min_stake();
+            assert_eq!(contract.set_reward_split(0, 1_000), Ok(()));
+            stake_as(&mut contract, accounts.django, min);
+            fund_rewards(&mut contract, 1_000_000);
+
+            ink::env::test::set_block_timestamp::<DefaultEnvironment>(250);
+            set_caller::<DefaultEnvironment>(accounts.bob);
+            assert_eq!(contract.set_reward_deposits_paused(true), Err(Error::Unauthorized));
+            set_caller::<DefaultEnvironment>(contract.owner);
+            assert_eq!(contract.set_reward_deposits_paused(true), Ok(()));
+            assert!(contract.is_reward_deposits_paused());
+
+            // New deposits are rejected on every entry point
+            assert_eq!(contract._notify_reward_amount(1_000), Err(Error::RewardDepositsPaused));
+            assert_eq!(contract._deposit_rewards(1_000), Err(Error::RewardDepositsPaused));
+            assert_eq!(contract.notify_pool_reward_amount(0, 1_000), Err(Error::RewardDepositsPaused));
+
+            // The in-flight stream keeps accruing and can be claimed
+            ink::env::test::set_block_timestamp::<DefaultEnvironment>(500);
+            assert_eq!(contract.get_pending_rewards(accounts.django), 500_000);
+            set_caller::<DefaultEnvironment>(accounts.django);
+            assert_eq!(contract.claim_rewards(), Ok(()));
+            ink::env::test::set_block_timestamp::<DefaultEnvironment>(1_000);
+            assert_eq!(contract.get_pending_rewards(accounts.django), 500_000);
+            // Staking itself is not paused
+            stake_as(&mut contract, accounts.eve, min);
+            assert_eq!(mock_psp22::balance_of(contract.lusdt_token, accounts.django), 500_000);
+        }
+
+        #[ink::test]
+        fn pending_over_cap_is_auto_claimed_on_next_stake() {
+            let (mut contract, accounts) = create_contract();
+            let min = contract.get_min_stake();
+            let lusdt = contract.lusdt_token;
+            assert_eq!(contract.get_max_pending_rewards(), 0);
+            assert_eq!(contract.set_max_pending_rewards(500_000), Ok(()));
+
+            stake_as(&mut contract, accounts.django, min);
+            fund_rewards(&mut contract, 400_000);
+            // At or under the cap nothing is paid out
+            stake_as(&mut contract, accounts.django, min);
+            assert_eq!(mock_psp22::balance_of(lusdt, accounts.django), 0);
+            assert_eq!(contract.get_pending_rewards(accounts.django), 400_000);
+
+            fund_rewards(&mut contract, 200_000);
+            stake_as(&mut contract, accounts.django, min);
+            assert_eq!(mock_psp22::balance_of(lusdt, accounts.django), 600_000);
+            assert_eq!(contract.get_pending_rewards(accounts.django), 0);
+
+            // Partial unstakes enforce the cap too
+            fund_rewards(&mut contract, 900_000);
+            set_caller::<DefaultEnvironment>(accounts.django);
+            assert_eq!(contract.unstake_amount(min), Ok(()));
+            assert_eq!(mock_psp22::balance_of(lusdt, accounts.django), 1_500_000);
+            assert_eq!(contract.get_pending_rewards(accounts.django), 0);
+        }
+
+        #[ink::test]
+        fn trait_calls_surface_protocol_errors() {
+            let (mut contract, accounts) = create_contract();
+
+            set_caller::<DefaultEnvironment>(accounts.bob);
+            assert_eq!(
+                StakingManagerApi::deposit_rewards(&mut contract, 1_000),
+                Err(ProtocolError::Unauthorized)
+            );
+
+            set_caller::<DefaultEnvironment>(contract.owner);
+            assert_eq!(
+                StakingManagerApi::notify_reward_amount(&mut contract, 0),
+                Err(ProtocolError::InvalidInput)
+            );
+            contract.set_reward_deposits_paused(true).unwrap();
+            assert_eq!(
+                StakingManagerApi::notify_reward_amount(&mut contract, 1_000),
+                Err(ProtocolError::Paused)
+            );
+        }
+
+        #[ink::test]
+        fn reward_rate_averages_recent_days() {
+            let (mut contract, accounts) = create_contract();
+            assert_eq!(contract.get_reward_rate_per_day(), 0);
+            assert_eq!(contract.get_estimated_apr_bps(), 0);
+
+            // Deposits with nobody staked still count toward the rate
+            ink::env::test::set_block_timestamp::<DefaultEnvironment>(DAY_MS);
+            fund_rewards(&mut contract, 700_000_000_000_000);
+            assert_eq!(contract.get_estimated_apr_bps(), 0);
+
+            let min = contract.get_min_stake();
+            stake_as(&mut contract, accounts.bob, min);
+            ink::env::test::set_block_timestamp::<DefaultEnvironment>(DAY_MS + DAY_MS / 2);
+            fund_rewards(&mut contract, 700_000_000_000_000); // same day bucket
+            ink::env::test::set_block_timestamp::<DefaultEnvironment>(3 * DAY_MS);
+            fund_rewards(&mut contract, 1_400_000_000_000_000);
+            assert_eq!(contract.get_reward_rate_per_day(), 400_000_000_000_000); // 28e14 / 7 days
+
+            // Day 1 rolls out of the window on day 8; day 3 still counts
+            ink::env::test::set_block_timestamp::<DefaultEnvironment>(8 * DAY_MS);
+            assert_eq!(contract.get_reward_rate_per_day(), 200_000_000_000_000);
+
+            // Day 8 reuses day 1's slot
+            fund_rewards(&mut contract, 700_000_000_000_000);
+            assert_eq!(contract.get_reward_rate_per_day(), 300_000_000_000_000);
+        }
+
+        #[ink::test]
+        fn estimated_apr_follows_ema_reward_rate() {
+            let (mut contract, accounts) = create_contract();
+            let min = contract.get_min_stake();
+            stake_as(&mut contract, accounts.bob, min);
+
+            // One day's deposit: the EMA reads 1e14/day, the 7-day window only 1e14/7
+            ink::env::test::set_block_timestamp::<DefaultEnvironment>(DAY_MS);
+            fund_rewards(&mut contract, 100_000_000_000_000);
+            assert_eq!(contract.get_ema_reward_rate(), 100_000_000_000_000);
+            assert_eq!(contract.get_reward_rate_per_day(), 14_285_714_285_714);
+
+            // 1e14/day * 365 * 10_000 / 1e17 staked
+            assert_eq!(contract.get_estimated_apr_bps(), 3_650);
+        }
+
+        #[ink::test]
+        fn ema_alpha_validation() {
+            let (mut contract, accounts) = create_contract();
+            assert_eq!(contract.set_ema_alpha_bps(0), Err(Error::InvalidConfig));
+            assert_eq!(contract.set_ema_alpha_bps(10_001), Err(Error::InvalidConfig));
+            assert_eq!(contract.set_ema_alpha_bps(5_000), Ok(()));
+            assert_eq!(contract.get_ema_alpha_bps(), 5_000);
+
+            set_caller::<DefaultEnvironment>(accounts.bob);
+            assert_eq!(contract.set_ema_alpha_bps(1_000), Err(Error::Unauthorized));
+        }
+
+        #[ink::test]
+        fn buyback_share_is_swapped_and_burned() {
+            let (mut contract, accounts) = create_contract();
+            let router = accounts.eve;
+            let burn_engine = accounts.frank;
+            let this = AccountId::from(CONTRACT);
+
+            // Router holds LUNES liquidity and pays 2 LUNES per LUSDT
+            mock_psp22::set_balance(contract.lunes_token, router, 1_000_000_000);
+            mock_router::set_rate(2);
+
+            set_caller::<DefaultEnvironment>(accounts.alice);
+            contract
+                .set_buyback_config(2_500, Some(router), Some(burn_engine))
+                .unwrap();
+            contract.set_swap_slippage(2 * PRECISION, 100).unwrap();
+
+            let min = contract.get_min_stake();
+            stake_as(&mut contract, accounts.django, min);
+            fund_rewards(&mut contract, 1_000_000);
+
+            // 25% swapped: LUSDT went to the router, LUNES to the BurnEngine
+            assert_eq!(contract.get_buyback_totals(), (250_000, 500_000));
+            assert_eq!(mock_psp22::balance_of(contract.lusdt_token, router), 250_000);
+            assert_eq!(mock_psp22::balance_of(contract.lunes_token, burn_engine), 500_000);
+            assert_eq!(mock_psp22::balance_of(contract.lusdt_token, this), 750_000);
+
+            // Stakers only receive the remaining 75%
+            assert_eq!(contract.get_total_rewards_deposited(), 750_000);
+            assert_eq!(contract.get_pending_rewards(accounts.django), 750_000);
+        }
+
+        #[ink::test]
+        fn buyback_rejects_swap_below_slippage_bound() {
+            let (mut contract, accounts) = create_contract();
+            let router = accounts.eve;
+            let burn_engine = accounts.frank;
+
+            mock_psp22::set_balance(contract.lunes_token, router, 1_000_000_000);
+            set_caller::<DefaultEnvironment>(accounts.alice);
+            contract
+                .set_buyback_config(2_500, Some(router), Some(burn_engine))
+                .unwrap();
+            let min = contract.get_min_stake();
+            stake_as(&mut contract, accounts.django, min);
+
+            let lusdt = contract.lusdt_token;
+            mock_psp22::set_balance(lusdt, AccountId::from(CONTRACT), 1_000_000);
+            set_caller::<DefaultEnvironment>(accounts.alice);
+
+            // No reference rate yet: swaps are refused outright
+            mock_router::set_rate(2);
+            assert_eq!(contract._notify_reward_amount(1_000_000), Err(Error::SlippageExceeded));
+
+            // Expecting 3 LUNES per LUSDT with 10% slippage, a 2:1 fill is too far below
+            assert_eq!(contract.set_swap_slippage(3 * PRECISION, 10_001), Err(Error::InvalidConfig));
+            contract.set_swap_slippage(3 * PRECISION, 1_000).unwrap();
+            assert_eq!(contract.get_swap_slippage(), (3 * PRECISION, 1_000));
+            assert_eq!(contract._notify_reward_amount(1_000_000), Err(Error::SlippageExceeded));
+            assert_eq!(contract.get_buyback_totals(), (0, 0));
+
+            // A fill within the bound goes through
+            mock_router::set_rate(3);
+            mock_psp22::set_balance(lusdt, AccountId::from(CONTRACT), 1_000_000);
+            assert_eq!(contract._notify_reward_amount(1_000_000), Ok(()));
+            assert_eq!(contract.get_buyback_totals(), (250_000, 750_000));
+
+            set_caller::<DefaultEnvironment>(accounts.bob);
+            assert_eq!(contract.set_swap_slippage(PRECISION, 100), Err(Error::Unauthorized));
+        }
+
+        #[ink::test]
+        fn claim_and_notify_without_target_is_plain_claim() {
+            let (mut contract, accounts) = create_contract();
+            let min = contract.get_min_stake();
+            stake_as(&mut contract, accounts.django, min);
+            fund_rewards(&mut contract, 1_000_000);
+
+            set_caller::<DefaultEnvironment>(accounts.django);
+            assert_eq!(contract.claim_and_notify(), Ok(()));
+            assert_eq!(mock_psp22::balance_of(contract.lusdt_token, accounts.django), 1_000_000);
+            assert_eq!(contract.get_pending_rewards(accounts.django), 0);
+        }
+
+        #[ink::test]
+        fn claim_and_notify_forwards_to_target() {
+            let (mut contract, accounts) = create_contract();
+            let router = accounts.eve;
+            let min = contract.get_min_stake();
+            let lunes_before = mock_psp22::balance_of(contract.lunes_token, accounts.django);
+
+            // Router holds LUNES liquidity and pays 3 LUNES per LUSDT
+            mock_psp22::set_balance(contract.lunes_token, router, 1_000_000_000);
+            mock_router::set_rate(3);
+            set_caller::<DefaultEnvironment>(accounts.bob);
+            assert_eq!(contract.set_auto_compound_target(Some(router)), Err(Error::Unauthorized));
+            set_caller::<DefaultEnvironment>(accounts.alice);
+            contract.set_auto_compound_target(Some(router)).unwrap();
+            assert_eq!(contract.get_auto_compound_target(), Some(router));
+            contract.set_swap_slippage(3 * PRECISION, 100).unwrap();
+
+            stake_as(&mut contract, accounts.django, min);
+            fund_rewards(&mut contract, 1_000_000);
+
+            set_caller::<DefaultEnvironment>(accounts.django);
+            assert_eq!(contract.claim_and_notify(), Ok(()));
+            // LUSDT went to the router, LUNES came back to the staker
+            assert_eq!(mock_psp22::balance_of(contract.lusdt_token, router), 1_000_000);
+            assert_eq!(mock_psp22::balance_of(contract.lusdt_token, accounts.django), 0);
+            assert_eq!(
+                mock_psp22::balance_of(contract.lunes_token, accounts.django),
+                lunes_before + 3_000_000
+            );
+            assert_eq!(contract.get_total_rewards_claimed(), 1_000_000);
+            assert_eq!(contract.claim_and_notify(), Err(Error::NoRewardsToClaim));
+        }
+
+        #[ink::test]
+        fn claim_and_notify_rejects_swap_below_slippage_bound() {
+            let (mut contract, accounts) = create_contract();
+            let router = accounts.eve;
+            let min = contract.get_min_stake();
 
-        // ═══════════════════════════════════════════════════════════════
-        // ADMIN — Limited powers (CANNOT withdraw funds)
-        // ═══════════════════════════════════════════════════════════════
+            mock_psp22::set_balance(contract.lunes_token, router, 1_000_000_000);
+            mock_router::set_rate(2);
+            set_caller::<DefaultEnvironment>(accounts.alice);
+            contract.set_auto_compound_target(Some(router)).unwrap();
+            // Expecting 3 LUNES per LUSDT within 5%, a 2:1 fill is rejected
+            contract.set_swap_slippage(3 * PRECISION, 500).unwrap();
+            stake_as(&mut contract, accounts.django, min);
+            fund_rewards(&mut contract, 1_000_000);
 
-        /// Set the authorized depositor (Tax Manager contract address).
-        /// Only owner. This address can call deposit_rewards / notify_reward_amount.
-        #[ink(message)]
-        pub fn set_authorized_depositor(&mut self, depositor: AccountId) -> Result<(), Error> {
-            self.ensure_owner()?;
-            self.authorized_depositor = Some(depositor);
-            self.env().emit_event(AdminUpdated {
-                name: "AuthorizedDepositor".into(),
-            });
-            Ok(())
+            set_caller::<DefaultEnvironment>(accounts.django);
+            assert_eq!(contract.claim_and_notify(), Err(Error::SlippageExceeded));
         }
 
-        /// Set unstake cooldown period in milliseconds. 0 = no cooldown.
-        /// Only owner.
-        #[ink(message)]
-        pub fn set_cooldown(&mut self, cooldown_ms: u64) -> Result<(), Error> {
-            self.ensure_owner()?;
-            self.unstake_cooldown_ms = cooldown_ms;
-            self.env().emit_event(AdminUpdated {
-                name: "Cooldown".into(),
-            });
-            Ok(())
-        }
+        #[ink::test]
+        fn repair_staker_fixes_inverted_snapshot() {
+            let (mut contract, accounts) = create_contract();
+            let min = contract.get_min_stake();
+            stake_as(&mut contract, accounts.django, min);
+            fund_rewards(&mut contract, 1_000_000);
+            set_caller::<DefaultEnvironment>(accounts.django);
+            contract.claim_rewards().unwrap();
 
-        /// Pause the contract (blocks new stakes, but allows unstake + claim).
-        /// Only owner.
-        #[ink(message)]
-        pub fn pause(&mut self) -> Result<(), Error> {
-            self.ensure_owner()?;
-            self.paused = true;
-            self.env().emit_event(AdminUpdated {
-                name: "Paused".into(),
-            });
-            Ok(())
-        }
+            // Corrupt the snapshot so it runs ahead of the accumulator
+            let mut info = contract.get_staker_info(accounts.django);
+            info.reward_per_token_paid = contract.reward_per_token_stored + PRECISION;
+            contract.stakers.insert(accounts.django, &info);
+            fund_rewards(&mut contract, 1_000_000);
+            set_caller::<DefaultEnvironment>(accounts.django);
+            assert_eq!(contract.claim_rewards(), Err(Error::ArithmeticOverflow));
 
-        /// Unpause the contract.
-        /// Only owner.
-        #[ink(message)]
-        pub fn unpause(&mut self) -> Result<(), Error> {
-            self.ensure_owner()?;
-            self.paused = false;
-            self.env().emit_event(AdminUpdated {
-                name: "Unpaused".into(),
-            });
-            Ok(())
+            assert_eq!(contract.repair_staker(accounts.django), Err(Error::Unauthorized));
+            set_caller::<DefaultEnvironment>(contract.owner);
+            assert_eq!(contract.repair_staker(accounts.eve), Err(Error::NoActiveStake));
+            assert_eq!(contract.repair_staker(accounts.django), Ok(()));
+            assert_eq!(
+                contract.get_staker_info(accounts.django).reward_per_token_paid,
+                contract.get_reward_per_token()
+            );
+
+            // Staking and claiming work again from here on
+            stake_as(&mut contract, accounts.django, min);
+            fund_rewards(&mut contract, 1_000_000);
+            set_caller::<DefaultEnvironment>(accounts.django);
+            assert_eq!(contract.claim_rewards(), Ok(()));
+            assert_eq!(mock_psp22::balance_of(contract.lusdt_token, accounts.django), 2_000_000);
         }
 
-        /// Update minimum stake requirement. Only owner.
-        #[ink(message)]
-        pub fn set_min_stake(&mut self, new_min: Balance) -> Result<(), Error> {
-            self.ensure_owner()?;
-            self.min_stake = new_min;
-            self.env().emit_event(AdminUpdated {
-                name: "MinStake".into(),
-            });
-            Ok(())
+        #[ink::test]
+        fn deposit_history_pages_in_order() {
+            let (mut contract, accounts) = create_contract();
+            let min = contract.get_min_stake();
+            stake_as(&mut contract, accounts.django, min);
+
+            for i in 1..=5u64 {
+                ink::env::test::set_block_timestamp::<DefaultEnvironment>(i * 1_000);
+                fund_rewards(&mut contract, i as u128 * 1_000);
+            }
+            assert_eq!(contract.get_deposit_history_count(), 5);
+
+            let first = contract.get_deposit_history(0, 3);
+            assert_eq!(first.len(), 3);
+            assert_eq!(first[0].0, 1_000);
+            assert_eq!(first[0].1, accounts.alice);
+            assert_eq!(first[0].2, 1_000);
+            assert_eq!(first[2].2, 3_000);
+
+            let rest = contract.get_deposit_history(3, 3);
+            assert_eq!(rest.len(), 2);
+            assert_eq!(rest[1].2, 5_000);
+            // Accumulator snapshot after the last deposit matches the live value
+            assert_eq!(rest[1].3, contract.get_reward_per_token());
+            assert!(rest[0].3 < rest[1].3);
+
+            assert!(contract.get_deposit_history(5, 10).is_empty());
         }
 
-        /// Upgradeable contract: set new code hash. Only owner.
-        #[ink(message)]
-        pub fn set_code(&mut self, code_hash: Hash) -> Result<(), Error> {
-            self.ensure_owner()?;
-            self.env().set_code_hash(&code_hash).unwrap_or_else(|err| {
-                panic!(
-                    "Failed to `set_code_hash` to {:?} due to {:?}",
-                    code_hash, err
-                )
-            });
-            Ok(())
+        #[ink::test]
+        fn deposit_history_ring_buffer_drops_oldest() {
+            let (mut contract, _) = create_contract();
+            for i in 0..(MAX_DEPOSIT_HISTORY + 2) {
+                contract._record_deposit(contract.owner, i as u128 + 1);
+            }
+            assert_eq!(contract.get_deposit_history_count(), MAX_DEPOSIT_HISTORY + 2);
+
+            // The two oldest entries were overwritten
+            let page = contract.get_deposit_history(0, 1);
+            assert_eq!(page[0].2, 3);
+            let last = contract.get_deposit_history(MAX_DEPOSIT_HISTORY - 1, 5);
+            assert_eq!(last.len(), 1);
+            assert_eq!(last[0].2, MAX_DEPOSIT_HISTORY as u128 + 2);
         }
 
-        // ─── Internal Helpers ────────────────────────────────────────
+        #[ink::test]
+        fn buyback_config_validation() {
+            let (mut contract, accounts) = create_contract();
+            assert_eq!(contract.get_buyback_config(), (0, None, None));
 
-        fn ensure_owner(&self) -> Result<(), Error> {
-            if self.env().caller() != self.owner {
+            // Non-zero share needs both router and burn engine
+            assert_eq!(
+                contract.set_buyback_config(1_000, Some(accounts.eve), None),
+                Err(Error::InvalidConfig)
+            );
+            assert_eq!(
+                contract.set_buyback_config(10_001, Some(accounts.eve), Some(accounts.frank)),
+                Err(Error::InvalidConfig)
+            );
+            assert_eq!(contract.set_buyback_config(0, None, None), Ok(()));
+
+            set_caller::<DefaultEnvironment>(accounts.bob);
+            assert_eq!(
+                contract.set_buyback_config(1_000, Some(accounts.eve), Some(accounts.frank)),
                 Err(Error::Unauthorized)
-            } else {
-                Ok(())
-            }
+            );
         }
 
-        fn ensure_not_paused(&self) -> Result<(), Error> {
-            if self.paused {
-                Err(Error::ContractPaused)
-            } else {
-                Ok(())
-            }
+        #[ink::test]
+        fn only_owner_can_freeze_rewards() {
+            let (mut contract, accounts) = create_contract();
+            set_caller::<DefaultEnvironment>(accounts.bob);
+            assert_eq!(
+                contract.set_reward_frozen(accounts.django, true),
+                Err(Error::Unauthorized)
+            );
+            assert!(!contract.is_reward_frozen(accounts.django));
         }
 
-        fn ensure_authorized_depositor(&self) -> Result<(), Error> {
-            let caller = self.env().caller();
-            if caller == self.owner {
-                return Ok(());
-            }
-            if let Some(depositor) = self.authorized_depositor {
-                if caller == depositor {
-                    return Ok(());
-                }
-            }
-            Err(Error::Unauthorized)
-        }
-    }
+        #[ink::test]
+        fn unstake_amount_partial_dust_and_full() {
+            let (mut contract, accounts) = create_contract();
+            let min = contract.get_min_stake();
+            let lunes = contract.lunes_token;
+            stake_as(&mut contract, accounts.bob, min * 3);
+            fund_rewards(&mut contract, 90_000);
 
-    // ─── Unit Tests ─────────────────────────────────────────────────
+            // Partial: keeps the position and its staked_at, rewards stay pending
+            set_caller::<DefaultEnvironment>(accounts.bob);
+            let staked_at = contract.get_staker_info(accounts.bob).staked_at;
+            let pending = contract.get_pending_rewards(accounts.bob);
+            assert!(pending > 0);
+            assert_eq!(contract.unstake_amount(min), Ok(()));
+            let info = contract.get_staker_info(accounts.bob);
+            assert_eq!(info.amount, min * 2);
+            assert_eq!(info.staked_at, staked_at);
+            assert_eq!(info.pending_rewards, pending);
+            assert_eq!(contract.get_total_staked(), min * 2);
+            assert_eq!(contract.get_staker_count(), 1);
+            assert_eq!(mock_psp22::balance_of(lunes, accounts.bob), min);
 
-    #[cfg(test)]
-    mod tests {
-        use super::*;
-        use ink::env::{
-            test::{set_caller, DefaultAccounts},
-            DefaultEnvironment,
-        };
+            // Leaving less than the minimum is rejected
+            assert_eq!(contract.unstake_amount(min + 1), Err(Error::BelowMinimumStake));
+            assert_eq!(contract.unstake_amount(min * 2 + 1), Err(Error::InsufficientStake));
+            assert_eq!(contract.unstake_amount(0), Err(Error::ZeroAmount));
 
-        fn setup_accounts() -> DefaultAccounts<DefaultEnvironment> {
-            ink::env::test::default_accounts::<DefaultEnvironment>()
+            // Full amount exits the position
+            assert_eq!(contract.unstake_amount(min * 2), Ok(()));
+            assert_eq!(contract.get_staker_info(accounts.bob).amount, 0);
+            assert_eq!(contract.get_total_staked(), 0);
+            assert_eq!(contract.get_staker_count(), 0);
+            assert_eq!(mock_psp22::balance_of(lunes, accounts.bob), min * 3);
         }
 
-        fn create_contract() -> (StakingManager, DefaultAccounts<DefaultEnvironment>) {
-            let accounts = setup_accounts();
+        #[ink::test]
+        fn unstake_amount_keeps_the_lock_tier_minimum() {
+            let (mut contract, accounts) = create_contract();
+            let min = contract.get_min_stake();
             set_caller::<DefaultEnvironment>(accounts.alice);
+            contract.set_tier_min_stake(1, min * 2).unwrap();
 
-            let min_stake: Balance = 100_000_000_000_000_000; // 100k LUNES (12 decimals)
-            let contract = StakingManager::new(
-                accounts.bob,     // lunes_token (mock)
-                accounts.charlie, // lusdt_token (mock)
-                min_stake,
-            );
+            mock_psp22::set_balance(contract.lunes_token, accounts.django, min * 4);
+            set_caller::<DefaultEnvironment>(accounts.django);
+            contract.stake_locked(min * 4, 1).unwrap();
+            ink::env::test::set_block_timestamp::<DefaultEnvironment>(LOCK_TIERS[1].0);
 
-            (contract, accounts)
+            // Above the global minimum but below tier 1's
+            assert_eq!(contract.unstake_amount(min * 2 + 1), Err(Error::BelowMinimumStake));
+            assert_eq!(contract.unstake_amount(min * 2), Ok(()));
+            assert_eq!(contract.get_staker_info(accounts.django).amount, min * 2);
         }
 
         #[ink::test]
-        fn constructor_works() {
-            let (contract, accounts) = create_contract();
-            assert_eq!(contract.get_owner(), accounts.alice);
-            assert_eq!(contract.get_total_staked(), 0);
-            assert_eq!(contract.get_staker_count(), 0);
-            assert_eq!(contract.get_min_stake(), 100_000_000_000_000_000);
-            assert!(!contract.is_paused());
+        fn unstake_amount_compounds_before_shrinking() {
+            let accounts = setup_accounts();
+            set_caller::<DefaultEnvironment>(accounts.alice);
+            ink::env::test::set_callee::<DefaultEnvironment>(AccountId::from(CONTRACT));
+            let mut contract = StakingManager::new(accounts.bob, accounts.bob, 1_000);
+
+            stake_as(&mut contract, accounts.django, 2_000);
+            set_caller::<DefaultEnvironment>(accounts.django);
+            contract.set_auto_compound(true).unwrap();
+            fund_rewards(&mut contract, 500);
+
+            // The compounded 500 counts toward the remainder: 2_500 - 1_500 = 1_000
+            set_caller::<DefaultEnvironment>(accounts.django);
+            assert_eq!(contract.unstake_amount(1_500), Ok(()));
+            assert_eq!(contract.get_staker_info(accounts.django).amount, 1_000);
+            assert_eq!(contract.get_total_staked(), 1_000);
+            assert_eq!(contract.get_pending_rewards(accounts.django), 0);
         }
 
         #[ink::test]
-        fn staker_info_default() {
-            let (contract, accounts) = create_contract();
-            let info = contract.get_staker_info(accounts.bob);
+        fn emergency_unstake_bypasses_cooldown_and_forfeits_rewards() {
+            let (mut contract, accounts) = create_contract();
+            let min = contract.get_min_stake();
+            let lunes = contract.lunes_token;
+            set_caller::<DefaultEnvironment>(accounts.alice);
+            contract.set_cooldown(86_400_000).unwrap();
+            stake_as(&mut contract, accounts.django, min);
+            stake_as(&mut contract, accounts.eve, min);
+            fund_rewards(&mut contract, 1_000_000);
+
+            set_caller::<DefaultEnvironment>(accounts.django);
+            assert_eq!(contract.unstake(), Err(Error::CooldownNotElapsed));
+            let rpt_before = contract.get_reward_per_token();
+            assert_eq!(contract.emergency_unstake(), Ok(()));
+
+            assert_eq!(mock_psp22::balance_of(lunes, accounts.django), min);
+            let info = contract.get_staker_info(accounts.django);
             assert_eq!(info.amount, 0);
             assert_eq!(info.pending_rewards, 0);
+            assert_eq!(contract.get_total_staked(), min);
+            assert_eq!(contract.get_staker_count(), 1);
+
+            // django's 500_000 is folded back in for eve
+            assert!(contract.get_reward_per_token() > rpt_before);
+            assert_eq!(contract.get_pending_rewards(accounts.eve), 1_000_000);
+            assert_eq!(contract.emergency_unstake(), Err(Error::NoActiveStake));
         }
 
         #[ink::test]
-        fn admin_functions_require_owner() {
+        fn claim_with_callback_notifies_strategy_contract() {
             let (mut contract, accounts) = create_contract();
+            let amount = contract.get_min_stake();
+            let lusdt = contract.lusdt_token;
+            mock_strategy::register(accounts.django, false);
+            mock_strategy::register(accounts.eve, true);
+            stake_as(&mut contract, accounts.django, amount);
+            stake_as(&mut contract, accounts.eve, amount);
+            stake_as(&mut contract, accounts.frank, amount);
+            fund_rewards(&mut contract, 300_000);
 
-            // Non-owner cannot pause
-            set_caller::<DefaultEnvironment>(accounts.bob);
-            assert_eq!(contract.pause(), Err(Error::Unauthorized));
+            set_caller::<DefaultEnvironment>(accounts.django);
+            assert_eq!(contract.claim_rewards_with_callback(vec![1, 2, 3]), Ok(()));
+            assert_eq!(mock_strategy::received(), vec![(accounts.django, 100_000, vec![1, 2, 3])]);
 
-            // Owner can pause
-            set_caller::<DefaultEnvironment>(accounts.alice);
-            assert_eq!(contract.pause(), Ok(()));
-            assert!(contract.is_paused());
+            // A reverting callback doesn't undo the claim
+            set_caller::<DefaultEnvironment>(accounts.eve);
+            assert_eq!(contract.claim_rewards_with_callback(vec![]), Ok(()));
+            assert_eq!(mock_psp22::balance_of(lusdt, accounts.eve), 100_000);
 
-            // Owner can unpause
-            assert_eq!(contract.unpause(), Ok(()));
-            assert!(!contract.is_paused());
+            // Plain accounts just claim
+            set_caller::<DefaultEnvironment>(accounts.frank);
+            assert_eq!(contract.claim_rewards_with_callback(vec![9]), Ok(()));
+            assert_eq!(mock_psp22::balance_of(lusdt, accounts.frank), 100_000);
+            assert_eq!(mock_strategy::received().len(), 1);
+
+            assert_eq!(contract.claim_rewards_with_callback(vec![]), Err(Error::NoRewardsToClaim));
         }
 
         #[ink::test]
-        fn set_authorized_depositor() {
+        fn recompute_staker_count_fixes_drift() {
             let (mut contract, accounts) = create_contract();
+            let amount = contract.get_min_stake();
+            stake_as(&mut contract, accounts.django, amount);
+            stake_as(&mut contract, accounts.eve, amount);
+            stake_as(&mut contract, accounts.frank, amount);
+            set_caller::<DefaultEnvironment>(accounts.eve);
+            contract.unstake().unwrap();
+            // Re-staking a zeroed position must not be indexed twice
+            stake_as(&mut contract, accounts.eve, amount);
+            set_caller::<DefaultEnvironment>(accounts.frank);
+            contract.unstake().unwrap();
+            assert_eq!(contract.staker_index_len, 2);
 
-            assert_eq!(contract.get_authorized_depositor(), None);
-
+            // Owner only
+            assert_eq!(contract.recompute_staker_count(0, 10), Err(Error::Unauthorized));
             set_caller::<DefaultEnvironment>(accounts.alice);
+
+            // Correct count is left alone
+            let events_before = ink::env::test::recorded_events().count();
+            assert_eq!(contract.recompute_staker_count(0, 10), Ok(2));
+            assert_eq!(ink::env::test::recorded_events().count(), events_before);
+
+            // Simulate drift; pages must follow on from each other
+            contract.staker_count = 7;
+            assert_eq!(contract.recompute_staker_count(0, 0), Err(Error::InvalidBatchSize));
+            assert_eq!(contract.recompute_staker_count(0, 1), Ok(1));
+            assert_eq!(contract.get_staker_count(), 7);
+            assert_eq!(contract.recompute_staker_count(2, 1), Err(Error::InvalidConfig));
+            assert_eq!(contract.recompute_staker_count(1, 1), Ok(2));
+            assert_eq!(contract.get_staker_count(), 2);
+            assert_eq!(ink::env::test::recorded_events().count(), events_before + 1);
+        }
+
+        #[ink::test]
+        fn stakers_paged_stays_dense_on_exit() {
+            let (mut contract, accounts) = create_contract();
+            let min = contract.get_min_stake();
+            stake_as(&mut contract, accounts.django, min);
+            stake_as(&mut contract, accounts.eve, min * 2);
+            stake_as(&mut contract, accounts.frank, min * 3);
+            stake_as(&mut contract, accounts.django, min); // top-up, not re-indexed
+
             assert_eq!(
-                contract.set_authorized_depositor(accounts.django),
-                Ok(())
+                contract.get_stakers_paged(0, 2),
+                vec![(accounts.django, min * 2), (accounts.eve, min * 2)]
             );
+            assert_eq!(contract.get_stakers_paged(2, 2), vec![(accounts.frank, min * 3)]);
+            assert!(contract.get_stakers_paged(3, 2).is_empty());
+
+            // Removing the middle staker moves the last one into its slot
+            set_caller::<DefaultEnvironment>(accounts.eve);
+            contract.unstake().unwrap();
             assert_eq!(
-                contract.get_authorized_depositor(),
-                Some(accounts.django)
+                contract.get_stakers_paged(0, 10),
+                vec![(accounts.django, min * 2), (accounts.frank, min * 3)]
             );
+
+            set_caller::<DefaultEnvironment>(accounts.django);
+            contract.emergency_unstake().unwrap();
+            assert_eq!(contract.get_stakers_paged(0, 10), vec![(accounts.frank, min * 3)]);
+            set_caller::<DefaultEnvironment>(accounts.alice);
+            assert_eq!(contract.recompute_staker_count(0, 10), Ok(1));
         }
 
         #[ink::test]
-        fn set_cooldown() {
+        fn reconcile_recovers_rounding_dust() {
             let (mut contract, accounts) = create_contract();
+            let amount = contract.get_min_stake();
+            let stakers = [accounts.django, accounts.eve, accounts.frank];
+            for user in stakers {
+                stake_as(&mut contract, user, amount);
+            }
 
-            set_caller::<DefaultEnvironment>(accounts.alice);
-            assert_eq!(contract.set_cooldown(86_400_000), Ok(())); // 24 hours
-            assert_eq!(contract.get_cooldown_ms(), 86_400_000);
+            // 1_000_000 split three ways loses one unit per deposit+claim round
+            for _ in 0..10 {
+                fund_rewards(&mut contract, 1_000_000);
+                for user in stakers {
+                    set_caller::<DefaultEnvironment>(user);
+                    contract.claim_rewards().unwrap();
+                }
+            }
+            assert_eq!(contract.get_total_rewards_claimed(), 9_999_990);
+            assert_eq!(contract.get_reward_dust(), 10);
+
+            assert_eq!(contract.reconcile_rewards(), Ok(9));
+            for user in stakers {
+                assert_eq!(contract.get_pending_rewards(user), 3);
+                set_caller::<DefaultEnvironment>(user);
+                contract.claim_rewards().unwrap();
+            }
+            assert_eq!(contract.get_total_rewards_claimed(), 9_999_999);
+            assert_eq!(contract.get_reward_dust(), 1);
         }
 
         #[ink::test]
-        fn set_min_stake() {
+        fn insurance_claims_pay_pro_rata_while_open() {
             let (mut contract, accounts) = create_contract();
+            let amount = contract.get_min_stake();
+            let lusdt = contract.lusdt_token;
+            stake_as(&mut contract, accounts.django, amount);
+            stake_as(&mut contract, accounts.eve, amount * 3);
 
+            mock_psp22::set_balance(lusdt, accounts.alice, 1_000_000);
             set_caller::<DefaultEnvironment>(accounts.alice);
-            assert_eq!(contract.set_min_stake(200_000_000_000_000_000), Ok(()));
-            assert_eq!(contract.get_min_stake(), 200_000_000_000_000_000);
+            assert!(contract.deposit_insurance(1_000_000).is_ok());
+
+            // Closed by default
+            set_caller::<DefaultEnvironment>(accounts.django);
+            assert_eq!(contract.file_insurance_claim(), Err(Error::InsuranceClaimsDisabled));
+
+            set_caller::<DefaultEnvironment>(accounts.alice);
+            assert_eq!(contract.open_insurance_claims("".into()), Err(Error::InvalidConfig));
+            assert_eq!(contract.open_insurance_claims("Bridge shortfall, incident #1".into()), Ok(1));
+
+            // A top-up after the window opened doesn't raise the payout
+            stake_as(&mut contract, accounts.django, amount * 4);
+            assert_eq!(contract.file_insurance_claim(), Ok(250_000));
+            assert_eq!(contract.file_insurance_claim(), Err(Error::InsuranceAlreadyClaimed));
+            set_caller::<DefaultEnvironment>(accounts.eve);
+            assert_eq!(contract.file_insurance_claim(), Ok(750_000));
+
+            assert_eq!(mock_psp22::balance_of(lusdt, accounts.django), 250_000);
+            assert_eq!(contract.get_insurance_claim(1, accounts.eve), 750_000);
+            assert_eq!(contract.get_insurance_state(), (true, 0, 1));
+
+            // Stakers joining after the window opened are not covered
+            ink::env::test::set_block_timestamp::<DefaultEnvironment>(1_000);
+            stake_as(&mut contract, accounts.frank, amount);
+            assert_eq!(contract.file_insurance_claim(), Err(Error::NoActiveStake));
+
+            set_caller::<DefaultEnvironment>(accounts.alice);
+            assert!(contract.close_insurance_claims().is_ok());
+            set_caller::<DefaultEnvironment>(accounts.frank);
+            assert_eq!(contract.file_insurance_claim(), Err(Error::InsuranceClaimsDisabled));
+        }
+
+        /// Send `amount` mock LUSDT to the contract and notify pool `pool_id` as the owner.
+        fn fund_pool(contract: &mut StakingManager, pool_id: u32, amount: Balance) {
+            let lusdt = contract.lusdt_token;
+            let this = AccountId::from(CONTRACT);
+            mock_psp22::set_balance(lusdt, this, mock_psp22::balance_of(lusdt, this) + amount);
+            set_caller::<DefaultEnvironment>(contract.owner);
+            contract.notify_pool_reward_amount(pool_id, amount).unwrap();
         }
 
         #[ink::test]
-        fn reward_accounting_math() {
-            // Test the reward-per-token math with mock values
-            let (contract, _) = create_contract();
+        fn reward_pools_are_independent() {
+            let (mut contract, accounts) = create_contract();
+            let amount = contract.get_min_stake();
+            let lunes = contract.lunes_token;
+            let lusdt = contract.lusdt_token;
 
-            // No stakers, no rewards
-            assert_eq!(contract.get_reward_per_token(), 0);
-            assert_eq!(contract.get_total_rewards_deposited(), 0);
-            assert_eq!(contract.get_undistributed_rewards(), 0);
+            let flexible = contract.create_reward_pool().unwrap();
+            let locked = contract.create_reward_pool().unwrap();
+            assert_eq!((flexible, locked), (1, 2));
+            assert_eq!(contract.get_reward_pool_count(), 2);
+
+            for (user, pool_id) in [(accounts.django, flexible), (accounts.eve, locked), (accounts.frank, locked)] {
+                mock_psp22::set_balance(lunes, user, amount);
+                set_caller::<DefaultEnvironment>(user);
+                contract.stake_into_pool(pool_id, amount).unwrap();
+            }
+            assert_eq!(contract.get_reward_pool(locked).unwrap().total_staked, amount * 2);
+            assert_eq!(contract.get_total_staked(), 0); // main pool untouched
+
+            fund_pool(&mut contract, flexible, 100_000);
+            fund_pool(&mut contract, locked, 1_000_000);
+            assert_eq!(contract.get_pool_pending_rewards(flexible, accounts.django), 100_000);
+            assert_eq!(contract.get_pool_pending_rewards(locked, accounts.eve), 500_000);
+            assert_eq!(contract.get_pool_pending_rewards(flexible, accounts.eve), 0);
+
+            set_caller::<DefaultEnvironment>(accounts.django);
+            assert_eq!(contract.claim_pool_rewards(flexible), Ok(()));
+            assert_eq!(mock_psp22::balance_of(lusdt, accounts.django), 100_000);
+            assert_eq!(contract.claim_pool_rewards(locked), Err(Error::NoActiveStake));
+
+            set_caller::<DefaultEnvironment>(accounts.eve);
+            assert_eq!(contract.unstake_from_pool(locked), Ok(()));
+            assert_eq!(mock_psp22::balance_of(lusdt, accounts.eve), 500_000);
+            assert_eq!(mock_psp22::balance_of(lunes, accounts.eve), amount);
+            assert_eq!(contract.get_reward_pool(locked).unwrap().total_staked, amount);
+
+            // A failed reward payout on pool exit is escrowed, not lost or left behind
+            fund_pool(&mut contract, locked, 200_000);
+            mock_psp22::reject_next_transfer(lusdt);
+            set_caller::<DefaultEnvironment>(accounts.frank);
+            assert_eq!(contract.unstake_from_pool(locked), Ok(()));
+            assert_eq!(contract.get_escrowed_rewards(accounts.frank), 700_000);
+            assert_eq!(contract.get_pool_position(locked, accounts.frank).pending_rewards, 0);
+            assert_eq!(mock_psp22::balance_of(lunes, accounts.frank), amount);
         }
 
         #[ink::test]
-        fn get_pending_rewards_no_stake() {
-            let (contract, accounts) = create_contract();
-            assert_eq!(contract.get_pending_rewards(accounts.bob), 0);
+        fn reward_pool_queues_deposits_until_staked() {
+            let (mut contract, accounts) = create_contract();
+            let amount = contract.get_min_stake();
+            let pool_id = contract.create_reward_pool().unwrap();
+
+            fund_pool(&mut contract, pool_id, 300_000);
+            assert_eq!(contract.get_reward_pool(pool_id).unwrap().queued_rewards, 300_000);
+
+            mock_psp22::set_balance(contract.lunes_token, accounts.django, amount);
+            set_caller::<DefaultEnvironment>(accounts.django);
+            contract.stake_into_pool(pool_id, amount).unwrap();
+            fund_pool(&mut contract, pool_id, 100_000);
+
+            assert_eq!(contract.get_reward_pool(pool_id).unwrap().queued_rewards, 0);
+            assert_eq!(contract.get_pool_pending_rewards(pool_id, accounts.django), 400_000);
+        }
+
+        #[ink::test]
+        fn unknown_reward_pool_is_rejected() {
+            let (mut contract, accounts) = create_contract();
+            set_caller::<DefaultEnvironment>(accounts.django);
+            assert_eq!(contract.stake_into_pool(7, 1), Err(Error::PoolNotFound));
+            set_caller::<DefaultEnvironment>(accounts.alice);
+            assert_eq!(contract.notify_pool_reward_amount(7, 1), Err(Error::PoolNotFound));
+
+            set_caller::<DefaultEnvironment>(accounts.bob);
+            assert_eq!(contract.create_reward_pool(), Err(Error::Unauthorized));
         }
     }
 }
@@ -20,9 +20,9 @@ export interface LusdtTokenContract {
 
 export interface TaxManagerContract {
   // Processamento de taxas
-  processFees: (operation: 'Mint' | 'Burn', user: string, amount: string) => Promise<string>;
+  processFees: (operation: 'Mint' | 'Burn' | 'Transfer', user: string, amount: string) => Promise<string>;
   processFeesFlexible: (
-    operation: 'Mint' | 'Burn', 
+    operation: 'Mint' | 'Burn' | 'Transfer', 
     user: string, 
     amount: string, 
     feeType: 'Lunes' | 'Lusdt' | 'Usdt'
@@ -68,10 +68,11 @@ export interface RedemptionRequestedEvent {
   solanaRecipientAddress: string;
   requestId: string;
   blockTimestamp: number;
+  formatVersion: number;
 }
 
 export interface FeesProcessedEvent {
-  operation: 'Mint' | 'Burn';
+  operation: 'Mint' | 'Burn' | 'Transfer';
   user: string;
   lusdtAmount: string;
   feeInLunes: string;